@@ -0,0 +1,99 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+/// `System` allocator keeping track of the currently allocated
+/// and the peak bytes, to be installed by a bin as
+/// 
+/// ```text
+/// #[global_allocator]
+/// static ALLOCATOR: chapter2::CountingAllocator = chapter2::CountingAllocator;
+/// ```
+/// 
+/// so that `EquationExperimentStat::peak_memory_bytes` is reported
+pub struct CountingAllocator;
+
+static INSTALLED: AtomicBool = AtomicBool::new(false);
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+thread_local! {
+    // bytes allocated minus those deallocated by this thread (negative by freeing the others'),
+    // so that `with_peak_memory` is not disturbed by the trials run in parallel
+    static THREAD_CURRENT: Cell<isize> = const { Cell::new(0) };
+    static THREAD_PEAK: Cell<isize> = const { Cell::new(0) };
+    static THREAD_ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+fn add_thread_bytes(bytes: isize) {
+    // `try_with` as it may be called while the thread-locals are destroyed
+    let _ = THREAD_CURRENT.try_with(|current| {
+        current.set(current.get() + bytes);
+        let _ = THREAD_PEAK.try_with(|peak| peak.set(peak.get().max(current.get())));
+    });
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            INSTALLED.store(true, Ordering::Relaxed);
+            let current = CURRENT.fetch_add(layout.size(), Ordering::Relaxed) + layout.size();
+            PEAK.fetch_max(current, Ordering::Relaxed);
+            add_thread_bytes(layout.size() as isize);
+            let _ = THREAD_ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+        }
+        ptr
+    }
+    
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+        CURRENT.fetch_sub(layout.size(), Ordering::Relaxed);
+        add_thread_bytes(-(layout.size() as isize));
+    }
+}
+
+impl CountingAllocator {
+    /// whether `CountingAllocator` is the global allocator (and has allocated at least once)
+    pub fn is_installed() -> bool {
+        INSTALLED.load(Ordering::Relaxed)
+    }
+    
+    /// bytes currently allocated
+    pub fn current_bytes() -> usize {
+        CURRENT.load(Ordering::Relaxed)
+    }
+    
+    /// maximum of `current_bytes` since the last `reset_peak`
+    pub fn peak_bytes() -> usize {
+        PEAK.load(Ordering::Relaxed)
+    }
+    
+    pub fn reset_peak() {
+        PEAK.store(CURRENT.load(Ordering::Relaxed), Ordering::Relaxed);
+    }
+}
+
+/// What `f` of `with_allocations` allocated on this thread
+pub(crate) struct Allocations {
+    /// the peak bytes on top of those allocated before
+    pub(crate) peak_bytes: usize,
+    /// the number of allocations (each reallocation as one)
+    pub(crate) count: usize,
+}
+
+/// `f()` with its `Allocations`, or `None` if `CountingAllocator` is not installed
+pub(crate) fn with_allocations<F, R>(f: F) -> (R, Option<Allocations>)
+where
+    F: FnOnce() -> R,
+{
+    let baseline = THREAD_CURRENT.with(Cell::get);
+    THREAD_PEAK.with(|peak| peak.set(baseline));
+    let count_before = THREAD_ALLOCATIONS.with(Cell::get);
+    let result = f();
+    let allocations = Allocations {
+        peak_bytes: (THREAD_PEAK.with(Cell::get) - baseline).max(0) as usize,
+        count: THREAD_ALLOCATIONS.with(Cell::get) - count_before,
+    };
+    (result, CountingAllocator::is_installed().then_some(allocations))
+}
@@ -0,0 +1,166 @@
+use crate::{Float, Scalar, Matrix, Vector};
+
+/// N x N banded matrix of `T` (`f64` by default) storing only the
+/// `lower` subdiagonals, the diagonal, and the `upper` superdiagonals:
+/// 
+/// ```text
+/// a_{ij} = 0  unless  -lower <= j - i <= upper
+/// ```
+/// 
+/// row by row in N (lower + 1 + upper) entries, e.g. lower = upper = 1
+/// for the tridiagonal systems of discretized second-order ODEs.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BandedMatrix<const N: usize, T = f64> {
+    lower: usize,
+    upper: usize,
+    /// `(i, j)` is at `i * (lower + 1 + upper) + (j + lower - i)`
+    bands: Vec<T>,
+}
+
+impl<const N: usize, T: Scalar> BandedMatrix<N, T> {
+    pub fn zeroed(lower: usize, upper: usize) -> Self {
+        Self { lower, upper, bands: vec![T::ZERO; N * (lower + 1 + upper)] }
+    }
+    
+    /// `f(i, j)` is called only for the positions inside the band
+    pub fn from_fn(lower: usize, upper: usize, mut f: impl FnMut(usize, usize) -> T) -> Self {
+        let mut banded = Self::zeroed(lower, upper);
+        for i in 0..N {
+            for j in banded.band_columns(i) {
+                banded[(i, j)] = f(i, j);
+            }
+        }
+        banded
+    }
+    
+    /// Toeplitz matrix with `a` on the subdiagonal,
+    /// `b` on the diagonal, and `c` on the superdiagonal,
+    /// i.e. `test_matrices::tridiagonal` without the zeros
+    pub fn tridiagonal(a: T, b: T, c: T) -> Self {
+        Self::from_fn(1, 1, |i, j| if i == j { b } else if i > j { a } else { c })
+    }
+    
+    /// Discard the entries outside the band of `dense`
+    pub fn from_dense(dense: &Matrix<N, N, T>, lower: usize, upper: usize) -> Self {
+        Self::from_fn(lower, upper, |i, j| dense[(i, j)])
+    }
+    
+    /// number of subdiagonals
+    pub fn lower(&self) -> usize {
+        self.lower
+    }
+    /// number of superdiagonals
+    pub fn upper(&self) -> usize {
+        self.upper
+    }
+    
+    fn width(&self) -> usize {
+        self.lower + 1 + self.upper
+    }
+    
+    /// columns j of the i-th row inside the band
+    pub fn band_columns(&self, i: usize) -> std::ops::Range<usize> {
+        i.saturating_sub(self.lower)..(i + self.upper + 1).min(N)
+    }
+    
+    fn is_in_band(&self, i: usize, j: usize) -> bool {
+        i < N && j < N && j + self.lower >= i && j <= i + self.upper
+    }
+    
+    /// a_{ij}, or zero outside the band
+    pub fn get(&self, i: usize, j: usize) -> T {
+        if self.is_in_band(i, j) { self[(i, j)] } else { T::ZERO }
+    }
+    
+    pub fn to_dense(&self) -> Matrix<N, N, T> {
+        Matrix::<N, N, T>::from_fn(|i, j| self.get(i, j))
+    }
+    
+    pub fn transpose(&self) -> Self {
+        Self::from_fn(self.upper, self.lower, |i, j| self[(j, i)])
+    }
+    
+    /// Solve Ax = d for a tridiagonal A (`lower` = `upper` = 1) by `thomas`
+    pub fn solve_tridiagonal(&self, d: &Vector<N, T>) -> Option<Vector<N, T>> {
+        assert!(self.lower == 1 && self.upper == 1, "Matrix is not tridiagonal");
+        let lower = Vector::<N, T>::from_fn(|i, _| if i > 0 { self.get(i, i - 1) } else { T::ZERO });
+        let diagonal = Vector::<N, T>::from_fn(|i, _| self[(i, i)]);
+        let upper = Vector::<N, T>::from_fn(|i, _| if i + 1 < N { self.get(i, i + 1) } else { T::ZERO });
+        thomas(&lower, &diagonal, &upper, d)
+    }
+}
+
+/// Solve the tridiagonal system
+/// 
+/// ```text
+/// a_i x_{i-1} + b_i x_i + c_i x_{i+1} = d_i  (a_0 and c_{N-1} unused)
+/// ```
+/// 
+/// in O(N) by the Thomas algorithm, i.e. Gaussian elimination without pivoting:
+/// 
+/// ```text
+/// c'_i = c_i / (b_i - a_i c'_{i-1}),  d'_i = (d_i - a_i d'_{i-1}) / (b_i - a_i c'_{i-1}),
+/// x_{N-1} = d'_{N-1},  x_i = d'_i - c'_i x_{i+1}
+/// ```
+/// 
+/// stable e.g. for diagonally dominant systems. Returns `None` if some pivot vanishes.
+pub fn thomas<const N: usize, T: Scalar>(
+    a: &Vector<N, T>,
+    b: &Vector<N, T>,
+    c: &Vector<N, T>,
+    d: &Vector<N, T>,
+) -> Option<Vector<N, T>> {
+    let mut c_prime = Vector::<N, T>::zeroed();
+    let mut x = Vector::<N, T>::zeroed();
+    for i in 0..N {
+        let (c_prev, d_prev) = if i > 0 { (c_prime[i - 1], x[i - 1]) } else { (T::ZERO, T::ZERO) };
+        let pivot = b[i] - a[i] * c_prev;
+        if pivot.modulus() <= T::Real::TOLERANCE {
+            return None;
+        }
+        c_prime[i] = c[i] / pivot;
+        x[i] = (d[i] - a[i] * d_prev) / pivot;
+    }
+    for i in (0..N.saturating_sub(1)).rev() {
+        let update = c_prime[i] * x[i + 1];
+        x[i] -= update;
+    }
+    Some(x)
+}
+
+impl<const N: usize, T: Scalar> std::ops::Index<(usize, usize)> for BandedMatrix<N, T> {
+    type Output = T;
+    fn index(&self, (i, j): (usize, usize)) -> &Self::Output {
+        assert!(self.is_in_band(i, j), "index outside the band");
+        &self.bands[i * self.width() + (j + self.lower - i)]
+    }
+}
+impl<const N: usize, T: Scalar> std::ops::IndexMut<(usize, usize)> for BandedMatrix<N, T> {
+    fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut Self::Output {
+        assert!(self.is_in_band(i, j), "index outside the band");
+        let width = self.width();
+        &mut self.bands[i * width + (j + self.lower - i)]
+    }
+}
+
+/// O(N (lower + upper)) matrix-vector product
+impl<const N: usize, T: Scalar> std::ops::Mul<&Vector<N, T>> for &BandedMatrix<N, T> {
+    type Output = Vector<N, T>;
+    fn mul(self, rhs: &Vector<N, T>) -> Self::Output {
+        Vector::<N, T>::from_fn(|i, _| self.band_columns(i).map(|j| self[(i, j)] * rhs[j]).sum())
+    }
+}
+
+/// The product of banded matrices is banded with the sums of the bandwidths
+impl<const N: usize, T: Scalar> std::ops::Mul for &BandedMatrix<N, T> {
+    type Output = BandedMatrix<N, T>;
+    fn mul(self, rhs: Self) -> Self::Output {
+        BandedMatrix::<N, T>::from_fn(self.lower + rhs.lower, self.upper + rhs.upper, |i, j| {
+            // a_{ik} b_{kj} is in both bands iff k is in the band columns of the i-th row of A
+            // and j - upper(B) <= k <= j + lower(B)
+            let columns = self.band_columns(i);
+            let ks = columns.start.max(j.saturating_sub(rhs.upper))..columns.end.min(j + rhs.lower + 1);
+            ks.map(|k| self[(i, k)] * rhs[(k, j)]).sum()
+        })
+    }
+}
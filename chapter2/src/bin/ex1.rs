@@ -2,14 +2,20 @@
 #![feature(generic_const_exprs)]
 
 use chapter2::{Matrix, Vector};
-use chapter2::{EPSILON, EquationSolver, back_substitution};
+use chapter2::{Benchmark, EquationSolver, ExperimentConfig, ExperimentStats, Float, ScalingStudy, Scalar, SingularValueProfile, UpperTriangular, WithConditionNumber, back_substitution};
 
-fn do_gaussian_elimination<const N: usize>(ab: &mut Matrix<N, {N + 1}>) {
+/// to report the peak memory of each solve
+#[global_allocator]
+static ALLOCATOR: chapter2::CountingAllocator = chapter2::CountingAllocator;
+
+fn do_gaussian_elimination<const N: usize, T: Scalar>(ab: &mut Matrix<N, {N + 1}, T>) {
     for k in 0..(N - 1) {
         let (i, _pivot) = (k..N)
             .map(|i| (i, ab[(i, k)]))
-            .filter(|(_, value)| value.abs() > EPSILON)
-            .max_by(|(_, a), (_, b)| f64::partial_cmp(&a.abs(), &b.abs()).expect("found NaN or Inf"))
+            .filter(|(_, value)| value.modulus() > T::Real::TOLERANCE)
+            // reversed to take the first of the largest, as `max_by` returns the last
+            .rev()
+            .max_by(|(_, a), (_, b)| T::Real::partial_cmp(&a.modulus(), &b.modulus()).expect("found NaN or Inf"))
             .expect("Matrix is singular");
         
         if i != k {
@@ -19,63 +25,158 @@ fn do_gaussian_elimination<const N: usize>(ab: &mut Matrix<N, {N + 1}>) {
         for i in (k + 1)..N {
             let factor = ab[(i, k)] / ab[(k, k)];
             for j in k..(N + 1) {
-                ab[(i, j)] -= factor * ab[(k, j)];
+                let update = factor * ab[(k, j)];
+                ab[(i, j)] -= update;
             }
+            chapter2::count_flops(1 + 2 * (N + 1 - k));
         }
     }
 }
 
-fn solve_by_gaussian_elimination<const N: usize>(a: &Matrix<N, N>, b: &Vector<N>) -> Vector<N> where [(); N + 1]: {
+fn solve_by_gaussian_elimination<const N: usize, T: Scalar>(a: &Matrix<N, N, T>, b: &Vector<N, T>) -> Vector<N, T> where [(); N + 1]: {
     let mut augmented_coefficient_matrix = Matrix::concat(a, b);
     do_gaussian_elimination(&mut augmented_coefficient_matrix);
-    /*
-     * rustc-1.92 reports error for
-     * ```
-     * let (a, b): (Matrix<N, N>, Vector<N>) = augmented_coefficient_matrix.into_split_last_column();
-     * ```
-     * as:
-     * ```
-     * mismatched types
-     * expected constant N
-     * found constant chapter2::::matrix::{impl#8}::into_split_last_column::{constant#0} (rustc E0308)
-     * ```
-    */
-    back_substitution(
-        &Matrix::<N, N>::from_fn(|i, j| augmented_coefficient_matrix[(i, j)]),
-        &Vector::<N>::from_fn(|i, _| augmented_coefficient_matrix[(i, N)]),
-    )
+    let (a, b): (Matrix<N, N, T>, Vector<N, T>) = augmented_coefficient_matrix.split_at_column();
+    back_substitution(&UpperTriangular::from_upper_part(a), &b)
 }
 
-fn plot_100_experiments<const N: usize>(solver: EquationSolver<N>) -> Result<(), Box<dyn std::error::Error>> {
-    let stats: [chapter2::EquationExperimentStat<N>; 100] = (0..100)
-        .map(|_| dbg!(solver.experiment_randomly()))
-        .collect::<Vec<_>>()
-        .try_into()
-        .unwrap();
+/// residual norms, relative errors, and time of the `config.trials` random experiments,
+/// with their `ExperimentSummary` printed and all the metrics written into a CSV and a JSON,
+/// returning the median time for the `ScalingStudy`
+fn plot_experiments<const N: usize>(config: &ExperimentConfig, solver: EquationSolver<N>) -> Result<f64, Box<dyn std::error::Error>> {
+    let record = config.record("gaussian_elimination", N, || solver.experiment_randomly());
+    let stats = &record.stats;
+    println!("N = {N} (seed {})\n{}", record.seed, chapter2::ExperimentSummary::new(stats));
+    stats.write_csv(format!("plot/ex1/n{N}-stats.csv"))?;
+    record.write_json(format!("plot/ex1/n{N}-stats.json"))?;
     
     chapter2::Plotter {
         y_desc: "residual norm",
-        data: stats.iter().map(|stat| stat.residual_norm).collect::<Vec<_>>().try_into().unwrap(),
+        data: stats.iter().map(|stat| stat.residual_norm).collect(),
     }.plot_into(format!("plot/ex1/n{N}-residual_norm.svg"))?;
     
     chapter2::Plotter {
         y_desc: "relative error",
-        data: stats.iter().map(|stat| stat.relative_error).collect::<Vec<_>>().try_into().unwrap(),
+        data: stats.iter().map(|stat| stat.relative_error).collect(),
     }.plot_into(format!("plot/ex1/n{N}-relative_error.svg"))?;
     
     chapter2::Plotter {
         y_desc: "time elapsed (sec.)",
-        data: stats.iter().map(|stat| stat.elapsed.as_secs_f64()).collect::<Vec<_>>().try_into().unwrap(),
+        data: stats.iter().map(|stat| stat.elapsed.as_secs_f64()).collect(),
     }.plot_into(format!("plot/ex1/n{N}-time_elapsed.svg"))?;
     
+    Ok(ScalingStudy::median_time(stats))
+}
+
+/// mean relative errors of 10 random experiments for each κ_2(A) = 10^0, 10^0.5, ..., 10^9
+/// of the singular values graded by `profile`, to be compared with the rule of thumb κ_2(A) ε
+/// (up to 10^9, as the pivots below `Float::TOLERANCE` are taken as singular)
+fn plot_relative_error_vs_condition_number<const N: usize>(solver: &EquationSolver<N>, profile: SingularValueProfile) -> Result<(), Box<dyn std::error::Error>> {
+    let points = (0..=18).map(|k| {
+        let condition_number = 10f64.powf(k as f64 / 2.0);
+        let distribution = WithConditionNumber { condition_number, profile };
+        let mean_relative_error = (0..10)
+            .map(|_| solver.experiment_randomly_from(&distribution).relative_error)
+            .sum::<f64>() / 10.0;
+        // may be exactly 0 for the well-conditioned ones
+        (condition_number, mean_relative_error.max(f64::EPSILON))
+    }).collect();
+    
+    let profile = match profile {
+        SingularValueProfile::Linear => "linear",
+        SingularValueProfile::Geometric => "geometric",
+    };
+    chapter2::CurvePlotter {
+        x_desc: "condition number",
+        y_desc: "relative error",
+        points,
+        log_x: true,
+    }.plot_into(format!("plot/ex1/n{N}-relative_error_vs_condition_number-{profile}.svg"))
+}
+
+/// times of the single shots and of the medians of 5 repetitions after a warm-up by `Benchmark`
+/// on the same 100 random instances, the latter less noisy by the allocator and the cache
+fn plot_100_benchmarked_times<const N: usize>() -> Result<(), Box<dyn std::error::Error>> where [(); N + 1]: {
+    let stats = EquationSolver::compare(&[
+        ("single_shot", EquationSolver::new(solve_by_gaussian_elimination::<N, f64>)),
+        ("benchmarked", EquationSolver::new(solve_by_gaussian_elimination::<N, f64>).with_benchmark(Benchmark::new(1, 5))),
+    ], 100);
+    
+    for (name, stats) in stats {
+        chapter2::Plotter {
+            y_desc: "time elapsed (sec.)",
+            data: stats.iter().map(|stat| stat.elapsed.as_secs_f64()).collect(),
+        }.plot_into(format!("plot/ex1/n{N}-time_elapsed-{name}.svg"))?;
+    }
+    
     Ok(())
 }
 
+/// peak bytes and allocations of a solve by the Gaussian elimination on the augmented N x (N + 1) matrix
+/// over those by `EquationSolver::new_lu` decomposing a copy of A in place, on the same random instance
+fn memory_of_gaussian_elimination_over_lu<const N: usize>() -> (f64, f64) where [(); N + 1]: {
+    let stats = EquationSolver::compare(&[
+        ("gaussian_elimination", EquationSolver::new(solve_by_gaussian_elimination::<N, f64>)),
+        ("lu", EquationSolver::new_lu()),
+    ], 1);
+    let [(_, gaussian_elimination), (_, lu)] = <[_; 2]>::try_from(stats).unwrap();
+    let ratio = |of: fn(&chapter2::EquationExperimentStat<N>) -> Option<usize>| {
+        of(&gaussian_elimination[0]).expect("CountingAllocator is not installed") as f64 / of(&lu[0]).expect("CountingAllocator is not installed") as f64
+    };
+    (ratio(|stat| stat.peak_memory_bytes), ratio(|stat| stat.allocation_count))
+}
+
+/// flops of a solve by the Gaussian elimination over the 2N^3 / 3 of the textbook,
+/// approaching 1 as N grows (the O(N^2) of `b` and the back substitution aside),
+/// or `None` without the `flop-count` feature
+fn flops_over_theoretical<const N: usize>() -> Option<f64> where [(); N + 1]: {
+    let stat = EquationSolver::new(solve_by_gaussian_elimination::<N, f64>).experiment_randomly();
+    stat.flop_count.map(|flop_count| flop_count as f64 / (2.0 * (N as f64).powi(3) / 3.0))
+}
+
+/// `cargo run --bin ex1 [-- <N>...]` for only the given sizes among 100, 200, 400, 800
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    plot_100_experiments(EquationSolver::new(solve_by_gaussian_elimination::<100>))?;
-    plot_100_experiments(EquationSolver::new(solve_by_gaussian_elimination::<200>))?;
-    plot_100_experiments(EquationSolver::new(solve_by_gaussian_elimination::<400>))?;
-    plot_100_experiments(EquationSolver::new(solve_by_gaussian_elimination::<800>))?;
+    let sizes = std::env::args().skip(1).map(|arg| arg.parse()).collect::<Result<Vec<usize>, _>>()?;
+    let config = ExperimentConfig::default().with_sizes(sizes);
+    let study = ScalingStudy::new(chapter2::sweep_sizes!(&config, [100, 200, 400, 800], |N| {
+        plot_experiments(&config, EquationSolver::new(solve_by_gaussian_elimination::<N, f64>))?
+    }));
+    // to be compared with O(N^3) of the Gaussian elimination
+    println!("{study}");
+    if study.exponent().is_some() {
+        chapter2::CurvePlotter {
+            x_desc: "N",
+            y_desc: "median time elapsed (sec.)",
+            points: study.median_times.iter().map(|&(n, time)| (n as f64, time)).collect(),
+            log_x: true,
+        }.plot_into("plot/ex1/time_elapsed_scaling.svg")?;
+    }
+    let memory_ratios = chapter2::sweep_sizes!(&ExperimentConfig::default(), [25, 50, 100, 200, 400], |N| memory_of_gaussian_elimination_over_lu::<N>());
+    println!("{:>8}  {:>18}  {:>22}", "N", "peak memory ratio", "allocation count ratio");
+    for (n, (peak_memory_ratio, allocation_count_ratio)) in &memory_ratios {
+        println!("{n:>8}  {peak_memory_ratio:>18.3}  {allocation_count_ratio:>22.3}");
+    }
+    chapter2::CurvePlotter {
+        x_desc: "N",
+        y_desc: "peak memory ratio (Gaussian elimination / LU)",
+        points: memory_ratios.iter().map(|&(n, (peak_memory_ratio, _))| (n as f64, peak_memory_ratio)).collect(),
+        log_x: true,
+    }.plot_into("plot/ex1/peak_memory_ratio-lu.svg")?;
+    
+    if cfg!(feature = "flop-count") {
+        let flop_ratios = chapter2::sweep_sizes!(&ExperimentConfig::default(), [25, 50, 100, 200, 400], |N| flops_over_theoretical::<N>());
+        println!("{:>8}  {:>24}", "N", "flops / (2N^3 / 3)");
+        for (n, ratio) in &flop_ratios {
+            println!("{n:>8}  {:>24.4}", ratio.unwrap());
+        }
+    }
+    
+    if config.includes(100) {
+        plot_100_benchmarked_times::<100>()?;
+        for profile in [SingularValueProfile::Linear, SingularValueProfile::Geometric] {
+            plot_relative_error_vs_condition_number(&EquationSolver::<100>::new(solve_by_gaussian_elimination::<100, f64>), profile)?;
+        }
+    }
     Ok(())
 }
 
@@ -95,19 +196,326 @@ mod tests {
         
         dbg!(&ab);
         
-        let expected = nalgebra::DMatrix::from_row_slice(3, 4, &[
-            -3.0, -1.0, 2.0, -11.0,
-            0.0, 5./3., 2./3., 13./3.,
-            0.0, 0.0, 1./5., -1./5.,
+        let expected = Matrix::from([
+            [-3.0, -1.0, 2.0, -11.0],
+            [0.0, 5./3., 2./3., 13./3.],
+            [0.0, 0.0, 1./5., -1./5.],
+        ]);
+        
+        chapter2::assert_matrix_approx_eq!(ab, expected);
+    }
+    
+    #[test]
+    fn test_sweep_sizes() {
+        let config = ExperimentConfig::default().with_trials(3).with_sizes([2, 8]);
+        let counts = chapter2::sweep_sizes!(&config, [2, 4, 8], |N| {
+            let stats = EquationSolver::<N>::new(solve_by_gaussian_elimination::<N, f64>).experiments(&config);
+            assert!(stats.iter().all(|stat| stat.relative_error < 1e-6));
+            stats.len()
+        });
+        assert_eq!(counts, vec![(2, 3), (8, 3)]);
+        
+        let all = chapter2::sweep_sizes!(&ExperimentConfig::default(), [1, 2], |N| N * N);
+        assert_eq!(all, vec![(1, 1), (2, 4)]);
+    }
+    
+    #[test]
+    fn test_experiment_randomly_from() {
+        use chapter2::MatrixDistribution;
+        
+        let solver = EquationSolver::<20>::new(solve_by_gaussian_elimination::<20, f64>);
+        
+        let stat = solver.experiment_randomly_from(&chapter2::Orthogonal);
+        assert!((stat.condition_number - 1.0).abs() < 1e-8);
+        assert!(stat.relative_error < 1e-12);
+        
+        for profile in [SingularValueProfile::Linear, SingularValueProfile::Geometric] {
+            let stat = solver.experiment_randomly_from(&WithConditionNumber { condition_number: 1e8, profile });
+            assert!((stat.condition_number / 1e8 - 1.0).abs() < 1e-4);
+            assert!(stat.relative_error < 1e-4);
+        }
+        
+        let linear = SingularValueProfile::Linear.singular_values::<5>(100.0);
+        let geometric = SingularValueProfile::Geometric.singular_values::<5>(100.0);
+        for (i, (l, g)) in [(1.0, 1.0), (0.7525, 0.1f64.sqrt()), (0.505, 0.1), (0.2575, 0.1f64.powf(1.5)), (0.01, 0.01)].into_iter().enumerate() {
+            assert!((linear[i] - l).abs() < 1e-12);
+            assert!((geometric[i] - g).abs() < 1e-12);
+        }
+        
+        let spd: Matrix<20, 20> = chapter2::Spd.sample();
+        chapter2::assert_matrix_approx_eq!(spd, spd.transpose());
+        let symmetric: Matrix<20, 20> = chapter2::Symmetrized(chapter2::Gaussian::default()).sample();
+        chapter2::assert_matrix_approx_eq!(symmetric, symmetric.transpose());
+        
+        let a: Matrix<3, 3> = chapter2::WithSpectrum(chapter2::DiagonalMatrix::from(Vector::from([3.0, 2.0, 1.0]))).sample();
+        assert!((a.condition_number() - 3.0).abs() < 1e-8);
+    }
+    
+    #[test]
+    fn test_experiment_on() {
+        let a = chapter2::test_matrices::hilbert::<6>();
+        let x = Vector::<6>::from_fn(|_, _| 1.0);
+        let stat = EquationSolver::new(solve_by_gaussian_elimination::<6, f64>).experiment_on(&a, &(&a * &x));
+        assert!((stat.condition_number / 1.495e7 - 1.0).abs() < 1e-3, "{}", stat.condition_number);
+        assert!(stat.residual_norm < 1e-12);
+        // the relative error amplified up to κ_2 ε
+        assert!(stat.relative_error < stat.condition_number * f64::EPSILON);
+        chapter2::assert_matrix_approx_eq!(stat.reference_solution, x, 1e-6);
+    }
+    
+    #[test]
+    fn test_experiment_summary() {
+        let summary = chapter2::MetricSummary::of(&[3.0, 1.0, 4.0, 1.0, 5.0], &[0.0, 0.25, 0.9, 1.0]);
+        assert_eq!(summary.mean, 2.8);
+        assert_eq!(summary.median, 3.0);
+        assert!((summary.std_dev - 3.2f64.sqrt()).abs() < 1e-12);
+        assert_eq!((summary.min, summary.max), (1.0, 5.0));
+        assert_eq!(summary.quantiles, vec![(0.0, 1.0), (0.25, 1.0), (0.9, 4.6), (1.0, 5.0)]);
+        
+        let config = ExperimentConfig::default().with_trials(10);
+        let stats = EquationSolver::<10>::new(solve_by_gaussian_elimination::<10, f64>).experiments(&config);
+        let summary = chapter2::ExperimentSummary::new(&stats);
+        assert_eq!(summary.trials, 10);
+        assert!(summary.metric("peak_memory_bytes").is_some());
+        
+        let relative_error = summary.metric("relative_error").unwrap();
+        let max = stats.iter().map(|stat| stat.relative_error).fold(0.0, f64::max);
+        assert_eq!(relative_error.max, max);
+        assert!(relative_error.min <= relative_error.quantiles[0].1 && relative_error.quantiles[1].1 <= max);
+        assert_eq!(summary.to_string().lines().count(), 1 + summary.metrics.len());
+    }
+    
+    #[test]
+    fn test_write_csv() {
+        let config = ExperimentConfig::default().with_trials(3);
+        let stats = EquationSolver::<4>::new(solve_by_gaussian_elimination::<4, f64>).experiments(&config);
+        let path = std::env::temp_dir().join(format!("ex1-test_write_csv-{}.csv", std::process::id()));
+        stats.write_csv(&path).unwrap();
+        let csv = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        
+        let lines = csv.lines().collect::<Vec<_>>();
+        let flop_count = if cfg!(feature = "flop-count") { ",flop_count" } else { "" };
+        assert_eq!(lines[0], format!("trial,residual_norm,relative_error,condition_number,elapsed_secs,elapsed_spread_secs,refinement_count,peak_memory_bytes,allocation_count{flop_count}"));
+        assert_eq!(lines.len(), 1 + 3);
+        for (trial, (line, stat)) in lines[1..].iter().zip(&stats).enumerate() {
+            let cells = line.split(',').collect::<Vec<_>>();
+            assert_eq!(cells.len(), lines[0].split(',').count());
+            assert_eq!(cells[0], trial.to_string());
+            assert_eq!(cells[2].parse::<f64>().unwrap(), stat.relative_error);
+        }
+    }
+    
+    #[test]
+    fn test_write_json() {
+        let solver = EquationSolver::<4>::new(solve_by_gaussian_elimination::<4, f64>);
+        let config = ExperimentConfig::default().with_trials(3).with_seed(42);
+        let record = config.record("gaussian_elimination", 4, || solver.experiment_randomly());
+        assert_eq!(record.seed, 42);
+        
+        // reproduced by the seed
+        let condition_numbers = |stats: &[chapter2::EquationExperimentStat<4>]| stats.iter().map(|stat| stat.condition_number).collect::<Vec<_>>();
+        assert_eq!(condition_numbers(&record.stats), condition_numbers(&solver.experiments(&config)));
+        let unseeded = ExperimentConfig::default().with_trials(3).record("gaussian_elimination", 4, || solver.experiment_randomly());
+        assert_eq!(
+            condition_numbers(&unseeded.stats),
+            condition_numbers(&solver.experiments(&ExperimentConfig::default().with_trials(3).with_seed(unseeded.seed))),
+        );
+        
+        let path = std::env::temp_dir().join(format!("ex1-test_write_json-{}.json", std::process::id()));
+        record.write_json(&path).unwrap();
+        let json = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        
+        let lines = json.lines().collect::<Vec<_>>();
+        assert_eq!(lines[1..4], ["  \"solver\": \"gaussian_elimination\",", "  \"n\": 4,", "  \"seed\": 42,"]);
+        assert_eq!(lines.len(), 5 + 1 + 3 + 2);
+        assert!(lines[6].starts_with("    {\"residual_norm\": ") && lines[6].ends_with("},"));
+        assert!(lines[8].contains(&format!("\"relative_error\": {:?}", record.stats[2].relative_error)) && lines[8].ends_with('}'));
+        
+        for (secs, expected) in [(951782400, "2000-02-29T00:00:00Z"), (1735689599, "2024-12-31T23:59:59Z")] {
+            let record = chapter2::ExperimentRecord::<chapter2::EquationExperimentStat<4>> {
+                solver: "gaussian_elimination".into(),
+                n: 4,
+                seed: 42,
+                timestamp: std::time::UNIX_EPOCH + std::time::Duration::from_secs(secs),
+                stats: vec![],
+            };
+            record.write_json(&path).unwrap();
+            let json = std::fs::read_to_string(&path).unwrap();
+            std::fs::remove_file(&path).unwrap();
+            assert!(json.contains(&format!("  \"timestamp\": \"{expected}\",\n  \"trials\": [\n  ]")), "{json}");
+        }
+    }
+    
+    #[test]
+    fn test_scaling_study() {
+        let study = ScalingStudy::new(vec![(10, 2e-3), (20, 1.6e-2), (40, 1.28e-1)]);
+        let (exponent, coefficient) = study.fit().unwrap();
+        assert!((exponent - 3.0).abs() < 1e-12);
+        assert!((coefficient - 2e-6).abs() < 1e-15);
+        assert!(ScalingStudy::new(vec![(10, 1.0), (10, 2.0)]).fit().is_none());
+        assert!(ScalingStudy::new(vec![]).exponent().is_none());
+        // dropping the times of 0 or NaN instead of fitting their logarithms
+        let (exponent, _) = ScalingStudy::new(vec![(5, 0.0), (10, 2e-3), (20, 1.6e-2), (30, f64::NAN), (40, 1.28e-1)]).fit().unwrap();
+        assert!((exponent - 3.0).abs() < 1e-12);
+        assert!(ScalingStudy::new(vec![(10, 0.0), (20, 1.6e-2)]).fit().is_none());
+        
+        let config = ExperimentConfig::default().with_trials(5);
+        let study = chapter2::scaling_study!(&config, [8, 16], |N| EquationSolver::<N>::new(solve_by_gaussian_elimination::<N, f64>).experiments(&config));
+        assert_eq!(study.median_times.iter().map(|&(n, _)| n).collect::<Vec<_>>(), [8, 16]);
+        assert!(study.median_times.iter().all(|&(_, time)| time > 0.0));
+        assert!(study.exponent().unwrap().is_finite());
+        assert!(study.to_string().ends_with(&format!("N^{:.3}\n", study.exponent().unwrap())));
+    }
+    
+    #[test]
+    fn test_run_trials() {
+        let solver = EquationSolver::<20>::new(solve_by_gaussian_elimination::<20, f64>);
+        let config = ExperimentConfig::default().with_trials(64).with_seed(7);
+        let stats = solver.experiments(&config);
+        
+        // in the order of the trials, each by its own seed
+        let again = solver.experiments(&config);
+        assert!(std::iter::zip(&stats, &again).all(|(stat, again)| stat.condition_number == again.condition_number));
+        assert!((1..64).all(|i| stats[i].condition_number != stats[i - 1].condition_number));
+        
+        // the same allocations for each solve, not disturbed by the others (in parallel or not)
+        let peak_memory_bytes = stats[0].peak_memory_bytes.unwrap();
+        assert!(peak_memory_bytes > 0);
+        assert!(stats.iter().all(|stat| stat.peak_memory_bytes == Some(peak_memory_bytes)));
+    }
+    
+    #[test]
+    fn test_benchmark() {
+        let mut calls = 0;
+        let (result, timing) = Benchmark::new(2, 4).run(|| { calls += 1; calls });
+        assert_eq!((result, calls), (6, 6));
+        assert!(timing.min <= timing.median && timing.median <= timing.max);
+        assert_eq!(timing.spread(), timing.max - timing.min);
+        
+        let (_, timing) = Benchmark::default().run(|| ());
+        assert_eq!((timing.min, timing.spread()), (timing.median, std::time::Duration::ZERO));
+        
+        let a = chapter2::test_matrices::hilbert::<6>();
+        let b = Vector::<6>::from_fn(|_, _| 1.0);
+        let single_shot = EquationSolver::new(solve_by_gaussian_elimination::<6, f64>).experiment_on(&a, &b);
+        let benchmarked = EquationSolver::new(solve_by_gaussian_elimination::<6, f64>).with_benchmark(Benchmark::new(1, 5)).experiment_on(&a, &b);
+        assert_eq!(single_shot.elapsed_spread, std::time::Duration::ZERO);
+        chapter2::assert_matrix_approx_eq!(benchmarked.solution, single_shot.solution, 0.0);
+    }
+    
+    #[test]
+    fn test_allocation_count() {
+        let a = chapter2::test_matrices::hilbert::<6>();
+        let b = Vector::<6>::from_fn(|_, _| 1.0);
+        let single_shot = EquationSolver::new_lu().experiment_on(&a, &b);
+        let benchmarked = EquationSolver::new_lu().with_benchmark(Benchmark::new(2, 3)).experiment_on(&a, &b);
+        assert!(single_shot.allocation_count.unwrap() > 0);
+        // per solve
+        assert_eq!(benchmarked.allocation_count, single_shot.allocation_count);
+        assert_eq!(benchmarked.peak_memory_bytes, single_shot.peak_memory_bytes);
+        
+        let (peak_memory_ratio, allocation_count_ratio) = memory_of_gaussian_elimination_over_lu::<20>();
+        assert!(peak_memory_ratio > 0.0 && allocation_count_ratio > 0.0);
+    }
+    
+    #[test]
+    #[cfg(feature = "flop-count")]
+    fn test_flop_count() {
+        let a = Matrix::from([
+            [2.0, 1.0, -1.0],
+            [-3.0, -1.0, 2.0],
+            [-2.0, 1.0, 2.0],
         ]);
+        let b = Vector::from([8.0, -11.0, -3.0]);
+        // the elimination on the 3 x 4 augmented matrix 2 (1 + 2 * 4) + (1 + 2 * 3)
+        // and the back substitution N^2 + N
+        let stat = EquationSolver::new(solve_by_gaussian_elimination::<3, f64>).experiment_on(&a, &b);
+        assert_eq!(stat.flop_count, Some(25 + 12));
+        // the factorization 2 (1 + 2 * 3) + (1 + 2 * 2) and the two substitutions 2 (N^2 + N)
+        let stat = EquationSolver::new_lu().with_benchmark(Benchmark::new(1, 3)).experiment_on(&a, &b);
+        assert_eq!(stat.flop_count, Some(19 + 24));
         
-        for i in 0..3 {
-            for j in 0..4 {
-                assert!(
-                    (ab[(i, j)] - expected[(i, j)]).abs() < EPSILON,
-                    "ab[{i}, {j}] = {}, expected[{i}, {j}] = {}", ab[(i, j)], expected[(i, j)]
-                );
+        let ratio = flops_over_theoretical::<100>().unwrap();
+        assert!((1.0..1.1).contains(&ratio), "{ratio}");
+    }
+    
+    #[test]
+    fn test_dyn_matrix() {
+        use chapter2::DynMatrix;
+        
+        let a = Matrix::<3, 2>::from_fn(|i, j| (i + 2 * j) as f64 - 1.5);
+        let b = Matrix::<2, 4>::from_fn(|i, j| 1.0 / (1 + i + j) as f64);
+        let product = &DynMatrix::from(&a) * &DynMatrix::from(&b);
+        assert_eq!(product.shape(), (3, 4));
+        chapter2::assert_matrix_approx_eq!(Matrix::<3, 4>::try_from(&product).unwrap(), &a * &b);
+        
+        assert_eq!(Matrix::<4, 3>::try_from(&product).unwrap_err(), "dynamic matrix dimensions do not match matrix size");
+        assert_eq!(Matrix::<3, 3>::try_from(&product).unwrap_err(), "dynamic matrix dimensions do not match matrix size");
+        let rows: [&[f64]; 2] = [&[1.0, 2.0], &[3.0]];
+        assert_eq!(DynMatrix::try_from(&rows[..]).unwrap_err(), "rows have different lengths");
+        let rows: [&[f64]; 2] = [&[1.0, 2.0], &[3.0, 4.0]];
+        assert_eq!(DynMatrix::try_from(&rows[..]).unwrap(), DynMatrix::from(&Matrix::from([[1.0, 2.0], [3.0, 4.0]])));
+    }
+    
+    #[test]
+    fn test_symmetric_matrix() {
+        use chapter2::SymmetricMatrix;
+        
+        // distinct values on each lower position
+        let dense = Matrix::<5, 5>::from_fn(|i, j| (10 * i.max(j) + i.min(j)) as f64);
+        let symmetric = SymmetricMatrix::try_from(&dense).unwrap();
+        for i in 0..5 {
+            for j in 0..5 {
+                assert_eq!(symmetric[(i, j)], dense[(i, j)], "({i}, {j})");
+                assert_eq!(symmetric[(j, i)], dense[(i, j)], "({j}, {i})");
             }
         }
+        chapter2::assert_matrix_approx_eq!(symmetric.to_dense(), dense, 0.0);
+        assert_eq!(SymmetricMatrix::from_fn(|i, j| { assert!(j <= i); dense[(i, j)] }), symmetric);
+        
+        let mut written = SymmetricMatrix::<5>::zeroed();
+        written[(1, 3)] = 7.0;
+        assert_eq!((written[(1, 3)], written[(3, 1)]), (7.0, 7.0));
+        
+        let x = Vector::<5>::random_uniform(-1.0, 1.0);
+        chapter2::assert_matrix_approx_eq!(&symmetric * &x, &dense * &x, 1e-12);
+        assert!(SymmetricMatrix::try_from(&Matrix::from([[1.0, 2.0], [3.0, 1.0]])).is_err());
+    }
+    
+    #[test]
+    fn test_with_condition_number() {
+        use chapter2::MatrixDistribution;
+        
+        let solver = EquationSolver::<2>::new(solve_by_gaussian_elimination::<2, f64>);
+        
+        // κ_2(diag(1, 1e6)) = 1e6 exactly
+        let diagonal = Matrix::<2, 2>::from([
+            [1.0, 0.0],
+            [0.0, 1e6],
+        ]);
+        let stat = solver.experiment_on(&diagonal, &Vector::from([1.0, 1.0]));
+        assert_eq!(stat.condition_number, 1e6);
+        
+        // of the singular values (1, 1e-6) for N = 2 by either profile, rotated to non-diagonal
+        for profile in [SingularValueProfile::Linear, SingularValueProfile::Geometric] {
+            chapter2::assert_matrix_approx_eq!(profile.singular_values::<2>(1e6).diagonal(), Vector::from([1.0, 1e-6]), 1e-15);
+            let a: Matrix<2, 2> = WithConditionNumber { condition_number: 1e6, profile }.sample();
+            assert!(a[(0, 1)] != 0.0 && a[(1, 0)] != 0.0);
+            let stat = solver.experiment_on(&a, &Vector::from([1.0, 1.0]));
+            assert!((stat.condition_number / 1e6 - 1.0).abs() < 1e-8, "{}", stat.condition_number);
+        }
+        
+        // and all the singular values, not only the extreme ones
+        let singular_values = SingularValueProfile::Geometric.singular_values::<20>(1e6);
+        let a = Matrix::<20, 20>::random_with_singular_values(&singular_values);
+        let mut computed = nalgebra::DMatrix::from_column_slice(20, 20, a.as_slice())
+            .singular_values()
+            .iter()
+            .copied()
+            .collect::<Vec<_>>();
+        computed.sort_by(|x, y| y.total_cmp(x));
+        assert!(computed.iter().zip(singular_values.diagonal().iter()).all(|(c, s)| (c / s - 1.0).abs() < 1e-8));
     }
 }
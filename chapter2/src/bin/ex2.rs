@@ -1,101 +1,146 @@
+#![allow(incomplete_features)]
+#![feature(generic_const_exprs)]
+
 use chapter2::{Matrix, Vector};
-use chapter2::{EPSILON, EquationSolver, forward_substitution, back_substitution};
+use chapter2::{Benchmark, EquationSolver, ExperimentConfig, Scalar};
+use chapter2::lu::{lu_decomposition, gaussian_elimination, Pivoting};
 
-struct LUDecomposition<const N: usize> {
-    l: Matrix<N, N>,
-    u: Matrix<N, N>,
-    pi: [usize; N],
-}
+/// to report the peak memory of each solve
+#[global_allocator]
+static ALLOCATOR: chapter2::CountingAllocator = chapter2::CountingAllocator;
 
-fn lu_decomposition<const N: usize>(
-    a: &Matrix<N, N>,
-) -> LUDecomposition<N> {
-    // initialize `pi` as an identity permutation
-    let mut pi: [usize; N] = std::array::from_fn(|i| i);
-    // initialize `l` as an identity matrix
-    let mut l = Matrix::<N, N>::identity();
-    // initialize `u` as `a` itself
-    let mut u = a.clone();
-    
-    /*
-     * NOTE:
-     * 
-     * Our textbook illustrates this step as
-     * iterating k from 1 to **N - 1** by 1-based index,
-     * which is equivalent to iterating k from 0 to **N - 2** by 0-based index.
-     * 
-     * It's wrong. It should be iterating k from 0 to **N - 1** by 0-based index,
-     * i.e., 1 to **N** by 1-based index.
-     */
-    for k in 0..N {
-        let (i, _pivot) = (k..N)
-            .map(|i| (i, u[(i, k)]))
-            .filter(|(_, value)| value.abs() > EPSILON)
-            .max_by(|(_, a), (_, b)| f64::partial_cmp(&a.abs(), &b.abs()).expect("found NaN or Inf"))
-            .expect("Matrix is singular");
-        
-        if i != k {
-            u.swap_rows(i, k);
-            l.swap_rows(i, k);
-            pi.swap(i, k);
-        }
-        
-        for i in (k + 1)..N {
-            let factor = u[(i, k)] / u[(k, k)];
-            for j in k..N {
-                u[(i, j)] -= factor * u[(k, j)];
-            }
-            l[(i, k)] = factor;
-        }
-        l[(k, k)] = 1.0;
-        l.column_mut(k).take(k).for_each(|it| *it = 0.0);
-    }
-    
-    LUDecomposition { l, u, pi }
+fn solve_by_lu_decomposition<const N: usize, T: Scalar>(
+    a: &Matrix<N, N, T>,
+    b: &Vector<N, T>,
+) -> Vector<N, T> {
+    lu_decomposition(a).expect("Matrix is singular").solve(b)
 }
 
-fn solve_by_lu_decomposition<const N: usize>(
-    a: &Matrix<N, N>,
-    b: &Vector<N>,
-) -> Vector<N> {
-    let LUDecomposition { l, u, pi } = lu_decomposition(a);
-    
-    // solve Ly = Pb by forward substitution
-    let y = forward_substitution(&l, &Vector::from_fn(|i, _| b[pi[i]]));
-    // solve Ux = y by back substitution
-    back_substitution(&u, &y)
-}
-
-fn plot_100_experiments<const N: usize>(solver: EquationSolver<N>) -> Result<(), Box<dyn std::error::Error>> {
-    let stats: [chapter2::EquationExperimentStat<N>; 100] = (0..100)
-        .map(|_| dbg!(solver.experiment_randomly()))
-        .collect::<Vec<_>>()
-        .try_into()
-        .unwrap();
+fn plot_experiments<const N: usize>(config: &ExperimentConfig, solver: EquationSolver<N>) -> Result<(), Box<dyn std::error::Error>> {
+    let stats = solver.experiments(config);
     
     chapter2::Plotter {
         y_desc: "residual norm",
-        data: stats.iter().map(|stat| stat.residual_norm).collect::<Vec<_>>().try_into().unwrap(),
+        data: stats.iter().map(|stat| stat.residual_norm).collect(),
     }.plot_into(format!("plot/ex2/n{N}-residual_norm.svg"))?;
     
     chapter2::Plotter {
         y_desc: "relative error",
-        data: stats.iter().map(|stat| stat.relative_error).collect::<Vec<_>>().try_into().unwrap(),
+        data: stats.iter().map(|stat| stat.relative_error).collect(),
     }.plot_into(format!("plot/ex2/n{N}-relative_error.svg"))?;
     
     chapter2::Plotter {
         y_desc: "time elapsed (sec.)",
-        data: stats.iter().map(|stat| stat.elapsed.as_secs_f64()).collect::<Vec<_>>().try_into().unwrap(),
+        data: stats.iter().map(|stat| stat.elapsed.as_secs_f64()).collect(),
     }.plot_into(format!("plot/ex2/n{N}-time_elapsed.svg"))?;
     
     Ok(())
 }
 
+/// relative errors of the LU decomposition over those of the Gaussian elimination (both partially pivoted)
+/// on the same 100 random instances by `EquationSolver::compare`
+fn plot_100_comparisons_with_gaussian_elimination<const N: usize>() -> Result<(), Box<dyn std::error::Error>> {
+    let stats = EquationSolver::compare(&[
+        ("lu", EquationSolver::new(solve_by_lu_decomposition::<N, f64>)),
+        ("gaussian_elimination", EquationSolver::new(|a, b| gaussian_elimination(a, b, Pivoting::Partial).expect("Matrix is singular"))),
+    ], 100);
+    let [(_, lu), (_, gaussian_elimination)] = <[_; 2]>::try_from(stats).unwrap();
+    
+    chapter2::Plotter {
+        y_desc: "relative error ratio (LU / Gaussian elimination)",
+        data: std::iter::zip(&lu, &gaussian_elimination).map(|(lu, gaussian_elimination)| lu.relative_error / gaussian_elimination.relative_error).collect(),
+    }.plot_into(format!("plot/ex2/n{N}-relative_error_ratio-gaussian_elimination.svg"))?;
+    
+    Ok(())
+}
+
+/// residual norms by each pivoting strategy on the same 100 random instances
+fn plot_100_experiments_by_pivoting<const N: usize>() -> Result<(), Box<dyn std::error::Error>> {
+    let solvers = Pivoting::ALL.map(|pivoting| {
+        let name = match pivoting {
+            Pivoting::None => "no_pivoting",
+            Pivoting::Partial => "partial_pivoting",
+            Pivoting::ScaledPartial => "scaled_partial_pivoting",
+            Pivoting::Complete => "complete_pivoting",
+        };
+        (name, EquationSolver::<N>::new(move |a, b| gaussian_elimination(a, b, pivoting).expect("breakdown on a random instance")))
+    });
+    
+    for (name, stats) in EquationSolver::compare(&solvers, 100) {
+        chapter2::Plotter {
+            y_desc: "residual norm",
+            data: stats.iter().map(|stat| stat.residual_norm).collect(),
+        }.plot_into(format!("plot/ex2/n{N}-residual_norm-{name}.svg"))?;
+    }
+    
+    Ok(())
+}
+
+/// accuracy gain by the iterative refinement, on the same 100 random instances
+fn plot_100_experiments_with_refinement<const N: usize>(max_refinements: usize) -> Result<(), Box<dyn std::error::Error>> {
+    let instances = (0..100)
+        .map(|_| (Matrix::<N, N>::random_uniform(-1.0, 1.0), Vector::<N>::random_uniform(-1.0, 1.0)))
+        .collect::<Vec<_>>();
+    let refined = instances.iter()
+        .map(|(a, b)| chapter2::lu::solve_with_refinement(a, b, max_refinements).expect("Matrix is singular"))
+        .collect::<Vec<_>>();
+    
+    chapter2::Plotter {
+        y_desc: "residual norm (without refinement)",
+        data: instances.iter().map(|(a, b)| (b - a * &solve_by_lu_decomposition(a, b)).norm()).collect(),
+    }.plot_into(format!("plot/ex2/n{N}-residual_norm-unrefined.svg"))?;
+    
+    chapter2::Plotter {
+        y_desc: "residual norm (with refinement)",
+        data: std::iter::zip(&instances, &refined).map(|((a, b), (x, _))| (b - a * x).norm()).collect(),
+    }.plot_into(format!("plot/ex2/n{N}-residual_norm-refined.svg"))?;
+    
+    chapter2::Plotter {
+        y_desc: "refinement count",
+        data: refined.iter().map(|&(_, count)| count as f64).collect(),
+    }.plot_into(format!("plot/ex2/n{N}-refinement_count.svg"))?;
+    
+    Ok(())
+}
+
+/// elapsed time of the Gauss-Jordan elimination over that of the Gaussian elimination (both partially pivoted)
+/// on the same 100 random instances, each the median of 5 repetitions after a warm-up by `Benchmark`,
+/// to be compared with the operation count ratio 3/2
+fn plot_100_experiments_gauss_jordan<const N: usize>() -> Result<(), Box<dyn std::error::Error>> {
+    let benchmark = Benchmark::new(1, 5);
+    let stats = EquationSolver::compare(&[
+        ("gauss_jordan", EquationSolver::<N>::new(|a, b| chapter2::gauss_jordan(a, b).expect("Matrix is singular")).with_benchmark(benchmark)),
+        ("gaussian_elimination", EquationSolver::new(|a, b| gaussian_elimination(a, b, Pivoting::Partial).expect("Matrix is singular")).with_benchmark(benchmark)),
+    ], 100);
+    let [(_, gauss_jordan), (_, gaussian_elimination)] = <[_; 2]>::try_from(stats).unwrap();
+    
+    chapter2::Plotter {
+        y_desc: "time elapsed ratio (Gauss-Jordan / Gaussian elimination)",
+        data: std::iter::zip(&gauss_jordan, &gaussian_elimination).map(|(gauss_jordan, gaussian_elimination)| gauss_jordan.elapsed.as_secs_f64() / gaussian_elimination.elapsed.as_secs_f64()).collect(),
+    }.plot_into(format!("plot/ex2/n{N}-time_elapsed_ratio-gauss_jordan.svg"))?;
+    
+    Ok(())
+}
+
+/// `cargo run --bin ex2 [-- <N>...]` for only the given sizes among 100, 200, 400, 800
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    plot_100_experiments(EquationSolver::new(solve_by_lu_decomposition::<100>))?;
-    plot_100_experiments(EquationSolver::new(solve_by_lu_decomposition::<200>))?;
-    plot_100_experiments(EquationSolver::new(solve_by_lu_decomposition::<400>))?;
-    plot_100_experiments(EquationSolver::new(solve_by_lu_decomposition::<800>))?;
+    let sizes = std::env::args().skip(1).map(|arg| arg.parse()).collect::<Result<Vec<usize>, _>>()?;
+    let config = ExperimentConfig::default().with_sizes(sizes);
+    chapter2::sweep_sizes!(&config, [100, 200, 400, 800], |N| {
+        plot_experiments(&config, EquationSolver::new(solve_by_lu_decomposition::<N, f64>))?
+    });
+    if config.includes(100) {
+        plot_100_experiments_by_pivoting::<100>()?;
+        plot_100_experiments_with_refinement::<100>(5)?;
+        plot_100_experiments_gauss_jordan::<100>()?;
+        plot_100_comparisons_with_gaussian_elimination::<100>()?;
+    }
+    if config.includes(400) {
+        plot_100_experiments_by_pivoting::<400>()?;
+        plot_100_experiments_with_refinement::<400>(5)?;
+        plot_100_experiments_gauss_jordan::<400>()?;
+        plot_100_comparisons_with_gaussian_elimination::<400>()?;
+    }
     Ok(())
 }
 
@@ -111,32 +156,288 @@ mod tests {
             [-2.0, 1.0, 2.0],
         ]);
         
-        let my_decomposition = lu_decomposition(&a);
+        let my_decomposition = lu_decomposition(&a).unwrap();
         
         let reference_decomposition = nalgebra::SMatrix::<_, 3, 3>::from_fn(|i, j| a[(i, j)]).lu();
         
         assert!(reference_decomposition.l().shape() == (3, 3));
-        for i in 0..3 {
-            for j in 0..3 {
-                assert!(
-                    (my_decomposition.l[(i, j)] - reference_decomposition.l()[(i, j)]).abs() < EPSILON,
-                    "L matrix differs at ({i}, {j}): {} vs {}",
-                    my_decomposition.l[(i, j)],
-                    reference_decomposition.l()[(i, j)]
-                );
+        chapter2::assert_matrix_approx_eq!(
+            my_decomposition.l(),
+            Matrix::<3, 3>::from_fn(|i, j| reference_decomposition.l()[(i, j)]),
+        );
+        
+        assert!(reference_decomposition.u().shape() == (3, 3));
+        chapter2::assert_matrix_approx_eq!(
+            my_decomposition.u(),
+            Matrix::<3, 3>::from_fn(|i, j| reference_decomposition.u()[(i, j)]),
+        );
+    }
+    
+    #[test]
+    fn test_blocked_lu_step_in_place() {
+        const K: usize = 2;
+        
+        let a = Matrix::<6, 6>::from_fn(|i, j| if i == j { 10.0 } else { 1.0 / (1 + i + 2 * j) as f64 });
+        
+        // one step of blocked LU without pivoting, all in place
+        let mut lu = a.clone();
+        {
+            let [mut a11, mut a12, mut a21, mut a22] = lu.split_at_mut((K, K));
+            // A11 <- L11 \ U11
+            for k in 0..K {
+                for i in (k + 1)..K {
+                    a11[(i, k)] /= a11[(k, k)];
+                    for j in (k + 1)..K {
+                        a11[(i, j)] -= a11[(i, k)] * a11[(k, j)];
+                    }
+                }
+            }
+            // A21 <- A21 U11^{-1} = L21
+            for i in 0..a21.nrows() {
+                for j in 0..K {
+                    a21[(i, j)] = (a21[(i, j)] - (0..j).map(|k| a21[(i, k)] * a11[(k, j)]).sum::<f64>()) / a11[(j, j)];
+                }
+            }
+            // A12 <- L11^{-1} A12 = U12
+            for j in 0..a12.ncols() {
+                for i in 0..K {
+                    a12[(i, j)] -= (0..i).map(|k| a11[(i, k)] * a12[(k, j)]).sum::<f64>();
+                }
             }
+            // A22 <- A22 - L21 U12, the Schur complement
+            a22.gemm(-1.0, a21.as_view(), a12.as_view(), 1.0);
         }
         
-        assert!(reference_decomposition.u().shape() == (3, 3));
-        for i in 0..3 {
-            for j in 0..3 {
-                assert!(
-                    (my_decomposition.u[(i, j)] - reference_decomposition.u()[(i, j)]).abs() < EPSILON,
-                    "U matrix differs at ({i}, {j}): {} vs {}",
-                    my_decomposition.u[(i, j)],
-                    reference_decomposition.u()[(i, j)]
-                );
+        let l11 = Matrix::<K, K>::from_fn(|i, j| if i == j { 1.0 } else if i > j { lu[(i, j)] } else { 0.0 });
+        let u11 = Matrix::<K, K>::from_fn(|i, j| if i <= j { lu[(i, j)] } else { 0.0 });
+        let u12 = lu.view((..K, K..)).to_matrix::<K, 4>();
+        let l21 = lu.view((K.., ..K)).to_matrix::<4, K>();
+        let s = lu.view((K.., K..)).to_matrix::<4, 4>();
+        
+        chapter2::assert_matrix_approx_eq!(&l11 * &u11, a.view((..K, ..K)).to_matrix::<K, K>());
+        chapter2::assert_matrix_approx_eq!(&l11 * &u12, a.view((..K, K..)).to_matrix::<K, 4>());
+        chapter2::assert_matrix_approx_eq!(&l21 * &u11, a.view((K.., ..K)).to_matrix::<4, K>());
+        chapter2::assert_matrix_approx_eq!(&(&l21 * &u12) + &s, a.view((K.., K..)).to_matrix::<4, 4>());
+        
+        // the same by the runtime-sized arithmetic of views
+        let schur_complement = lu.view((K.., K..));
+        let reconstructed = &(lu.view((K.., ..K)) * lu.view((..K, K..))) + &schur_complement.to_dyn_matrix();
+        assert!(reconstructed.approx_eq(&a.view((K.., K..)).to_dyn_matrix(), 1e-12));
+        assert!((schur_complement + schur_complement).approx_eq(&(schur_complement * 2.0), 0.0));
+        assert!((schur_complement - schur_complement).frobenius_norm() == 0.0);
+    }
+    
+    #[test]
+    fn test_growth_factor_of_partial_and_complete_pivoting() {
+        let a = chapter2::test_matrices::wilkinson_growth::<20>();
+        
+        let partial = chapter2::lu::growth_factor(&a, lu_decomposition(&a).unwrap().u());
+        assert!((partial - 2f64.powi(19)).abs() < chapter2::EPSILON);
+        
+        let complete = chapter2::lu::full_pivot_lu(&a).unwrap();
+        assert!(chapter2::lu::growth_factor(&a, complete.u()) < 4.0);
+        
+        let b = Vector::<20>::random_uniform(-1.0, 1.0);
+        assert!((&b - &a * &complete.solve(&b)).norm() < chapter2::EPSILON);
+    }
+    
+    #[test]
+    fn test_no_pivot_lu() {
+        let a = Matrix::<3, 3>::from([
+            [4.0, 1.0, 0.0],
+            [1.0, 4.0, 1.0],
+            [0.0, 1.0, 4.0],
+        ]);
+        let lu = chapter2::lu::no_pivot_lu(&a).unwrap();
+        chapter2::assert_matrix_approx_eq!(&**lu.l() * &**lu.u(), a);
+        
+        let b = Matrix::<2, 2>::from([
+            [0.0, 1.0],
+            [1.0, 0.0],
+        ]);
+        assert_eq!(chapter2::lu::no_pivot_lu(&b).unwrap_err(), chapter2::lu::NoPivotBreakdown { step: 0 });
+    }
+    
+    #[test]
+    fn test_doolittle_and_crout() {
+        use chapter2::lu::{compact_lu, CompactScheme};
+        
+        let a = Matrix::<3, 3>::from([
+            [4.0, 1.0, 2.0],
+            [1.0, 5.0, 1.0],
+            [2.0, 1.0, 6.0],
+        ]);
+        
+        let doolittle = compact_lu(&a, CompactScheme::Doolittle).unwrap();
+        chapter2::assert_matrix_approx_eq!(&**doolittle.l() * &**doolittle.u(), a);
+        assert!((0..3).all(|i| doolittle.l()[(i, i)] == 1.0));
+        
+        let crout = compact_lu(&a, CompactScheme::Crout).unwrap();
+        chapter2::assert_matrix_approx_eq!(&**crout.l() * &**crout.u(), a);
+        assert!((0..3).all(|i| crout.u()[(i, i)] == 1.0));
+    }
+    
+    #[test]
+    fn test_band_lu() {
+        use chapter2::BandedMatrix;
+        use chapter2::lu::band_lu;
+        
+        // small diagonal to force the row interchanges
+        let a = BandedMatrix::<20>::from_fn(2, 1, |i, j| if i == j { 1e-3 } else { (i + 2 * j + 1) as f64 });
+        let b = Vector::<20>::random_uniform(-1.0, 1.0);
+        let lu = band_lu(&a).unwrap();
+        assert_eq!(lu.u().upper(), 3);
+        chapter2::assert_matrix_approx_eq!(lu.solve(&b), lu_decomposition(&a.to_dense()).unwrap().solve(&b));
+    }
+    
+    #[test]
+    fn test_multiple_right_hand_sides() {
+        let a = Matrix::<5, 5>::random_uniform(-1.0, 1.0);
+        let b = Matrix::<5, 3>::random_uniform(-1.0, 1.0);
+        
+        let factorized = EquationSolver::new_lu();
+        let direct = EquationSolver::new(solve_by_lu_decomposition);
+        let x = factorized.solve_matrix(&a, &b);
+        chapter2::assert_matrix_approx_eq!(&a * &x, b);
+        chapter2::assert_matrix_approx_eq!(direct.solve_matrix(&a, &b), x);
+        
+        chapter2::assert_matrix_approx_eq!(&a * &factorized.inverse(&a), Matrix::<5, 5>::identity());
+        
+        // the failed factorization as the error rather than a panic
+        let singular = Matrix::<5, 5>::from_fn(|i, j| (i + j) as f64);
+        let fallible = EquationSolver::new_fallible(|a, b| lu_decomposition(a).map(|lu| lu.solve(b)).ok_or(chapter2::SolveError::Singular));
+        assert!(matches!(factorized.try_solve_matrix(&singular, &b), Err(chapter2::SolveError::Singular)));
+        assert!(matches!(fallible.try_solve_matrix(&singular, &b), Err(chapter2::SolveError::Singular)));
+        assert!(matches!(factorized.try_inverse(&singular), Err(chapter2::SolveError::Singular)));
+    }
+    
+    #[test]
+    fn test_iterative_refinement() {
+        let a = chapter2::test_matrices::hilbert::<8>();
+        let b = Vector::<8>::random_uniform(-1.0, 1.0);
+        
+        let (x, count) = chapter2::lu::solve_with_refinement(&a, &b, 10).unwrap();
+        assert!(count >= 1);
+        assert!((&b - &a * &x).norm() <= (&b - &a * &solve_by_lu_decomposition(&a, &b)).norm() * 10.0);
+        
+        let stat = EquationSolver::<20>::new_lu().with_refinement(3).experiment_randomly();
+        assert!((1..=3).contains(&stat.refinement_count));
+        assert_eq!(EquationSolver::<20>::new_lu().experiment_randomly().refinement_count, 0);
+    }
+    
+    #[test]
+    fn test_scaled_partial_pivoting() {
+        // partial pivoting takes the large 2 of the first row, whose scale 1e5 makes it relatively small
+        let a = Matrix::<2, 2>::from([
+            [2.0, 1e5],
+            [1.0, 1.0],
+        ]);
+        assert_eq!(lu_decomposition(&a).unwrap().permutation(), &[0, 1]);
+        assert_eq!(chapter2::lu::scaled_partial_pivot_lu(&a).unwrap().permutation(), &[1, 0]);
+        
+        let a = Matrix::<6, 6>::random_uniform(-1.0, 1.0);
+        let b = Vector::<6>::random_uniform(-1.0, 1.0);
+        for pivoting in Pivoting::ALL {
+            chapter2::assert_matrix_approx_eq!(&a * &gaussian_elimination(&a, &b, pivoting).unwrap(), b, 1e-8);
+        }
+        
+        // capturing the strategy
+        for pivoting in Pivoting::ALL {
+            let solver = EquationSolver::new(move |a, b| gaussian_elimination(a, b, pivoting).unwrap());
+            chapter2::assert_matrix_approx_eq!(solver.solve(&a, &b), gaussian_elimination(&a, &b, pivoting).unwrap(), 0.0);
+        }
+    }
+    
+    #[test]
+    fn test_gauss_jordan() {
+        let a = Matrix::<3, 3>::from([
+            [0.0, 2.0, 1.0],
+            [1.0, 1.0, 0.0],
+            [3.0, 0.0, 1.0],
+        ]);
+        let b = Vector::<3>::from([[3.0], [2.0], [4.0]]);
+        chapter2::assert_matrix_approx_eq!(chapter2::gauss_jordan(&a, &b).unwrap(), Vector::<3>::from([[1.0], [1.0], [1.0]]));
+        
+        let (x, inverse) = chapter2::gauss_jordan_with_inverse(&a, &b).unwrap();
+        chapter2::assert_matrix_approx_eq!(x, Vector::<3>::from([[1.0], [1.0], [1.0]]));
+        chapter2::assert_matrix_approx_eq!(&a * &inverse, Matrix::<3, 3>::identity());
+        
+        assert!(chapter2::gauss_jordan(&Matrix::<2, 2>::from([[1.0, 2.0], [2.0, 4.0]]), &Vector::<2>::zeroed()).is_none());
+    }
+    
+    #[test]
+    fn test_compare() {
+        let stats = EquationSolver::compare(&[
+            ("lu", EquationSolver::<10>::new(solve_by_lu_decomposition::<10, f64>)),
+            ("cholesky_normal", EquationSolver::new(|a, b| EquationSolver::new_cholesky().solve(&(a.transpose() * a), &(a.transpose() * b)))),
+            ("lu_again", EquationSolver::new_lu()),
+        ], 5);
+        
+        assert_eq!(stats.iter().map(|&(name, ref stats)| (name, stats.len())).collect::<Vec<_>>(), [("lu", 5), ("cholesky_normal", 5), ("lu_again", 5)]);
+        for i in 0..5 {
+            let (_, lu) = &stats[0];
+            for (_, other) in &stats[1..] {
+                assert_eq!(other[i].condition_number, lu[i].condition_number);
+                chapter2::assert_matrix_approx_eq!(other[i].reference_solution, lu[i].reference_solution, 0.0);
             }
+            // the same algorithm on the same instance
+            chapter2::assert_matrix_approx_eq!(stats[2].1[i].solution, lu[i].solution, 0.0);
+        }
+        assert!((1..5).any(|i| stats[0].1[i].condition_number != stats[0].1[0].condition_number));
+    }
+    
+    #[test]
+    fn test_fallible() {
+        use chapter2::SolveError;
+        
+        let singular = Matrix::<3, 3>::from([
+            [1.0, 2.0, 3.0],
+            [4.0, 5.0, 6.0],
+            [7.0, 8.0, 9.0],
+        ]);
+        let b = Vector::<3>::from_fn(|_, _| 1.0);
+        assert_eq!(EquationSolver::new_lu().try_solve(&singular, &b).unwrap_err(), SolveError::Singular);
+        assert_eq!(EquationSolver::new_cholesky().try_experiment_on(&-Matrix::<3, 3>::identity(), &b).unwrap_err().to_string(), chapter2::NotPositiveDefinite { column: 0 }.to_string());
+        
+        let a = chapter2::test_matrices::poisson_2d::<16>();
+        let b = Vector::<16>::from_fn(|_, _| 1.0);
+        let jacobi = |max_iterations| EquationSolver::new_fallible(move |a, b| chapter2::jacobi(a, b, 1e-10, max_iterations).into_result());
+        assert_eq!(jacobi(5).try_solve(&a, &b).unwrap_err(), SolveError::NotConverged { iteration_count: 5 });
+        assert!(jacobi(10000).try_experiment_on(&a, &b).unwrap().residual_norm < 1e-8);
+        
+        let config = chapter2::ExperimentConfig::default().with_trials(4);
+        let outcomes = EquationSolver::<10>::new_fallible(|_, _| Err(SolveError::Singular)).try_experiments(&config);
+        assert!(outcomes.iter().all(|outcome| matches!(outcome, chapter2::TrialOutcome::Failed(message) if message == "Matrix is singular")));
+        assert_eq!(chapter2::ExperimentSummary::new(&outcomes).failure_rate(), Some(1.0));
+        let outcomes = EquationSolver::<10>::new_lu().try_experiments(&config);
+        assert_eq!(chapter2::ExperimentSummary::new(&outcomes).failure_rate(), Some(0.0));
+        assert_eq!(chapter2::ExperimentSummary::new(&EquationSolver::<10>::new_lu().experiments(&config)).failure_rate(), None);
+    }
+    
+    #[test]
+    fn test_ldlt_on_symmetric_indefinite() {
+        fn assert_ldlt<const N: usize>(a: &Matrix<N, N>) -> chapter2::Ldlt<N> {
+            let ldlt = chapter2::ldlt(a);
+            let (l, d, pi) = (&**ldlt.l(), ldlt.d(), ldlt.permutation());
+            let pap = Matrix::<N, N>::from_fn(|i, j| a[(pi[i], pi[j])]);
+            assert!((&(l * d) * &l.transpose() - pap).frobenius_norm() < 1e-12 * a.frobenius_norm());
+            
+            let b = Vector::<N>::random_uniform(-1.0, 1.0);
+            let x = ldlt.solve(&b).unwrap();
+            assert!((&(a * &x) - &b).norm() < 1e-12 * a.frobenius_norm() * x.norm());
+            ldlt
         }
+        
+        // the zero diagonal forces a 2 x 2 pivot
+        let ldlt = assert_ldlt(&Matrix::from([
+            [0.0, 1.0, 2.0, 0.5],
+            [1.0, 0.0, -1.0, 3.0],
+            [2.0, -1.0, 0.0, 1.0],
+            [0.5, 3.0, 1.0, 0.0],
+        ]));
+        assert_ne!(ldlt.d()[(1, 0)], 0.0);
+        
+        let a = Matrix::<20, 20>::random_uniform(-1.0, 1.0);
+        assert_ldlt(&(&a + &a.transpose()));
     }
 }
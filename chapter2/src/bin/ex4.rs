@@ -1,19 +1,20 @@
 use chapter2::{Matrix, Vector};
-use chapter2::{EPSILON, DominantEigenvalueSolver, DominantEigenvalueSolution};
+use chapter2::{EPSILON, DominantEigenvalueSolver, DominantEigenvalueSolution, PartialEigenSolver, ExperimentConfig, TrialOutcome};
+use chapter2::shifted_inverse_iteration;
 
 fn solve_by_power_iteration<const N: usize>(a: &Matrix<N, N>) -> DominantEigenvalueSolution<N> {
-    const MAX_ITERATIONS: usize = 1000_000;
+    const MAX_ITERATIONS: usize = 1_000_000;
     
     let mut mu = Vec::<f64>::new();
     let mut x_k = Vector::<N>::filled_with(1.0);
+    let mut y_k = Vector::<N>::zeroed();
     for count in 1..MAX_ITERATIONS {
-        let y_k = a * &x_k;
+        chapter2::check_deadline();
         
-        let (i, _max_abs) = x_k
-            .iter()
-            .enumerate()
-            .max_by(|(_, p), (_, q)| f64::partial_cmp(&p.abs(), &q.abs()).expect("found NaN or Inf"))
-            .expect("Vector is zero");
+        // y_k = A x_k
+        a.gemv(1.0, &x_k, 0.0, &mut y_k);
+        
+        let i = x_k.argmax_abs();
         
         let mu_k = y_k[i] / x_k[i];
         if mu.last().is_some_and(|it| (it.abs() - mu_k.abs()).abs() < EPSILON) {
@@ -24,53 +25,240 @@ fn solve_by_power_iteration<const N: usize>(a: &Matrix<N, N>) -> DominantEigenva
             };
         }
         
-        x_k = y_k.normalized();
+        std::mem::swap(&mut x_k, &mut y_k);
+        x_k.normalize();
         mu.push(mu_k);
     }
     
     panic!("`mu` seems to diverge");
 }
 
-fn plot_100_experiments<const N: usize>(solver: DominantEigenvalueSolver<N>) -> Result<(), Box<dyn std::error::Error>> {
-    let stats: [chapter2::DominantEigenvalueExperimentStat<N>; 100] = (0..100)
-        .map(|_| dbg!(solver.experiment_randomly()))
-        .collect::<Vec<_>>()
-        .try_into()
-        .unwrap();
+/// the stats of the trials completed within `config.timeout`, reporting how many of them
+/// diverged (panicked) or timed out instead of aborting all of them
+fn plot_experiments<const N: usize>(config: &ExperimentConfig, solver: DominantEigenvalueSolver<N>) -> Result<(), Box<dyn std::error::Error>> {
+    let outcomes = solver.try_experiments(config);
+    let failed = outcomes.iter().filter(|outcome| matches!(outcome, TrialOutcome::Failed(_))).count();
+    let timed_out = outcomes.iter().filter(|outcome| matches!(outcome, TrialOutcome::TimedOut(_))).count();
+    if failed + timed_out > 0 {
+        println!("N = {N}: {failed} failed and {timed_out} timed out of {} trials", outcomes.len());
+    }
+    let stats = outcomes.into_iter().filter_map(TrialOutcome::into_completed).collect::<Vec<_>>();
     
     chapter2::Plotter {
         y_desc: "residual norm",
-        data: stats.iter().map(|stat| stat.residual_norm).collect::<Vec<_>>().try_into().unwrap(),
+        data: stats.iter().map(|stat| stat.residual_norm).collect(),
     }.plot_into(format!("plot/ex4/n{N}-residual_norm.svg"))?;
     
     chapter2::Plotter {
         y_desc: "eigenvalue's relative error",
-        data: stats.iter().map(|stat| stat.eigenvalue_relative_error).collect::<Vec<_>>().try_into().unwrap(),
+        data: stats.iter().map(|stat| stat.eigenvalue_relative_error).collect(),
     }.plot_into(format!("plot/ex4/n{N}-eigenvalue_relative_error.svg"))?;
     
     chapter2::Plotter {
         y_desc: "eigenvector's relative error",
-        data: stats.iter().map(|stat| stat.eigenvector_relative_error).collect::<Vec<_>>().try_into().unwrap(),
+        data: stats.iter().map(|stat| stat.eigenvector_relative_error).collect(),
     }.plot_into(format!("plot/ex4/n{N}-eigenvector_relative_error.svg"))?;
     
     chapter2::Plotter {
         y_desc: "time elapsed (sec.)",
-        data: stats.iter().map(|stat| stat.elapsed.as_secs_f64()).collect::<Vec<_>>().try_into().unwrap(),
+        data: stats.iter().map(|stat| stat.elapsed.as_secs_f64()).collect(),
     }.plot_into(format!("plot/ex4/n{N}-time_elapsed.svg"))?;
     
     chapter2::Plotter {
         y_desc: "# of steps",
-        data: stats.iter().map(|stat| stat.iteration_count as f64).collect::<Vec<_>>().try_into().unwrap(),
+        data: stats.iter().map(|stat| stat.iteration_count as f64).collect(),
     }.plot_into(format!("plot/ex4/n{N}-iteration_count.svg"))?;
     
     Ok(())
 }
 
+/// `plot_experiments` on 100 non-symmetric A by `experiment_randomly_nonsymmetric`,
+/// compared with the true dominant eigenpair by `EigenReference::Eigen`
+fn plot_100_nonsymmetric_experiments<const N: usize>(solver: DominantEigenvalueSolver<N>) -> Result<(), Box<dyn std::error::Error>> {
+    let solver = solver.with_reference(chapter2::EigenReference::Eigen);
+    let stats = (0..100).map(|_| solver.experiment_randomly_nonsymmetric()).collect::<Vec<_>>();
+    
+    chapter2::Plotter {
+        y_desc: "residual norm",
+        data: stats.iter().map(|stat| stat.residual_norm).collect(),
+    }.plot_into(format!("plot/ex4/n{N}-nonsymmetric-residual_norm.svg"))?;
+    
+    // may be exactly 0 by chance
+    let errors = stats.iter().map(|stat| stat.eigenvalue_relative_error).collect::<Vec<_>>();
+    if errors.iter().all(|&error| error > 0.0) {
+        chapter2::Plotter {
+            y_desc: "eigenvalue's relative error",
+            data: errors,
+        }.plot_into(format!("plot/ex4/n{N}-nonsymmetric-eigenvalue_relative_error.svg"))?;
+    }
+    
+    chapter2::Plotter {
+        y_desc: "eigenvector's relative error",
+        data: stats.iter().map(|stat| stat.eigenvector_relative_error).collect(),
+    }.plot_into(format!("plot/ex4/n{N}-nonsymmetric-eigenvector_relative_error.svg"))?;
+    
+    chapter2::Plotter {
+        y_desc: "# of steps",
+        data: stats.iter().map(|stat| stat.iteration_count as f64).collect(),
+    }.plot_into(format!("plot/ex4/n{N}-nonsymmetric-iteration_count.svg"))?;
+    
+    Ok(())
+}
+
+/// plain and Aitken-accelerated iteration counts of the power iteration and their ratio
+/// on 100 random symmetric A of the eigenvalues N, N - 1, ..., 1, the slowly converging
+/// ratio (N - 1) / N, with the relative error of the accelerated eigenvalue from N
+fn plot_100_aitken_experiments<const N: usize>() -> Result<(), Box<dyn std::error::Error>> {
+    let spectrum = chapter2::DiagonalMatrix::from(Vector::<N>::from_fn(|i, _| (N - i) as f64));
+    let results = (0..100).map(|_| {
+        let a = Matrix::<N, N>::random_with_spectrum(&spectrum);
+        chapter2::aitken_power_iteration(&a, EPSILON).expect("Power iteration doesn't converge")
+    }).collect::<Vec<_>>();
+    
+    chapter2::Plotter {
+        y_desc: "# of steps (plain)",
+        data: results.iter().map(|r| r.plain_iteration_count as f64).collect(),
+    }.plot_into(format!("plot/ex4/n{N}-aitken-plain_iteration_count.svg"))?;
+    chapter2::Plotter {
+        y_desc: "# of steps (accelerated)",
+        data: results.iter().map(|r| r.solution.iteration_count as f64).collect(),
+    }.plot_into(format!("plot/ex4/n{N}-aitken-accelerated_iteration_count.svg"))?;
+    chapter2::Plotter {
+        y_desc: "speedup",
+        data: results.iter().map(|r| r.plain_iteration_count as f64 / r.solution.iteration_count as f64).collect(),
+    }.plot_into(format!("plot/ex4/n{N}-aitken-speedup.svg"))?;
+    
+    // may be exactly 0 for the integral eigenvalue
+    let errors = results.iter().map(|r| (r.solution.eigenvalue - N as f64).abs() / N as f64).collect::<Vec<_>>();
+    if errors.iter().all(|&error| error > 0.0) {
+        chapter2::Plotter {
+            y_desc: "eigenvalue's relative error",
+            data: errors,
+        }.plot_into(format!("plot/ex4/n{N}-aitken-eigenvalue_relative_error.svg"))?;
+    }
+    
+    Ok(())
+}
+
+/// eigenvalues of a symmetric A by nalgebra
+fn reference_eigenvalues<const N: usize>(a: &Matrix<N, N>) -> Vec<f64> {
+    nalgebra::DMatrix::from_column_slice(N, N, a.as_slice())
+        .symmetric_eigenvalues()
+        .iter()
+        .copied()
+        .collect()
+}
+
+/// `shifted_inverse_iteration` on 100 random symmetric A refining a coarse estimate σ of
+/// a random eigenvalue λ_j, off by 10% of the gap to its nearest neighbour
+fn plot_100_shifted_inverse_iterations<const N: usize>() -> Result<(), Box<dyn std::error::Error>> {
+    let (residual_norms, iteration_counts): (Vec<_>, Vec<_>) = (0..100).map(|_| {
+        let random = Matrix::<N, N>::random_uniform(-1.0, 1.0);
+        let a = &random + random.transpose();
+        let mut eigenvalues = reference_eigenvalues(&a);
+        eigenvalues.sort_by(f64::total_cmp);
+        let j = rand::random_range(0..N);
+        let gap = [j.checked_sub(1), Some(j + 1).filter(|&k| k < N)]
+            .into_iter()
+            .flatten()
+            .map(|k| (eigenvalues[k] - eigenvalues[j]).abs())
+            .fold(f64::INFINITY, f64::min);
+        let sigma = eigenvalues[j] + if rand::random() { 0.1 } else { -0.1 } * gap;
+        
+        let solution = shifted_inverse_iteration(&a, sigma, EPSILON).expect("not converged");
+        assert!((solution.eigenvalue - eigenvalues[j]).abs() < 1e-8, "not the eigenvalue nearest σ");
+        let residual_norm = (&a * &solution.eigenvector - solution.eigenvalue * &solution.eigenvector).norm();
+        (residual_norm, solution.iteration_count as f64)
+    }).unzip();
+    
+    chapter2::Plotter {
+        y_desc: "residual norm",
+        data: residual_norms,
+    }.plot_into(format!("plot/ex4/n{N}-shifted_inverse_iteration-residual_norm.svg"))?;
+    chapter2::Plotter {
+        y_desc: "# of steps",
+        data: iteration_counts,
+    }.plot_into(format!("plot/ex4/n{N}-shifted_inverse_iteration-iteration_count.svg"))?;
+    
+    Ok(())
+}
+
+/// accuracy of each of the `K` largest eigenpairs by `solver` on 100 random instances,
+/// decreasing for the later ones found after more deflations
+fn plot_100_partial_eigen_experiments<const N: usize, const K: usize>(solver: PartialEigenSolver<N>) -> Result<(), Box<dyn std::error::Error>> {
+    let stats = (0..100).map(|_| solver.experiment_randomly()).collect::<Vec<_>>();
+    
+    for j in 0..K {
+        chapter2::Plotter {
+            y_desc: "residual norm",
+            data: stats.iter().map(|stat| stat.residual_norms[j]).collect(),
+        }.plot_into(format!("plot/ex4/n{N}-deflation-eigenpair{j}-residual_norm.svg"))?;
+        
+        // may be exactly 0 for the integral eigenvalues
+        let errors = stats.iter().map(|stat| stat.eigenvalue_relative_errors[j]).collect::<Vec<_>>();
+        if errors.iter().all(|&error| error > 0.0) {
+            chapter2::Plotter {
+                y_desc: "eigenvalue's relative error",
+                data: errors,
+            }.plot_into(format!("plot/ex4/n{N}-deflation-eigenpair{j}-eigenvalue_relative_error.svg"))?;
+        }
+        
+        chapter2::Plotter {
+            y_desc: "# of steps",
+            data: stats.iter().map(|stat| stat.iteration_counts[j] as f64).collect(),
+        }.plot_into(format!("plot/ex4/n{N}-deflation-eigenpair{j}-iteration_count.svg"))?;
+    }
+    
+    Ok(())
+}
+
+/// a symmetric A whose P largest eigenvalues are `ratio` (1 + 0.1 (P - 1)), ..., `ratio`
+/// above the rest 1, 1 - 1 / N, ..., so that λ_P / λ_{P+1} = `ratio`
+fn random_with_gap<const N: usize, const P: usize>(ratio: f64) -> Matrix<N, N> {
+    Matrix::<N, N>::random_with_spectrum(&chapter2::DiagonalMatrix::from(Vector::<N>::from_fn(|i, _| {
+        if i < P { ratio * (1.0 + 0.1 * (P - 1 - i) as f64) } else { 1.0 - (i - P) as f64 / N as f64 }
+    })))
+}
+
+/// relative residuals of the subspace iteration of dimension P against the steps
+/// for the gaps λ_P / λ_{P+1} = 1.1, 1.5, 2, 4, decaying by λ_{P+1} / λ_P
+fn plot_subspace_iteration_convergence<const N: usize, const P: usize>() -> Result<(), Box<dyn std::error::Error>> {
+    let histories = [("gap 1.1", 1.1), ("gap 1.5", 1.5), ("gap 2", 2.0), ("gap 4", 4.0)].into_iter().map(|(name, ratio)| {
+        let a = random_with_gap::<N, P>(ratio);
+        (name, chapter2::subspace_iteration::<N, P>(&a, 1, EPSILON).expect("subspace iteration doesn't converge").residual_norms)
+    }).collect();
+    chapter2::ConvergencePlotter {
+        y_desc: "relative residual of the subspace",
+        histories,
+    }.plot_into(format!("plot/ex4/n{N}-p{P}-subspace_iteration-convergence.svg"))
+}
+
+/// iteration counts of the subspace iteration of dimension P for the 100 gaps
+/// λ_P / λ_{P+1} = 1.05, 1.10, ..., 6.00, roughly log(`EPSILON`) / log(λ_{P+1} / λ_P)
+fn plot_subspace_iteration_gap_dependence<const N: usize, const P: usize>() -> Result<(), Box<dyn std::error::Error>> {
+    let iteration_counts = (1..=100).map(|j| {
+        let a = random_with_gap::<N, P>(1.0 + 0.05 * j as f64);
+        chapter2::subspace_iteration::<N, P>(&a, 1, EPSILON).expect("subspace iteration doesn't converge").iteration_count as f64
+    }).collect::<Vec<_>>();
+    chapter2::Plotter {
+        y_desc: "# of steps",
+        data: iteration_counts,
+    }.plot_into(format!("plot/ex4/n{N}-p{P}-subspace_iteration-gap_dependence.svg"))
+}
+
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    plot_100_experiments(DominantEigenvalueSolver::new(solve_by_power_iteration::<50>))?;
-    plot_100_experiments(DominantEigenvalueSolver::new(solve_by_power_iteration::<100>))?;
-    plot_100_experiments(DominantEigenvalueSolver::new(solve_by_power_iteration::<200>))?;
-    plot_100_experiments(DominantEigenvalueSolver::new(solve_by_power_iteration::<400>))?;
+    plot_100_partial_eigen_experiments::<50, 5>(PartialEigenSolver::new_deflation(5))?;
+    plot_100_shifted_inverse_iterations::<100>()?;
+    plot_subspace_iteration_convergence::<100, 5>()?;
+    plot_subspace_iteration_gap_dependence::<100, 5>()?;
+    plot_100_aitken_experiments::<20>()?;
+    plot_100_aitken_experiments::<50>()?;
+    plot_100_nonsymmetric_experiments(DominantEigenvalueSolver::new(solve_by_power_iteration::<50>))?;
+    plot_100_nonsymmetric_experiments(DominantEigenvalueSolver::new(solve_by_power_iteration::<100>))?;
+    let config = ExperimentConfig::default().with_timeout(std::time::Duration::from_secs(10));
+    chapter2::sweep_sizes!(&config, [50, 100, 200, 400], |N| {
+        plot_experiments(&config, DominantEigenvalueSolver::new(solve_by_power_iteration::<N>))?
+    });
     Ok(())
 }
 
@@ -93,4 +281,162 @@ mod tests {
             1., f64::sqrt(2.), 1.
         ]).normalized()).norm() < EPSILON);
     }
+    
+    #[test]
+    fn test_shifted_inverse_iteration() {
+        // eigenvalues 2 - sqrt(2), 2, 2 + sqrt(2) with the eigenvectors (1, ∓sqrt(2), 1), (1, 0, -1)
+        let a = Matrix::<3, 3>::from([
+            [2.0, 1.0, 0.0],
+            [1.0, 2.0, 1.0],
+            [0.0, 1.0, 2.0],
+        ]);
+        for (sigma, eigenvalue, eigenvector) in [
+            (0.0, 2.0 - f64::sqrt(2.), [1., -f64::sqrt(2.), 1.]),
+            (1.9, 2.0, [1., 0., -1.]),
+            (10.0, 2.0 + f64::sqrt(2.), [1., f64::sqrt(2.), 1.]),
+        ] {
+            let solution = shifted_inverse_iteration(&a, sigma, EPSILON).unwrap();
+            assert!((solution.eigenvalue - eigenvalue).abs() < 1e-8, "{sigma}: {}", solution.eigenvalue);
+            let eigenvector = Vector::<3>::from(eigenvector).normalized();
+            assert!(solution.eigenvector.dot(&eigenvector).abs() > 1.0 - 1e-8);
+        }
+        // σ exactly an eigenvalue
+        assert!(shifted_inverse_iteration(&a, 2.0, EPSILON).is_none());
+        
+        // refining the coarse estimate of the power iteration to the dominant eigenvalue in a few steps
+        let coarse = solve_by_power_iteration(&a).eigenvalue + 1e-3;
+        let refined = shifted_inverse_iteration(&a, coarse, EPSILON).unwrap();
+        assert!((refined.eigenvalue - (2.0 + f64::sqrt(2.))).abs() < 1e-10);
+        assert!(refined.iteration_count <= 5, "{}", refined.iteration_count);
+        
+        // as a solver capturing σ and the tolerance
+        let (sigma, tolerance) = (10.0, EPSILON);
+        let stat = DominantEigenvalueSolver::new(move |a| shifted_inverse_iteration(a, sigma, tolerance).unwrap()).experiment_on(&a);
+        assert!(stat.eigenvalue_relative_error < 1e-10, "{}", stat.eigenvalue_relative_error);
+    }
+    
+    #[test]
+    fn test_deflated_power_iteration() {
+        let a = Matrix::<3, 3>::from([
+            [2.0, 1.0, 0.0],
+            [1.0, 2.0, 1.0],
+            [0.0, 1.0, 2.0],
+        ]);
+        let eigenpairs = chapter2::deflated_power_iteration(&a, 3, EPSILON).unwrap();
+        for (eigenpair, eigenvalue) in eigenpairs.iter().zip([2.0 + f64::sqrt(2.), 2.0, 2.0 - f64::sqrt(2.)]) {
+            assert!((eigenpair.eigenvalue - eigenvalue).abs() < 1e-8, "{}", eigenpair.eigenvalue);
+            let residual = &a * &eigenpair.eigenvector - eigenpair.eigenvalue * &eigenpair.eigenvector;
+            assert!(residual.norm() < 1e-8);
+        }
+        
+        let stat = PartialEigenSolver::<10>::new_deflation(3).experiment_randomly();
+        assert_eq!(stat.solution.len(), 3);
+        for j in 0..3 {
+            assert!((stat.solution[j].0 - (10 - j) as f64).abs() < 1e-6, "{:?}", stat.solution[j].0);
+            assert!(stat.eigenvalue_relative_errors[j] < 1e-8);
+            assert!(stat.eigenvector_angles[j] < 1e-6);
+        }
+    }
+    
+    #[test]
+    fn test_subspace_iteration() {
+        let a = random_with_gap::<30, 3>(2.0);
+        for interval in [1, 5] {
+            let subspace = chapter2::subspace_iteration::<30, 3>(&a, interval, EPSILON).unwrap();
+            assert_eq!(subspace.iteration_count % interval, 0);
+            assert_eq!(subspace.residual_norms.len(), subspace.iteration_count / interval);
+            chapter2::assert_matrix_approx_eq!(subspace.eigenvalues, Vector::<3>::from([2.4, 2.2, 2.0]), 1e-8);
+            let v = &subspace.eigenvectors;
+            chapter2::assert_matrix_approx_eq!(&v.transpose() * v, Matrix::<3, 3>::identity());
+            chapter2::assert_matrix_approx_eq!(&a * v, v * &chapter2::DiagonalMatrix::from(subspace.eigenvalues.clone()), 1e-8);
+        }
+        
+        // slower for the smaller gap
+        let count = |ratio| chapter2::subspace_iteration::<30, 3>(&random_with_gap::<30, 3>(ratio), 1, EPSILON).unwrap().iteration_count;
+        assert!(count(1.2) > 2 * count(2.0));
+    }
+    
+    #[test]
+    fn test_spectral_radius_and_norm2_estimates() {
+        // ρ = ||A||_2 = 2 + 2 cos(π / 21) for the symmetric tridiag(-1, 2, -1)
+        let a = chapter2::test_matrices::tridiagonal::<20>(-1.0, 2.0, -1.0);
+        let exact = 2.0 + 2.0 * (std::f64::consts::PI / 21.0).cos();
+        let rho = chapter2::spectral_radius_estimate(&a, 1e-12).unwrap();
+        assert!((rho - exact).abs() < 1e-6 * exact, "{rho}");
+        let norm2 = chapter2::norm2_estimate(&a, 1e-12).unwrap();
+        assert!((norm2 - exact).abs() < 1e-6 * exact, "{norm2}");
+        
+        // ρ_J = cos(π h) of the Jacobi iteration matrix I - D^{-1} A on the model Poisson problem
+        let a = chapter2::test_matrices::poisson_2d::<100>();
+        let jacobi = Matrix::<100, 100>::from_fn(|i, j| if i == j { 0.0 } else { -a[(i, j)] / a[(i, i)] });
+        let rho = chapter2::spectral_radius_estimate(&jacobi, 1e-12).unwrap();
+        let exact = (std::f64::consts::PI / 11.0).cos();
+        assert!((rho - exact).abs() < 1e-4, "{rho}");
+        
+        // a rectangular, non-symmetric A against the singular values
+        let a = Matrix::<30, 10>::random_uniform(-1.0, 1.0);
+        let sigma_max = nalgebra::DMatrix::from_fn(30, 10, |i, j| a[(i, j)]).singular_values().max();
+        let norm2 = chapter2::norm2_estimate(&a, 1e-12).unwrap();
+        assert!(norm2 <= sigma_max * (1.0 + 1e-12) && (norm2 - sigma_max).abs() < 1e-4 * sigma_max, "{norm2} vs {sigma_max}");
+    }
+    
+    #[test]
+    fn test_eigen_references() {
+        for reference in [chapter2::EigenReference::SingularValue, chapter2::EigenReference::SymmetricEigen, chapter2::EigenReference::Eigen] {
+            let solver = DominantEigenvalueSolver::<20>::new(solve_by_power_iteration::<20>).with_reference(reference);
+            let stat = solver.experiment_randomly();
+            assert!(stat.eigenvalue_relative_error < 1e-6, "{reference:?}: {stat:?}");
+            assert!(stat.eigenvector_angle < 1e-3, "{reference:?}: {stat:?}");
+        }
+        
+        // the singular value is no eigenvalue of a non-symmetric A
+        let solver = DominantEigenvalueSolver::<20>::new(solve_by_power_iteration::<20>);
+        let stat = solver.experiment_randomly_nonsymmetric();
+        assert!(stat.eigenvalue_relative_error > 1e-3, "{stat:?}");
+        let stat = solver.with_reference(chapter2::EigenReference::Eigen).experiment_randomly_nonsymmetric();
+        assert!(stat.eigenvalue_relative_error < 1e-8, "{stat:?}");
+        assert!(stat.eigenvector_relative_error < 1e-6, "{stat:?}");
+    }
+    
+    #[test]
+    fn test_aitken_power_iteration() {
+        let a = Matrix::<20, 20>::random_with_spectrum(&chapter2::DiagonalMatrix::from(Vector::<20>::from_fn(|i, _| (20 - i) as f64)));
+        let result = chapter2::aitken_power_iteration(&a, EPSILON).unwrap();
+        assert!((result.solution.eigenvalue - 20.0).abs() < 1e-6, "{}", result.solution.eigenvalue);
+        assert!(result.solution.iteration_count < result.plain_iteration_count, "{result:?}");
+    }
+    
+    #[test]
+    fn test_experiment_on() {
+        // the eigenvalues 1.567, 0.2085, 0.01141, ... well separated
+        let a = chapter2::test_matrices::hilbert::<5>();
+        
+        let stat = DominantEigenvalueSolver::new(solve_by_power_iteration::<5>).experiment_on(&a);
+        assert!((stat.reference_solution.0 - 1.567051).abs() < 1e-6);
+        assert!(stat.eigenvalue_relative_error < 1e-8, "{stat:?}");
+        
+        let stat = PartialEigenSolver::<5>::new_deflation(2).experiment_on(&a);
+        assert!((stat.reference_solution[1].0 - 0.2085342).abs() < 1e-6);
+        assert!(stat.eigenvalue_relative_errors.iter().all(|&error| error < 1e-6), "{stat:?}");
+    }
+    
+    #[test]
+    fn test_try_experiments() {
+        let config = ExperimentConfig::default().with_trials(3).with_timeout(std::time::Duration::from_millis(50));
+        
+        let outcomes = DominantEigenvalueSolver::new(solve_by_power_iteration::<10>).try_experiments(&config);
+        assert!(outcomes.iter().all(|outcome| outcome.completed().is_some_and(|stat| stat.residual_norm < 1e-6)));
+        
+        let outcomes = DominantEigenvalueSolver::<10>::new(|_| loop { chapter2::check_deadline() }).try_experiments(&config);
+        assert!(outcomes.iter().all(|outcome| matches!(outcome, TrialOutcome::TimedOut(elapsed) if *elapsed >= config.timeout.unwrap())));
+        
+        let outcomes = DominantEigenvalueSolver::<10>::new(|_| panic!("`mu` seems to diverge")).try_experiments(&config);
+        assert!(outcomes.iter().all(|outcome| matches!(outcome, TrialOutcome::Failed(message) if message == "`mu` seems to diverge")));
+        let summary = chapter2::ExperimentSummary::new(&outcomes);
+        assert_eq!((summary.metric("failed").unwrap().mean, summary.metric("timed_out").unwrap().mean), (1.0, 0.0));
+        assert!(summary.metric("residual_norm").is_none());
+        
+        // outside `try_run`
+        chapter2::check_deadline();
+    }
 }
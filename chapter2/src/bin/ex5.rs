@@ -0,0 +1,437 @@
+use chapter2::{Matrix, Vector, DiagonalMatrix};
+use chapter2::AllEigenvaluesSolver;
+
+/// on 100 random symmetric A of the eigenvalues N, N - 1, ..., 1 by `experiment_randomly`,
+/// the ratios (N - 1) / N, ..., 1 / 2 of which the unshifted QR algorithm converges by
+fn plot_100_experiments<const N: usize>(name: &str, solver: AllEigenvaluesSolver<N>) -> Result<(), Box<dyn std::error::Error>> {
+    let stats = (0..100).map(|_| solver.experiment_randomly()).collect::<Vec<_>>();
+    
+    chapter2::Plotter {
+        y_desc: "residual norm",
+        data: stats.iter().map(|stat| stat.residual_norm).collect(),
+    }.plot_into(format!("plot/ex5/n{N}-{name}-residual_norm.svg"))?;
+    
+    // may be exactly 0 for the integral eigenvalues, or the eigenvectors equal up to rounding
+    for (y_desc, file, errors) in [
+        ("max eigenvalues' relative error", "max_eigenvalues_relative_error", stats.iter().map(|stat| stat.max_eigenvalues_relative_error).collect::<Vec<_>>()),
+        ("max eigenvectors' angle", "max_eigenvector_angle", stats.iter().map(|stat| stat.max_eigenvector_angle).collect::<Vec<_>>()),
+    ] {
+        if errors.iter().all(|&error| error > 0.0) {
+            chapter2::Plotter {
+                y_desc,
+                data: errors,
+            }.plot_into(format!("plot/ex5/n{N}-{name}-{file}.svg"))?;
+        }
+    }
+    
+    chapter2::Plotter {
+        y_desc: "# of steps",
+        data: stats.iter().map(|stat| stat.iteration_count as f64).collect(),
+    }.plot_into(format!("plot/ex5/n{N}-{name}-iteration_count.svg"))?;
+    chapter2::Plotter {
+        y_desc: "time elapsed (sec.)",
+        data: stats.iter().map(|stat| stat.elapsed.as_secs_f64()).collect(),
+    }.plot_into(format!("plot/ex5/n{N}-{name}-time_elapsed.svg"))?;
+    
+    Ok(())
+}
+
+/// off(A) of the Jacobi method against the sweeps on a random symmetric A,
+/// decaying quadratically after the first few sweeps
+fn plot_jacobi_off_diagonal_decay<const N: usize>() -> Result<(), Box<dyn std::error::Error>> {
+    let random = Matrix::<N, N>::random_uniform(-1.0, 1.0);
+    let a = &random + random.transpose();
+    let (_, off_diagonal_norms) = chapter2::jacobi_eigenvalue_method(&a, chapter2::EPSILON).expect("Jacobi method doesn't converge");
+    chapter2::ConvergencePlotter {
+        y_desc: "off-diagonal norm",
+        histories: vec![("jacobi", off_diagonal_norms)],
+    }.plot_into(format!("plot/ex5/n{N}-jacobi-off_diagonal_norm.svg"))
+}
+
+/// relative errors of the largest and smallest Ritz values of the leading T_k (k = 1, ..., K)
+/// of one Lanczos process on the sparse model Poisson problem, with and without the reorthogonalization,
+/// floored at the machine epsilon for the log scale
+fn plot_lanczos_convergence<const N: usize, const K: usize>() -> Result<(), Box<dyn std::error::Error>> {
+    let a = chapter2::test_matrices::poisson_2d_sparse::<N>();
+    let h = 1.0 / ((N as f64).sqrt() + 1.0);
+    let (lambda_max, lambda_min) = (4.0 + 4.0 * (std::f64::consts::PI * h).cos(), 4.0 - 4.0 * (std::f64::consts::PI * h).cos());
+    
+    let mut histories = vec![];
+    for reorthogonalize in [false, true] {
+        let t = chapter2::lanczos::<N, K>(&a, reorthogonalize).expect("Lanczos breaks down").tridiagonal.to_dense();
+        let (largest, smallest): (Vec<_>, Vec<_>) = (1..=K).map(|k| {
+            let ritz_values = nalgebra::DMatrix::from_fn(k, k, |i, j| t[(i, j)]).symmetric_eigenvalues();
+            (
+                ((ritz_values.max() - lambda_max).abs() / lambda_max).max(f64::EPSILON),
+                ((ritz_values.min() - lambda_min).abs() / lambda_min).max(f64::EPSILON),
+            )
+        }).unzip();
+        histories.push((if reorthogonalize { "largest (reorthogonalized)" } else { "largest" }, largest));
+        histories.push((if reorthogonalize { "smallest (reorthogonalized)" } else { "smallest" }, smallest));
+    }
+    chapter2::ConvergencePlotter {
+        y_desc: "relative error of the Ritz value",
+        histories,
+    }.plot_into(format!("plot/ex5/n{N}-poisson_2d-lanczos_convergence.svg"))
+}
+
+/// errors of the largest Ritz value of the leading H_k (k = 1, ..., K) of one Arnoldi process
+/// on a nonsymmetric A whose spectrum is the disk |λ| <= 1 (circular law) and the outlier near 3
+/// from a rank-one perturbation, together with the modulus of the largest Ritz value in the disk,
+/// floored at the machine epsilon for the log scale
+fn plot_arnoldi_convergence<const N: usize, const K: usize>() -> Result<(), Box<dyn std::error::Error>> {
+    let random = Matrix::<N, N>::random_uniform(-1.0, 1.0);
+    let dense = Matrix::<N, N>::from_fn(|i, j| random[(i, j)] / (N as f64 / 3.0).sqrt() + 3.0 / N as f64);
+    let mut eigenvalues = nalgebra::DMatrix::from_fn(N, N, |i, j| dense[(i, j)]).complex_eigenvalues().iter().copied().collect::<Vec<_>>();
+    eigenvalues.sort_by(|x, y| y.norm().total_cmp(&x.norm()));
+    
+    let arnoldi = chapter2::arnoldi(&chapter2::SparseMatrix::from(&dense), &Vector::<N>::random_uniform(-1.0, 1.0), K);
+    let h = &arnoldi.hessenberg;
+    let (outlier, disk): (Vec<_>, Vec<_>) = (1..=arnoldi.steps()).map(|k| {
+        let mut ritz_values = nalgebra::DMatrix::from_fn(k, k, |i, j| h[(i, j)]).complex_eigenvalues().iter().copied().collect::<Vec<_>>();
+        ritz_values.sort_by(|x, y| y.norm().total_cmp(&x.norm()));
+        (
+            ((ritz_values[0] - eigenvalues[0]).norm() / eigenvalues[0].norm()).max(f64::EPSILON),
+            ritz_values.get(1).map_or(f64::EPSILON, |ritz_value| ritz_value.norm().max(f64::EPSILON)),
+        )
+    }).unzip();
+    chapter2::ConvergencePlotter {
+        y_desc: "Ritz value",
+        histories: vec![("relative error of the outlier", outlier), ("modulus in the disk", disk)],
+    }.plot_into(format!("plot/ex5/n{N}-nonsymmetric-arnoldi_convergence.svg"))
+}
+
+/// errors of all the eigenvalues of the 1D Poisson tridiag(-1, 2, -1) by the bisection,
+/// against the exact 2 - 2 cos(k π / (N + 1)), floored at the machine epsilon for the log scale
+fn plot_bisection_errors<const N: usize>() -> Result<(), Box<dyn std::error::Error>> {
+    let t = chapter2::BandedMatrix::<N>::tridiagonal(-1.0, 2.0, -1.0);
+    let eigenvalues = chapter2::bisection_eigenvalues(&t, chapter2::gershgorin_interval(&t), f64::EPSILON);
+    chapter2::Plotter {
+        y_desc: "absolute error",
+        data: eigenvalues.iter().enumerate().map(|(k, lambda)| {
+            let exact = 2.0 - 2.0 * ((k + 1) as f64 * std::f64::consts::PI / (N + 1) as f64).cos();
+            (lambda - exact).abs().max(f64::EPSILON)
+        }).collect(),
+    }.plot_into(format!("plot/ex5/n{N}-poisson_1d-bisection_error.svg"))
+}
+
+/// time of only the 10 smallest eigenvalues by `tridiagonalize` and the bisection on [0, 10.5),
+/// on 100 random symmetric A of the eigenvalues 1, 2, ..., N, to be compared with all by `symmetric_qr`
+fn plot_100_selective_bisections<const N: usize>() -> Result<(), Box<dyn std::error::Error>> {
+    let spectrum = DiagonalMatrix::from(Vector::<N>::from_fn(|i, _| (i + 1) as f64));
+    let elapsed = (0..100).map(|_| {
+        let a = Matrix::<N, N>::random_with_spectrum(&spectrum);
+        let start = std::time::Instant::now();
+        let (t, _) = chapter2::tridiagonalize(&a);
+        let eigenvalues = chapter2::bisection_eigenvalues(&t, 0.0..10.5, f64::EPSILON);
+        let elapsed = start.elapsed().as_secs_f64();
+        assert_eq!(eigenvalues.len(), 10);
+        elapsed
+    }).collect::<Vec<_>>();
+    chapter2::Plotter {
+        y_desc: "time elapsed (sec.)",
+        data: elapsed,
+    }.plot_into(format!("plot/ex5/n{N}-bisection_10_smallest-time_elapsed.svg"))
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    std::fs::create_dir_all("plot/ex5")?;
+    plot_100_experiments::<10>("unshifted_qr", AllEigenvaluesSolver::new_unshifted_qr())?;
+    plot_100_experiments::<20>("unshifted_qr", AllEigenvaluesSolver::new_unshifted_qr())?;
+    plot_100_experiments::<20>("symmetric_qr", AllEigenvaluesSolver::new_symmetric_qr())?;
+    plot_100_experiments::<20>("jacobi", AllEigenvaluesSolver::new_jacobi())?;
+    plot_100_experiments::<50>("jacobi", AllEigenvaluesSolver::new_jacobi())?;
+    plot_jacobi_off_diagonal_decay::<50>()?;
+    plot_lanczos_convergence::<2500, 100>()?;
+    plot_arnoldi_convergence::<200, 60>()?;
+    plot_bisection_errors::<100>()?;
+    plot_100_selective_bisections::<200>()?;
+    plot_100_experiments::<200>("symmetric_qr", AllEigenvaluesSolver::new_symmetric_qr())?;
+    plot_100_experiments::<400>("symmetric_qr", AllEigenvaluesSolver::new_symmetric_qr())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    /// ||A V - V Λ||_F of all the eigenpairs at once
+    fn residual_norm<const N: usize>(a: &Matrix<N, N>, solution: &chapter2::AllEigenvaluesSolution<N>) -> f64 {
+        let v = &solution.eigenvectors;
+        (&(a * v) - &(v * &DiagonalMatrix::from(solution.eigenvalues.clone()))).frobenius_norm()
+    }
+    
+    #[test]
+    fn test_qr_algorithm() {
+        let a = Matrix::<3, 3>::from([
+            [2.0, 1.0, 0.0],
+            [1.0, 2.0, 1.0],
+            [0.0, 1.0, 2.0],
+        ]);
+        let solution = AllEigenvaluesSolver::new_unshifted_qr().solve(&a);
+        chapter2::assert_matrix_approx_eq!(solution.eigenvalues, Vector::<3>::from([2.0 + f64::sqrt(2.), 2.0, 2.0 - f64::sqrt(2.)]), 1e-8);
+        chapter2::assert_matrix_approx_eq!(&solution.eigenvectors.transpose() * &solution.eigenvectors, Matrix::<3, 3>::identity());
+        assert!(residual_norm(&a, &solution) < 1e-8);
+        
+        // the rotation by π/2 with the eigenvalues ±i
+        let rotation = Matrix::<2, 2>::from([
+            [0.0, -1.0],
+            [1.0, 0.0],
+        ]);
+        assert!(chapter2::qr_algorithm(&rotation, chapter2::EPSILON).is_none());
+    }
+    
+    #[test]
+    fn test_symmetric_qr_algorithm() {
+        let a = Matrix::<3, 3>::from([
+            [2.0, 1.0, 0.0],
+            [1.0, 2.0, 1.0],
+            [0.0, 1.0, 2.0],
+        ]);
+        let solution = AllEigenvaluesSolver::new_symmetric_qr().solve(&a);
+        chapter2::assert_matrix_approx_eq!(solution.eigenvalues, Vector::<3>::from([2.0 + f64::sqrt(2.), 2.0, 2.0 - f64::sqrt(2.)]));
+        assert!(residual_norm(&a, &solution) < 1e-12);
+        
+        // ±λ pairs and clusters, for which the unshifted one doesn't converge
+        let random = Matrix::<30, 30>::random_uniform(-1.0, 1.0);
+        let a = &random + random.transpose();
+        let solution = AllEigenvaluesSolver::new_symmetric_qr().solve(&a);
+        let mut reference = nalgebra::DMatrix::from_column_slice(30, 30, a.as_slice())
+            .symmetric_eigenvalues()
+            .iter()
+            .copied()
+            .collect::<Vec<_>>();
+        reference.sort_by(|x, y| y.total_cmp(x));
+        chapter2::assert_matrix_approx_eq!(solution.eigenvalues, Vector::<30>::try_from(reference.as_slice()).unwrap());
+        chapter2::assert_matrix_approx_eq!(&solution.eigenvectors.transpose() * &solution.eigenvectors, Matrix::<30, 30>::identity());
+        assert!(residual_norm(&a, &solution) < 1e-12 * a.frobenius_norm());
+        // about 2 steps per eigenvalue by the cubic convergence
+        assert!(solution.iteration_count <= 3 * 30, "{}", solution.iteration_count);
+        
+        let diagonal = Matrix::<4, 4>::identity();
+        assert_eq!(AllEigenvaluesSolver::new_symmetric_qr().solve(&diagonal).iteration_count, 0);
+    }
+    
+    #[test]
+    fn test_jacobi_eigenvalue_method() {
+        let a = Matrix::<3, 3>::from([
+            [2.0, 1.0, 0.0],
+            [1.0, 2.0, 1.0],
+            [0.0, 1.0, 2.0],
+        ]);
+        let solution = AllEigenvaluesSolver::new_jacobi().solve(&a);
+        chapter2::assert_matrix_approx_eq!(solution.eigenvalues, Vector::<3>::from([2.0 + f64::sqrt(2.), 2.0, 2.0 - f64::sqrt(2.)]));
+        assert!(residual_norm(&a, &solution) < 1e-10);
+        
+        let random = Matrix::<30, 30>::random_uniform(-1.0, 1.0);
+        let a = &random + random.transpose();
+        let (solution, off_diagonal_norms) = chapter2::jacobi_eigenvalue_method(&a, chapter2::EPSILON).unwrap();
+        let reference = AllEigenvaluesSolver::new_symmetric_qr().solve(&a);
+        chapter2::assert_matrix_approx_eq!(solution.eigenvalues, reference.eigenvalues, 1e-8);
+        chapter2::assert_matrix_approx_eq!(&solution.eigenvectors.transpose() * &solution.eigenvectors, Matrix::<30, 30>::identity());
+        assert!(residual_norm(&a, &solution) < 1e-8 * a.frobenius_norm());
+        
+        assert_eq!(off_diagonal_norms.len(), solution.iteration_count + 1);
+        assert!(off_diagonal_norms.windows(2).all(|w| w[1] < w[0]));
+        assert!(solution.iteration_count <= 10, "{}", solution.iteration_count);
+    }
+    
+    #[test]
+    fn test_lanczos() {
+        let a = chapter2::test_matrices::poisson_2d_sparse::<400>();
+        chapter2::assert_matrix_approx_eq!(a.to_dense(), chapter2::test_matrices::poisson_2d::<400>());
+        let lambda_max = 4.0 + 4.0 * (std::f64::consts::PI / 21.0).cos();
+        
+        let lanczos = chapter2::lanczos::<400, 40>(&a, true).unwrap();
+        let q = &lanczos.basis;
+        chapter2::assert_matrix_approx_eq!(&q.transpose() * q, Matrix::<40, 40>::identity());
+        chapter2::assert_matrix_approx_eq!(&(&q.transpose() * &a.to_dense()) * q, lanczos.tridiagonal.to_dense());
+        
+        let ritz = lanczos.ritz_pairs();
+        assert!((ritz.values[0] - lambda_max).abs() < 1e-3 * lambda_max, "{}", ritz.values[0]);
+        for j in 0..40 {
+            let y = ritz.vectors.column_vector(j);
+            let residual = &a * &y - ritz.values[j] * &y;
+            assert!((residual.norm() - ritz.residual_norms[j]).abs() < 1e-8);
+        }
+        
+        // the orthogonality lost without the reorthogonalization after the extreme Ritz values converge
+        let lanczos = chapter2::lanczos::<400, 150>(&a, false).unwrap();
+        let q = &lanczos.basis;
+        let loss = (&(&q.transpose() * q) - &Matrix::<150, 150>::identity()).frobenius_norm();
+        assert!(loss > 1e-2, "{loss}");
+        
+        // breaking down in the 2-dimensional invariant subspace
+        let diagonal = chapter2::SparseMatrix::<3, 3>::from(&Matrix::<3, 3>::from([
+            [1.0, 0.0, 0.0],
+            [0.0, 2.0, 0.0],
+            [0.0, 0.0, 2.0],
+        ]));
+        assert!(chapter2::lanczos::<3, 2>(&diagonal, false).is_some());
+        assert!(chapter2::lanczos::<3, 3>(&diagonal, false).is_none());
+    }
+    
+    #[test]
+    fn test_arnoldi() {
+        let dense = Matrix::<30, 30>::random_uniform(-1.0, 1.0);
+        let a = chapter2::SparseMatrix::from(&dense);
+        let v = Vector::<30>::random_uniform(-1.0, 1.0);
+        
+        let arnoldi = chapter2::arnoldi(&a, &v, 10);
+        assert_eq!((arnoldi.steps(), arnoldi.basis.len()), (10, 11));
+        let q = Matrix::<30, 11>::from_fn(|i, j| arnoldi.basis[j][i]);
+        chapter2::assert_matrix_approx_eq!(&q.transpose() * &q, Matrix::<11, 11>::identity());
+        let h = Matrix::<11, 10>::from_fn(|i, j| arnoldi.hessenberg[(i, j)]);
+        assert!((0..11).all(|i| (0..10).all(|j| i <= j + 1 || h[(i, j)] == 0.0)));
+        let q_m = Matrix::<30, 10>::from_fn(|i, j| q[(i, j)]);
+        chapter2::assert_matrix_approx_eq!(&dense * &q_m, &q * &h);
+        
+        // the Ritz values of the full Krylov subspace are all the eigenvalues
+        let arnoldi = chapter2::arnoldi(&a, &v, 30);
+        let ritz_values = arnoldi.ritz_values();
+        assert_eq!(ritz_values.len(), 30);
+        let eigenvalues = nalgebra::DMatrix::from_fn(30, 30, |i, j| dense[(i, j)]).complex_eigenvalues();
+        for lambda in eigenvalues.iter() {
+            let distance = ritz_values.iter().map(|theta| (theta - lambda).norm()).fold(f64::INFINITY, f64::min);
+            assert!(distance < 1e-8, "{lambda}: {distance}");
+        }
+        assert!(ritz_values.windows(2).all(|w| w[0].norm() >= w[1].norm()));
+        
+        // breaking down in the 2-dimensional invariant subspace
+        let diagonal = chapter2::SparseMatrix::<3, 3>::from(&Matrix::<3, 3>::from([
+            [1.0, 0.0, 0.0],
+            [0.0, 2.0, 0.0],
+            [0.0, 0.0, 2.0],
+        ]));
+        let arnoldi = chapter2::arnoldi(&diagonal, &Vector::<3>::from_fn(|_, _| 1.0), 3);
+        assert!(arnoldi.is_invariant());
+        assert_eq!(arnoldi.steps(), 2);
+        let mut ritz_values = arnoldi.ritz_values().iter().map(|theta| theta.re).collect::<Vec<_>>();
+        ritz_values.sort_by(f64::total_cmp);
+        chapter2::assert_matrix_approx_eq!(Vector::<2>::from(<[f64; 2]>::try_from(ritz_values).unwrap()), Vector::<2>::from([1.0, 2.0]));
+    }
+    
+    #[test]
+    fn test_bisection_eigenvalues() {
+        let t = chapter2::BandedMatrix::<20>::tridiagonal(-1.0, 2.0, -1.0);
+        let exact = (1..=20).map(|k| 2.0 - 2.0 * (k as f64 * std::f64::consts::PI / 21.0).cos()).collect::<Vec<_>>();
+        assert_eq!(chapter2::sturm_count(&t, 0.0), 0);
+        assert_eq!(chapter2::sturm_count(&t, 2.0), 10);
+        assert_eq!(chapter2::sturm_count(&t, 4.0), 20);
+        let interval = chapter2::gershgorin_interval(&t);
+        assert_eq!((interval.start, interval.end), (0.0, 4.0f64.next_up()));
+        let eigenvalues = chapter2::bisection_eigenvalues(&t, interval, f64::EPSILON);
+        chapter2::assert_matrix_approx_eq!(Vector::<20>::from(<[f64; 20]>::try_from(eigenvalues).unwrap()), Vector::<20>::from(<[f64; 20]>::try_from(exact.clone()).unwrap()));
+        
+        // only those in [1, 3)
+        let selected = chapter2::bisection_eigenvalues(&t, 1.0..3.0, f64::EPSILON);
+        let expected = exact.iter().copied().filter(|lambda| (1.0..3.0).contains(lambda)).collect::<Vec<_>>();
+        assert_eq!(selected.len(), expected.len());
+        assert!(selected.iter().zip(&expected).all(|(lambda, exact)| (lambda - exact).abs() < 1e-12));
+        
+        // the nearly equal pairs of Wilkinson's matrix separated, and a tridiagonalized A
+        for a in [chapter2::test_matrices::wilkinson::<21>(), {
+            let random = Matrix::<21, 21>::random_uniform(-1.0, 1.0);
+            &random + random.transpose()
+        }] {
+            let mut reference = nalgebra::DMatrix::from_fn(21, 21, |i, j| a[(i, j)]).symmetric_eigenvalues().iter().copied().collect::<Vec<_>>();
+            reference.sort_by(f64::total_cmp);
+            let (t, _) = chapter2::tridiagonalize(&a);
+            let eigenvalues = chapter2::bisection_eigenvalues(&t, chapter2::gershgorin_interval(&t), f64::EPSILON);
+            assert_eq!(eigenvalues.len(), 21);
+            assert!(eigenvalues.iter().zip(&reference).all(|(lambda, reference)| (lambda - reference).abs() < 1e-10), "{eigenvalues:?}");
+        }
+    }
+    
+    #[test]
+    fn test_sort_and_match_eigenpairs() {
+        let eigenvalues = Vector::<4>::from([-3.0, 1.0, 4.0, -2.0]);
+        let vectors = Matrix::<3, 4>::from_fn(|i, j| (i * 4 + j) as f64);
+        for (order, expected) in [
+            (chapter2::EigenvalueOrder::DescendingValue, [2, 1, 3, 0]),
+            (chapter2::EigenvalueOrder::AscendingValue, [0, 3, 1, 2]),
+            (chapter2::EigenvalueOrder::DescendingModulus, [2, 0, 3, 1]),
+        ] {
+            let (sorted, sorted_vectors) = chapter2::sort_eigenpairs(&eigenvalues, &vectors, order);
+            for (j, &k) in expected.iter().enumerate() {
+                assert_eq!(sorted[j], eigenvalues[k]);
+                chapter2::assert_matrix_approx_eq!(sorted_vectors.column_vector(j), vectors.column_vector(k));
+            }
+        }
+        
+        // a decomposition permuted and sign-flipped back to the reference
+        let random = Matrix::<10, 10>::random_uniform(-1.0, 1.0);
+        let a = &random + random.transpose();
+        let reference = AllEigenvaluesSolver::new_symmetric_qr().solve(&a);
+        let jacobi = AllEigenvaluesSolver::new_jacobi().solve(&a);
+        let (ascending, ascending_vectors) = chapter2::sort_eigenpairs(&jacobi.eigenvalues, &jacobi.eigenvectors, chapter2::EigenvalueOrder::AscendingValue);
+        let flipped = Matrix::<10, 10>::from_fn(|i, j| if j % 2 == 0 { -ascending_vectors[(i, j)] } else { ascending_vectors[(i, j)] });
+        let (matched, matched_vectors) = chapter2::match_eigenpairs((&ascending, &flipped), (&reference.eigenvalues, &reference.eigenvectors));
+        chapter2::assert_matrix_approx_eq!(matched, reference.eigenvalues, 1e-8);
+        chapter2::assert_matrix_approx_eq!(matched_vectors, reference.eigenvectors, 1e-6);
+        
+        // the eigenvectors of a multiple eigenvalue told apart by the alignment
+        let eigenvalues = Vector::<2>::from([1.0, 1.0]);
+        let identity = Matrix::<2, 2>::identity();
+        let swapped = Matrix::<2, 2>::from([[0.0, 1.0], [-1.0, 0.0]]);
+        let (_, matched_vectors) = chapter2::match_eigenpairs((&eigenvalues, &swapped), (&eigenvalues, &identity));
+        chapter2::assert_matrix_approx_eq!(matched_vectors, identity);
+    }
+    
+    #[test]
+    fn test_all_eigenvalues_experiment() {
+        for solver in [AllEigenvaluesSolver::<10>::new_unshifted_qr(), AllEigenvaluesSolver::new_symmetric_qr(), AllEigenvaluesSolver::new_jacobi()] {
+            let stat = solver.experiment_randomly();
+            chapter2::assert_matrix_approx_eq!(stat.reference_solution.0, Vector::<10>::from_fn(|i, _| (10 - i) as f64), 1e-8);
+            assert!(stat.residual_norm < 1e-8, "{stat:?}");
+            assert!(stat.max_eigenvalues_relative_error < 1e-8, "{stat:?}");
+            assert!(stat.max_eigenvector_angle < 1e-6, "{stat:?}");
+            assert!(stat.orthogonality_error < 1e-8, "{stat:?}");
+            chapter2::assert_matrix_approx_eq!(stat.solution.1, stat.reference_solution.1, 1e-6);
+        }
+    }
+    
+    #[test]
+    fn test_experiment_on() {
+        // the eigenvalues 2 - 2 cos(kπ / 11) (k = 1, ..., 10)
+        let a = chapter2::test_matrices::tridiagonal::<10>(-1.0, 2.0, -1.0);
+        let expected = Vector::<10>::from_fn(|i, _| 2.0 - 2.0 * ((10 - i) as f64 * std::f64::consts::PI / 11.0).cos());
+        for solver in [AllEigenvaluesSolver::<10>::new_symmetric_qr(), AllEigenvaluesSolver::new_jacobi()] {
+            let stat = solver.experiment_on(&a);
+            chapter2::assert_matrix_approx_eq!(stat.solution.0, expected, 1e-8);
+            assert!(stat.residual_norm < 1e-8, "{stat:?}");
+            assert!(stat.max_eigenvalues_relative_error < 1e-8, "{stat:?}");
+        }
+    }
+    
+    #[test]
+    fn test_polar() {
+        let a = Matrix::<20, 20>::random_uniform(-1.0, 1.0);
+        let (u, h) = chapter2::polar(&a).unwrap();
+        chapter2::assert_matrix_approx_eq!(&u.transpose() * &u, Matrix::<20, 20>::identity(), 1e-12);
+        assert!(h.is_symmetric(0.0));
+        assert!(h.is_positive_definite(1e-12));
+        chapter2::assert_matrix_approx_eq!(&u * &h, a, 1e-12);
+        // the eigenvalues of H = (A^T A)^{1/2} are the singular values of A
+        let mut singular_values = nalgebra::DMatrix::from_column_slice(20, 20, a.as_slice())
+            .singular_values()
+            .iter()
+            .copied()
+            .collect::<Vec<_>>();
+        singular_values.sort_by(|x, y| y.total_cmp(x));
+        let eigenvalues = AllEigenvaluesSolver::new_symmetric_qr().solve(&h).eigenvalues;
+        chapter2::assert_matrix_approx_eq!(eigenvalues, Vector::<20>::try_from(singular_values.as_slice()).unwrap(), 1e-10);
+        
+        // H = A for a symmetric positive definite A
+        let spd = &(&a.transpose() * &a) + &Matrix::<20, 20>::identity();
+        let (u, h) = chapter2::polar(&spd).unwrap();
+        chapter2::assert_matrix_approx_eq!(u, Matrix::<20, 20>::identity(), 1e-10);
+        chapter2::assert_matrix_approx_eq!(h, spd, 1e-10);
+        
+        let singular = Matrix::<2, 2>::from([
+            [1.0, 2.0],
+            [2.0, 4.0],
+        ]);
+        assert!(chapter2::polar(&singular).is_none());
+    }
+}
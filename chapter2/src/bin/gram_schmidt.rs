@@ -0,0 +1,79 @@
+use chapter2::{Matrix, SingularValueProfile};
+use chapter2::{qr_classical_gram_schmidt, qr_modified_gram_schmidt, qr_householder};
+
+/// loss of orthogonality ||Q^T Q - I||_F
+fn orthogonality_loss<const N: usize, const M: usize>(q: &Matrix<N, M>) -> f64 {
+    (q.transpose() * q - Matrix::<M, M>::identity()).frobenius_norm()
+}
+
+/// computes Q of the QR decomposition
+type Orthogonalization<const N: usize> = fn(&Matrix<N, N>) -> Matrix<N, N>;
+
+fn plot_100_experiments<const N: usize>(condition_number: f64) -> Result<(), Box<dyn std::error::Error>> {
+    let instances = (0..100).map(|_| Matrix::<N, N>::random_with_condition_number(condition_number, SingularValueProfile::Geometric)).collect::<Vec<_>>();
+    
+    let methods: [(&str, Orthogonalization<N>); 3] = [
+        ("cgs", |a| qr_classical_gram_schmidt(a).0),
+        ("mgs", |a| qr_modified_gram_schmidt(a).0),
+        ("householder", |a| qr_householder(a).0),
+    ];
+    for (name, q_of) in methods {
+        chapter2::Plotter {
+            y_desc: "loss of orthogonality",
+            data: instances.iter().map(|a| orthogonality_loss(&q_of(a))).collect(),
+        }.plot_into(format!("plot/gram_schmidt/n{N}-cond{condition_number:e}-{name}.svg"))?;
+    }
+    
+    Ok(())
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    std::fs::create_dir_all("plot/gram_schmidt")?;
+    plot_100_experiments::<50>(1e4)?;
+    plot_100_experiments::<50>(1e8)?;
+    plot_100_experiments::<50>(1e12)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_gram_schmidt() {
+        let a = Matrix::<5, 3>::random_uniform(-1.0, 1.0);
+        for (q, r) in [qr_classical_gram_schmidt(&a), qr_modified_gram_schmidt(&a)] {
+            chapter2::assert_matrix_approx_eq!(&q * &r, a);
+            assert!(orthogonality_loss(&q) < 1e-12);
+            assert!((0..3).all(|i| (0..i).all(|j| r[(i, j)] == 0.0)));
+        }
+    }
+    
+    #[test]
+    fn test_householder_and_givens_qr() {
+        let a = Matrix::<5, 3>::random_uniform(-1.0, 1.0);
+        let givens = chapter2::GivensQr::new(&a);
+        for (q, r) in [qr_householder(&a), (givens.q(), givens.r().clone())] {
+            chapter2::assert_matrix_approx_eq!(&q * &r, a);
+            assert!(orthogonality_loss(&q) < 1e-12);
+            assert!((0..5).all(|i| (0..i.min(3)).all(|j| r[(i, j)] == 0.0)));
+        }
+        chapter2::assert_matrix_approx_eq!(givens.apply_qt(&a), givens.r());
+        
+        // N - 1 rotations for a Hessenberg A
+        let a = Matrix::<6, 6>::random_uniform(-1.0, 1.0);
+        let h = Matrix::<6, 6>::from_fn(|i, j| if i > j + 1 { 0.0 } else { a[(i, j)] });
+        let givens = chapter2::GivensQr::new(&h);
+        assert_eq!(givens.rotations().len(), 5);
+        chapter2::assert_matrix_approx_eq!(&givens.q() * givens.r(), h);
+        assert!(orthogonality_loss(&givens.q()) < 1e-12);
+    }
+    
+    #[test]
+    fn test_modified_gram_schmidt_keeps_orthogonality() {
+        let a = chapter2::test_matrices::hilbert::<8>();
+        let cgs_loss = dbg!(orthogonality_loss(&qr_classical_gram_schmidt(&a).0));
+        let mgs_loss = dbg!(orthogonality_loss(&qr_modified_gram_schmidt(&a).0));
+        assert!(mgs_loss < 1e-3 * cgs_loss);
+    }
+}
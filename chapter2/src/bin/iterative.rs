@@ -0,0 +1,500 @@
+use chapter2::{Matrix, Vector, IterativeSolution};
+use chapter2::{richardson, jacobi, gauss_seidel, sor, pcg};
+use chapter2::{IterativeSolve, StoppingCriterion, GaussSeidel, Gmres};
+use chapter2::{Preconditioner, NoPreconditioner, DiagonalMatrix, Ssor, Ic0, Ilu0, SparseMatrix};
+
+const TOLERANCE: f64 = 1e-10;
+const MAX_ITERATIONS: usize = 10000;
+
+/// iteratively solves Ax = b up to the relative residual `TOLERANCE`
+type IterativeSolver<const N: usize> = fn(&Matrix<N, N>, &Vector<N>) -> IterativeSolution<N>;
+
+fn solvers<const N: usize>() -> [(&'static str, IterativeSolver<N>); 3] {
+    [
+        ("jacobi", |a, b| jacobi(a, b, TOLERANCE, MAX_ITERATIONS)),
+        ("gauss_seidel", |a, b| gauss_seidel(a, b, TOLERANCE, MAX_ITERATIONS)),
+        ("sor_1.5", |a, b| sor(a, b, 1.5, TOLERANCE, MAX_ITERATIONS)),
+    ]
+}
+
+/// residual norms of all the `solvers` against the iteration on the same instance
+fn plot_convergence<const N: usize>(name: &str, a: &Matrix<N, N>, b: &Vector<N>) -> Result<(), Box<dyn std::error::Error>> {
+    chapter2::ConvergencePlotter {
+        y_desc: "residual norm",
+        histories: solvers::<N>().into_iter().map(|(method, solve)| (method, solve(a, b).residual_norms)).collect(),
+    }.plot_into(format!("plot/iterative/n{N}-{name}-convergence.svg"))
+}
+
+/// iteration counts and errors of each method on 100 random instances,
+/// and the convergence curve of the slowest one among them
+fn plot_100_experiments<const N: usize>() -> Result<(), Box<dyn std::error::Error>> {
+    // (name, solver, whether for symmetric positive definite A)
+    let methods = [
+        ("richardson", chapter2::IterativeSolver::<N>::new_richardson(), true),
+        ("jacobi", chapter2::IterativeSolver::new_jacobi(), false),
+        ("gauss_seidel", chapter2::IterativeSolver::new_gauss_seidel(), false),
+        ("sor_1.5", chapter2::IterativeSolver::new_sor(1.5), false),
+        ("cg", chapter2::IterativeSolver::new_cg(), true),
+        ("gmres_20", chapter2::IterativeSolver::new_gmres(20), false),
+    ];
+    for (method, solver, spd) in methods {
+        let stats = (0..100)
+            .map(|_| if spd { solver.experiment_randomly_spd() } else { solver.experiment_randomly() })
+            .collect::<Vec<_>>();
+        
+        chapter2::Plotter {
+            y_desc: "iteration count",
+            data: stats.iter().map(|s| s.iteration_count as f64).collect(),
+        }.plot_into(format!("plot/iterative/n{N}-{method}-iteration_count.svg"))?;
+        chapter2::Plotter {
+            y_desc: "relative error",
+            data: stats.iter().map(|s| s.relative_error).collect(),
+        }.plot_into(format!("plot/iterative/n{N}-{method}-relative_error.svg"))?;
+        
+        let slowest = stats.into_iter().max_by_key(|s| s.iteration_count).unwrap();
+        chapter2::ConvergencePlotter {
+            y_desc: "residual norm",
+            histories: vec![(method, slowest.residual_history)],
+        }.plot_into(format!("plot/iterative/n{N}-{method}-slowest-convergence.svg"))?;
+    }
+    Ok(())
+}
+
+/// iteration counts of SOR(ω) for ω = 0.02, 0.04, ..., 1.98 on the model Poisson problem,
+/// against the optimal ω = 2 / (1 + sin(π h)) by ρ_J = cos(π h)
+fn plot_sor_sweep<const N: usize>() -> Result<(), Box<dyn std::error::Error>> {
+    let a = chapter2::test_matrices::poisson_2d::<N>();
+    let b = Vector::<N>::from_fn(|_, _| 1.0);
+    let h = 1.0 / ((N as f64).sqrt() + 1.0);
+    let optimal = 2.0 / (1.0 + (std::f64::consts::PI * h).sin());
+    
+    let points = (1..100)
+        .map(|i| i as f64 * 0.02)
+        .map(|omega| (omega, sor(&a, &b, omega, TOLERANCE, MAX_ITERATIONS).iteration_count as f64))
+        .collect::<Vec<_>>();
+    let (best, _) = points.iter().copied().min_by(|(_, a), (_, b)| a.total_cmp(b)).unwrap();
+    println!("N = {N}: the best ω = {best} among the sweep, the optimal ω = {optimal}");
+    
+    chapter2::CurvePlotter {
+        x_desc: "ω",
+        y_desc: "iteration count",
+        points,
+        log_x: false,
+    }.plot_into(format!("plot/iterative/n{N}-poisson_2d-sor_sweep.svg"))
+}
+
+/// CG without and with each preconditioner on the model Poisson problem
+fn preconditioners<const N: usize>(a: &Matrix<N, N>) -> [(&'static str, Box<dyn Preconditioner<N>>); 4] {
+    let sparse = SparseMatrix::from(a);
+    [
+        ("cg", Box::new(NoPreconditioner)),
+        ("pcg_jacobi", Box::new(DiagonalMatrix::diagonal_part_of(a))),
+        ("pcg_ssor", Box::new(Ssor::new(&sparse, 1.5).expect("zero on the diagonal"))),
+        ("pcg_ic0", Box::new(Ic0::new(&sparse).expect("Matrix is not positive definite"))),
+    ]
+}
+
+/// Richardson with the optimal α as the baseline against Jacobi and (P)CG on the model Poisson problem,
+/// where Jacobi coincides with it by the constant diagonal 4 = (λ_min + λ_max) / 2
+fn plot_richardson_baseline<const N: usize>() -> Result<(), Box<dyn std::error::Error>> {
+    let a = chapter2::test_matrices::poisson_2d::<N>();
+    let b = Vector::<N>::random_uniform(-1.0, 1.0);
+    let ic0 = Ic0::new(&SparseMatrix::from(&a)).expect("Matrix is not positive definite");
+    chapter2::ConvergencePlotter {
+        y_desc: "residual norm",
+        histories: vec![
+            ("richardson", richardson(&a, &b, TOLERANCE, MAX_ITERATIONS).residual_norms),
+            ("jacobi", jacobi(&a, &b, TOLERANCE, MAX_ITERATIONS).residual_norms),
+            ("cg", pcg(&a, &b, &NoPreconditioner, TOLERANCE, MAX_ITERATIONS).residual_norms),
+            ("pcg_ic0", pcg(&a, &b, &ic0, TOLERANCE, MAX_ITERATIONS).residual_norms),
+        ],
+    }.plot_into(format!("plot/iterative/n{N}-poisson_2d-richardson_baseline.svg"))
+}
+
+fn plot_pcg_convergence<const N: usize>() -> Result<(), Box<dyn std::error::Error>> {
+    let a = chapter2::test_matrices::poisson_2d::<N>();
+    // random b exciting all the eigenvectors, unlike the constant one of the symmetric grid
+    let b = Vector::<N>::random_uniform(-1.0, 1.0);
+    chapter2::ConvergencePlotter {
+        y_desc: "residual norm",
+        histories: preconditioners(&a).into_iter()
+            .map(|(name, m)| (name, pcg(&a, &b, &*m, TOLERANCE, MAX_ITERATIONS).residual_norms))
+            .collect(),
+    }.plot_into(format!("plot/iterative/n{N}-poisson_2d-pcg_convergence.svg"))
+}
+
+/// errors of Gauss-Seidel against LU under each `StoppingCriterion` of the same tolerance
+/// on the model Poisson problem with 100 random b, where the slow convergence
+/// makes the solution change stop far from the solution
+fn plot_stopping_criteria<const N: usize>() -> Result<(), Box<dyn std::error::Error>> {
+    let a = chapter2::test_matrices::poisson_2d::<N>();
+    let lu = chapter2::lu::lu_decomposition(&a).expect("Matrix is singular");
+    let instances = (0..100).map(|_| Vector::<N>::random_uniform(-1.0, 1.0)).collect::<Vec<_>>();
+    for (name, criterion) in [
+        ("absolute_residual", StoppingCriterion::AbsoluteResidual(1e-8)),
+        ("relative_residual", StoppingCriterion::RelativeResidual(1e-8)),
+        ("solution_change", StoppingCriterion::SolutionChange(1e-8)),
+    ] {
+        let errors = instances.iter().map(|b| {
+            let x = lu.solve(b);
+            let solution = GaussSeidel.solve(&a, b, &Vector::zeroed(), MAX_ITERATIONS, criterion);
+            (&solution.solution - &x).norm() / x.norm()
+        }).collect::<Vec<_>>();
+        chapter2::Plotter {
+            y_desc: "relative error against LU",
+            data: errors,
+        }.plot_into(format!("plot/iterative/n{N}-poisson_2d-{name}-error.svg"))?;
+    }
+    Ok(())
+}
+
+/// residual norms of GMRES(m) against the restart cycles for m = 5, 10, 20, 50
+/// on the nonsymmetric 1D convection-diffusion -u'' + c u' discretized centrally
+/// (Péclet number c h / 2 = 0.5)
+fn plot_gmres_restart<const N: usize>() -> Result<(), Box<dyn std::error::Error>> {
+    let a = chapter2::test_matrices::tridiagonal::<N>(-1.5, 2.0, -0.5);
+    let b = Vector::<N>::from_fn(|_, _| 1.0);
+    chapter2::ConvergencePlotter {
+        y_desc: "residual norm",
+        histories: [("gmres_5", 5), ("gmres_10", 10), ("gmres_20", 20), ("gmres_50", 50)]
+            .into_iter()
+            .map(|(method, restart)| (method, chapter2::gmres(&a, &b, restart, TOLERANCE, MAX_ITERATIONS).residual_norms))
+            .collect(),
+    }.plot_into(format!("plot/iterative/n{N}-convection_diffusion-gmres_restart.svg"))
+}
+
+/// nonsymmetric 2D convection-diffusion -Δu + c u_x on the K x K grid (N = K^2) discretized centrally
+/// (Péclet number c h / 2 = 0.5), i.e. `poisson_2d` with the west and east neighbors -1.5 and -0.5
+fn convection_diffusion_2d<const N: usize>() -> Matrix<N, N> {
+    let a = chapter2::test_matrices::poisson_2d::<N>();
+    Matrix::from_fn(|i, j| match a[(i, j)] {
+        a_ij if a_ij != 0.0 && j + 1 == i => a_ij - 0.5,
+        a_ij if a_ij != 0.0 && j == i + 1 => a_ij + 0.5,
+        a_ij => a_ij,
+    })
+}
+
+/// residual norms of GMRES(10) without and with ILU(0) against the restart cycles
+/// on `convection_diffusion_2d`
+fn plot_gmres_ilu0<const N: usize>() -> Result<(), Box<dyn std::error::Error>> {
+    let a = convection_diffusion_2d::<N>();
+    let b = Vector::<N>::from_fn(|_, _| 1.0);
+    let criterion = StoppingCriterion::RelativeResidual(TOLERANCE);
+    let ilu0 = Ilu0::new(&SparseMatrix::from(&a)).expect("zero pivot in ILU(0)");
+    let plain = Gmres { restart: 10, preconditioner: NoPreconditioner }.solve(&a, &b, &Vector::zeroed(), MAX_ITERATIONS, criterion);
+    let preconditioned = Gmres { restart: 10, preconditioner: ilu0 }.solve(&a, &b, &Vector::zeroed(), MAX_ITERATIONS, criterion);
+    println!(
+        "N = {N}: GMRES(10) took {} restart cycles, {} with ILU(0)",
+        plain.iteration_count, preconditioned.iteration_count,
+    );
+    
+    chapter2::ConvergencePlotter {
+        y_desc: "residual norm",
+        histories: vec![
+            ("gmres_10", plain.residual_norms),
+            ("gmres_10_ilu0", preconditioned.residual_norms),
+        ],
+    }.plot_into(format!("plot/iterative/n{N}-convection_diffusion_2d-gmres_ilu0.svg"))
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    std::fs::create_dir_all("plot/iterative")?;
+    plot_convergence(
+        "diagonally_dominant",
+        &Matrix::<100, 100>::random_diagonally_dominant(),
+        &Vector::<100>::random_uniform(-1.0, 1.0),
+    )?;
+    plot_convergence(
+        "poisson",
+        &chapter2::test_matrices::tridiagonal::<20>(-1.0, 2.0, -1.0),
+        &Vector::<20>::from_fn(|_, _| 1.0),
+    )?;
+    plot_100_experiments::<100>()?;
+    plot_richardson_baseline::<100>()?;
+    plot_pcg_convergence::<100>()?;
+    plot_pcg_convergence::<400>()?;
+    plot_sor_sweep::<100>()?;
+    plot_sor_sweep::<400>()?;
+    plot_stopping_criteria::<100>()?;
+    plot_gmres_restart::<100>()?;
+    plot_gmres_ilu0::<100>()?;
+    plot_gmres_ilu0::<400>()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_jacobi_and_gauss_seidel() {
+        let a = Matrix::<10, 10>::random_diagonally_dominant();
+        let b = Vector::<10>::random_uniform(-1.0, 1.0);
+        let x = chapter2::lu::lu_decomposition(&a).unwrap().solve(&b);
+        for (_, solve) in solvers::<10>() {
+            let solution = solve(&a, &b);
+            assert!(solution.converged);
+            assert_eq!(solution.residual_norms.len(), solution.iteration_count + 1);
+            chapter2::assert_matrix_approx_eq!(solution.solution, x, 1e-8);
+        }
+    }
+    
+    #[test]
+    fn test_gauss_seidel_converges_twice_as_fast_on_poisson() {
+        let a = chapter2::test_matrices::tridiagonal::<10>(-1.0, 2.0, -1.0);
+        let b = Vector::<10>::from_fn(|_, _| 1.0);
+        let jacobi_count = jacobi(&a, &b, TOLERANCE, MAX_ITERATIONS).iteration_count;
+        let gauss_seidel_count = gauss_seidel(&a, &b, TOLERANCE, MAX_ITERATIONS).iteration_count;
+        // ρ_GS = ρ_J^2 for the consistently ordered tridiagonal A
+        let ratio = jacobi_count as f64 / gauss_seidel_count as f64;
+        assert!((ratio - 2.0).abs() < 0.2, "{jacobi_count} / {gauss_seidel_count}");
+    }
+    
+    #[test]
+    fn test_sor_optimal_relaxation() {
+        let a = chapter2::test_matrices::poisson_2d::<64>();
+        let b = Vector::<64>::from_fn(|_, _| 1.0);
+        let optimal = 2.0 / (1.0 + (std::f64::consts::PI / 9.0).sin());
+        let count = |omega| sor(&a, &b, omega, TOLERANCE, MAX_ITERATIONS).iteration_count;
+        assert!(count(optimal) < count(1.0) / 3);
+        assert!(count(optimal) <= count(optimal - 0.1).min(count(optimal + 0.1)));
+        // not converging for ω >= 2
+        assert!(!sor(&a, &b, 2.0, TOLERANCE, 1000).converged);
+    }
+    
+    #[test]
+    fn test_pcg() {
+        let a = Matrix::<20, 20>::random_spd();
+        let b = Vector::<20>::random_uniform(-1.0, 1.0);
+        let x = chapter2::lu::lu_decomposition(&a).unwrap().solve(&b);
+        for (_, m) in preconditioners(&a) {
+            let solution = pcg(&a, &b, &*m, TOLERANCE, MAX_ITERATIONS);
+            assert!(solution.converged);
+            chapter2::assert_matrix_approx_eq!(solution.solution, x, 1e-8);
+        }
+        
+        let a = chapter2::test_matrices::poisson_2d::<64>();
+        let b = Vector::<64>::random_uniform(-1.0, 1.0);
+        let [none, _, ssor, ic0] = preconditioners(&a)
+            .map(|(_, m)| pcg(&a, &b, &*m, TOLERANCE, MAX_ITERATIONS).iteration_count);
+        assert!(none <= 64);
+        assert!(ssor < none && ic0 < none, "{none}, {ssor}, {ic0}");
+    }
+    
+    #[test]
+    fn test_experiment_stat_records_residual_history() {
+        let stat = chapter2::IterativeSolver::<10>::new_gauss_seidel().experiment_randomly();
+        assert!(stat.converged);
+        assert_eq!(stat.residual_history.len(), stat.iteration_count + 1);
+        assert_eq!(*stat.residual_history.last().unwrap(), stat.residual_norm);
+        assert!(stat.relative_error < 1e-8);
+        
+        let stat = chapter2::IterativeSolver::<10>::new_cg().experiment_randomly_spd();
+        assert!(stat.converged && stat.relative_error < 1e-8);
+        
+        let stat = chapter2::IterativeSolver::<10>::new_jacobi()
+            .with_max_iterations(3)
+            .with_criterion(StoppingCriterion::AbsoluteResidual(0.0))
+            .experiment_randomly();
+        assert!(!stat.converged);
+        assert_eq!(stat.residual_history.len(), 4);
+    }
+    
+    #[test]
+    fn test_richardson() {
+        // λ_k = 2 - 2 cos(kπ / 11), so that λ_min + λ_max = 4
+        let a = chapter2::test_matrices::tridiagonal::<10>(-1.0, 2.0, -1.0);
+        let alpha = chapter2::Richardson::optimal_alpha(&a);
+        assert!((alpha - 0.5).abs() < 1e-6, "{alpha}");
+        
+        let a = Matrix::<20, 20>::random_spd();
+        let b = Vector::<20>::random_uniform(-1.0, 1.0);
+        let x = chapter2::lu::lu_decomposition(&a).unwrap().solve(&b);
+        let solution = richardson(&a, &b, TOLERANCE, 100 * MAX_ITERATIONS);
+        assert!(solution.converged);
+        chapter2::assert_matrix_approx_eq!(solution.solution, x, 1e-6);
+        // too large α diverges
+        let alpha = 2.5 * chapter2::Richardson::optimal_alpha(&a);
+        let diverging = chapter2::Richardson { alpha: Some(alpha) }
+            .solve(&a, &b, &Vector::zeroed(), 100, StoppingCriterion::RelativeResidual(TOLERANCE));
+        assert!(diverging.residual_norms[100] > diverging.residual_norms[0]);
+        
+        // Jacobi is Richardson with α = 1 / 4 = 2 / (λ_min + λ_max) on the model Poisson problem
+        let a = chapter2::test_matrices::poisson_2d::<64>();
+        let b = Vector::<64>::random_uniform(-1.0, 1.0);
+        let richardson_count = richardson(&a, &b, TOLERANCE, MAX_ITERATIONS).iteration_count;
+        let jacobi_count = jacobi(&a, &b, TOLERANCE, MAX_ITERATIONS).iteration_count;
+        assert!(richardson_count.abs_diff(jacobi_count) <= 1, "{richardson_count}, {jacobi_count}");
+        assert!(pcg(&a, &b, &NoPreconditioner, TOLERANCE, MAX_ITERATIONS).iteration_count < richardson_count / 5);
+    }
+    
+    #[test]
+    fn test_stopping_criteria() {
+        let a = chapter2::test_matrices::poisson_2d::<64>();
+        let b = Vector::<64>::random_uniform(-1.0, 1.0);
+        let x = chapter2::lu::lu_decomposition(&a).unwrap().solve(&b);
+        let solve = |x0: &Vector<64>, criterion| GaussSeidel.solve(&a, &b, x0, MAX_ITERATIONS, criterion);
+        
+        let relative = solve(&Vector::zeroed(), StoppingCriterion::RelativeResidual(1e-8));
+        assert!(relative.converged);
+        assert!(*relative.residual_norms.last().unwrap() <= 1e-8 * b.norm());
+        let absolute = solve(&Vector::zeroed(), StoppingCriterion::AbsoluteResidual(1e-8));
+        assert!(*absolute.residual_norms.last().unwrap() <= 1e-8);
+        // the error is about ρ / (1 - ρ) times the last change when slowly converging
+        let change = solve(&Vector::zeroed(), StoppingCriterion::SolutionChange(1e-8));
+        assert!(change.converged);
+        assert!((&change.solution - &x).norm() > 1e-8 * x.norm());
+        
+        // already converged at the exact solution
+        assert_eq!(solve(&x, StoppingCriterion::RelativeResidual(1e-8)).iteration_count, 0);
+        // continuing from the last iterate of `relative` instead of 0
+        let restarted = solve(&relative.solution, StoppingCriterion::RelativeResidual(1e-12));
+        assert!(restarted.converged);
+        assert_eq!(restarted.residual_norms[0], *relative.residual_norms.last().unwrap());
+        
+        let cg = chapter2::Pcg { preconditioner: NoPreconditioner }
+            .solve(&a, &b, &relative.solution, MAX_ITERATIONS, StoppingCriterion::RelativeResidual(TOLERANCE));
+        assert!(cg.converged);
+        chapter2::assert_matrix_approx_eq!(cg.solution, x, 1e-8);
+    }
+    
+    #[test]
+    fn test_gmres() {
+        let a = Matrix::<30, 30>::random_diagonally_dominant();
+        let b = Vector::<30>::random_uniform(-1.0, 1.0);
+        let x = chapter2::lu::lu_decomposition(&a).unwrap().solve(&b);
+        let solution = chapter2::gmres(&a, &b, 10, TOLERANCE, MAX_ITERATIONS);
+        assert!(solution.converged);
+        chapter2::assert_matrix_approx_eq!(solution.solution, x, 1e-8);
+        
+        // no restart: exact in one cycle of N Arnoldi steps up to rounding
+        let solution = chapter2::gmres(&a, &b, 30, 1e-12, MAX_ITERATIONS);
+        assert!(solution.iteration_count <= 2, "{}", solution.iteration_count);
+        
+        // nonsymmetric and not diagonally dominant
+        let a = chapter2::test_matrices::tridiagonal::<50>(-1.5, 2.0, -0.5);
+        let b = Vector::<50>::from_fn(|_, _| 1.0);
+        let x = chapter2::lu::lu_decomposition(&a).unwrap().solve(&b);
+        let solution = chapter2::gmres(&a, &b, 20, TOLERANCE, MAX_ITERATIONS);
+        assert!(solution.converged);
+        assert!(solution.residual_norms.windows(2).all(|w| w[1] <= w[0] * (1.0 + 1e-12)));
+        chapter2::assert_matrix_approx_eq!(solution.solution, x, 1e-6);
+    }
+    
+    #[test]
+    fn test_sparse_matrix() {
+        // duplicated positions are summed up, even into a stored zero
+        let a = SparseMatrix::<3, 4>::from_triplets([(2, 1, 1.0), (0, 3, 2.0), (2, 1, 0.5), (0, 0, -1.0), (1, 2, 3.0), (0, 3, -2.0)]);
+        assert_eq!(a.nnz(), 4);
+        assert_eq!(a.triplets().collect::<Vec<_>>(), [(0, 0, -1.0), (0, 3, 0.0), (1, 2, 3.0), (2, 1, 1.5)]);
+        chapter2::assert_matrix_approx_eq!(
+            a.to_dense(),
+            Matrix::from([
+                [-1.0, 0.0, 0.0, 0.0],
+                [0.0, 0.0, 3.0, 0.0],
+                [0.0, 1.5, 0.0, 0.0],
+            ]),
+            0.0,
+        );
+        
+        let dense = Matrix::<20, 30>::random_uniform(-1.0, 1.0).map(|x| if x.abs() < 0.7 { 0.0 } else { x });
+        let sparse = SparseMatrix::from(&dense);
+        assert_eq!(sparse.nnz(), dense.entries().filter(|&(_, _, x)| x != 0.0).count());
+        chapter2::assert_matrix_approx_eq!(sparse.to_dense(), dense, 0.0);
+        
+        let x = Vector::<30>::random_uniform(-1.0, 1.0);
+        chapter2::assert_matrix_approx_eq!(&sparse * &x, &dense * &x, 1e-14);
+        let mut y = Vector::<20>::random_uniform(-1.0, 1.0);
+        let expected = &(&dense * &x) * 2.0 + &y * -0.5;
+        sparse.spmv(2.0, &x, -0.5, &mut y);
+        chapter2::assert_matrix_approx_eq!(y, expected, 1e-14);
+        // `y` is not read when `beta` is 0
+        let mut y = Vector::<20>::filled_with(f64::NAN);
+        sparse.spmv(1.0, &x, 0.0, &mut y);
+        chapter2::assert_matrix_approx_eq!(y, &dense * &x, 1e-14);
+    }
+    
+    #[test]
+    fn test_triplet_builder() {
+        use chapter2::TripletBuilder;
+        
+        // the 1D Laplacian assembled element by element, each adding [1 -1; -1 1]
+        let mut builder = TripletBuilder::<6, 6>::new();
+        assert!(builder.is_empty());
+        for e in 0..5 {
+            builder.add(e, e, 1.0).add(e, e + 1, -1.0).add(e + 1, e, -1.0).add(e + 1, e + 1, 1.0);
+        }
+        assert_eq!(builder.len(), 20);
+        
+        let reference = Matrix::<6, 6>::from_fn(|i, j| match i.abs_diff(j) {
+            0 if i == 0 || i == 5 => 1.0,
+            0 => 2.0,
+            1 => -1.0,
+            _ => 0.0,
+        });
+        chapter2::assert_matrix_approx_eq!(builder.to_dense(), reference, 0.0);
+        let sparse = builder.to_sparse();
+        assert_eq!(sparse.nnz(), 16);
+        chapter2::assert_matrix_approx_eq!(sparse.to_dense(), reference, 0.0);
+        let x = Vector::<6>::random_uniform(-1.0, 1.0);
+        chapter2::assert_matrix_approx_eq!(&sparse * &x, &reference * &x, 1e-15);
+        
+        let mut extended = TripletBuilder::<6, 6>::default();
+        extended.extend(reference.entries().filter(|&(_, _, x)| x != 0.0));
+        assert_eq!(SparseMatrix::from(extended), sparse);
+        assert_eq!(SparseMatrix::from(builder), sparse);
+    }
+    
+    #[test]
+    fn test_ilu0() {
+        let a = convection_diffusion_2d::<36>();
+        let ilu0 = Ilu0::new(&SparseMatrix::from(&a)).unwrap();
+        let (l, u) = (ilu0.l(), ilu0.u());
+        // L U = A on the sparsity pattern of A, which L and U stay in
+        assert!(l.triplets().chain(u.triplets()).all(|(i, j, _)| a[(i, j)] != 0.0 || i == j));
+        let lu = &l.to_dense() * &u.to_dense();
+        assert!(a.entries().filter(|&(_, _, a_ij)| a_ij != 0.0).all(|(i, j, a_ij)| (lu[(i, j)] - a_ij).abs() < 1e-12));
+        // while the dropped fill-ins make M = L U differ from A
+        assert!((&lu - &a).frobenius_norm() > 1e-3);
+        
+        let r = Vector::<36>::random_uniform(-1.0, 1.0);
+        chapter2::assert_matrix_approx_eq!(&lu * &ilu0.apply(&r), r, 1e-12);
+        
+        // exact LU of a banded A
+        let a = chapter2::test_matrices::tridiagonal::<10>(-1.5, 2.0, -0.5);
+        let ilu0 = Ilu0::new(&SparseMatrix::from(&a)).unwrap();
+        chapter2::assert_matrix_approx_eq!(&ilu0.l().to_dense() * &ilu0.u().to_dense(), a, 1e-12);
+    }
+    
+    #[test]
+    fn test_preconditioned_gmres() {
+        let a = convection_diffusion_2d::<100>();
+        let b = Vector::<100>::from_fn(|_, _| 1.0);
+        let x = chapter2::lu::lu_decomposition(&a).unwrap().solve(&b);
+        let criterion = StoppingCriterion::RelativeResidual(TOLERANCE);
+        
+        let plain = Gmres { restart: 10, preconditioner: NoPreconditioner }.solve(&a, &b, &Vector::zeroed(), MAX_ITERATIONS, criterion);
+        let ilu0 = Ilu0::new(&SparseMatrix::from(&a)).unwrap();
+        let preconditioned = Gmres { restart: 10, preconditioner: ilu0 }.solve(&a, &b, &Vector::zeroed(), MAX_ITERATIONS, criterion);
+        assert!(plain.converged && preconditioned.converged);
+        assert!(preconditioned.iteration_count < plain.iteration_count, "{} vs {}", preconditioned.iteration_count, plain.iteration_count);
+        // the true residual is minimized, never increasing
+        assert!(preconditioned.residual_norms.windows(2).all(|w| w[1] <= w[0] * (1.0 + 1e-12)));
+        chapter2::assert_matrix_approx_eq!(preconditioned.solution, x, 1e-8);
+    }
+    
+    #[test]
+    fn test_ic0() {
+        let a = chapter2::test_matrices::poisson_2d::<36>();
+        let ic0 = Ic0::new(&SparseMatrix::from(&a)).unwrap();
+        let l = ic0.l().to_dense();
+        // L L^T = A on the lower sparsity pattern of A, which L stays in
+        assert!(ic0.l().triplets().all(|(i, j, _)| j <= i && a[(i, j)] != 0.0));
+        let llt = &l * &l.transpose();
+        assert!(a.entries().filter(|&(i, j, a_ij)| j <= i && a_ij != 0.0).all(|(i, j, a_ij)| (llt[(i, j)] - a_ij).abs() < 1e-12));
+        assert!((&llt - &a).frobenius_norm() > 1e-3);
+        
+        let r = Vector::<36>::random_uniform(-1.0, 1.0);
+        chapter2::assert_matrix_approx_eq!(&llt * &ic0.apply(&r), r, 1e-12);
+    }
+}
@@ -0,0 +1,159 @@
+use chapter2::{Matrix, Vector, LeastSquaresSolver};
+use chapter2::{solve_least_squares_normal, solve_least_squares_qr};
+
+fn plot_100_experiments<const N: usize, const M: usize>(name: &str, solver: &LeastSquaresSolver<N, M>) -> Result<(), Box<dyn std::error::Error>> {
+    let stats = (0..100).map(|_| solver.experiment_randomly()).collect::<Vec<_>>();
+    
+    chapter2::Plotter {
+        y_desc: "relative error",
+        data: stats.iter().map(|stat| stat.relative_error).collect(),
+    }.plot_into(format!("plot/least_squares/n{N}-m{M}-{name}-relative_error.svg"))?;
+    chapter2::Plotter {
+        y_desc: "residual norm / minimum",
+        data: stats.iter().map(|stat| stat.residual_norm / stat.reference_residual_norm).collect(),
+    }.plot_into(format!("plot/least_squares/n{N}-m{M}-{name}-residual_ratio.svg"))?;
+    chapter2::Plotter {
+        y_desc: "time elapsed (sec.)",
+        data: stats.iter().map(|stat| stat.elapsed.as_secs_f64()).collect(),
+    }.plot_into(format!("plot/least_squares/n{N}-m{M}-{name}-time_elapsed.svg"))?;
+    
+    Ok(())
+}
+
+/// finds x minimizing ||b - Ax||_2
+type LeastSquares<const N: usize, const M: usize> = fn(&Matrix<N, M>, &Vector<N>) -> Vector<M>;
+
+/// polynomial fitting of degree M - 1 at 100 equispaced nodes in [0, 1]
+/// by the ill-conditioned Vandermonde matrix, with the data b = V c of
+/// random coefficients c so that the relative error of recovering c is measurable
+fn plot_100_vandermonde_fittings<const M: usize>() -> Result<(), Box<dyn std::error::Error>> {
+    let xs = Vector::<100>::from_fn(|i, _| i as f64 / 99.0);
+    let v: Matrix<100, M> = chapter2::test_matrices::vandermonde(&xs);
+    let coefficients = (0..100).map(|_| Vector::<M>::random_uniform(-1.0, 1.0)).collect::<Vec<_>>();
+    
+    let methods: [(&str, LeastSquares<100, M>); 2] = [
+        ("normal", |a, b| solve_least_squares_normal(a, b).expect("Matrix is rank deficient")),
+        ("qr", |a, b| solve_least_squares_qr(a, b).expect("Matrix is rank deficient")),
+    ];
+    for (name, solve) in methods {
+        chapter2::Plotter {
+            y_desc: "relative error of coefficients",
+            data: coefficients.iter().map(|c| (&solve(&v, &(&v * c)) - c).norm() / c.norm()).collect(),
+        }.plot_into(format!("plot/least_squares/vandermonde-m{M}-{name}-relative_error.svg"))?;
+    }
+    
+    Ok(())
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    std::fs::create_dir_all("plot/least_squares")?;
+    plot_100_experiments("normal", &LeastSquaresSolver::<100, 10>::new_normal_equations())?;
+    plot_100_experiments("normal", &LeastSquaresSolver::<400, 50>::new_normal_equations())?;
+    plot_100_experiments("qr", &LeastSquaresSolver::<100, 10>::new_qr())?;
+    plot_100_experiments("qr", &LeastSquaresSolver::<400, 50>::new_qr())?;
+    plot_100_experiments("minimum_norm", &LeastSquaresSolver::<10, 100>::new_minimum_norm())?;
+    plot_100_experiments("minimum_norm", &LeastSquaresSolver::<50, 400>::new_minimum_norm())?;
+    plot_100_vandermonde_fittings::<6>()?;
+    plot_100_vandermonde_fittings::<9>()?;
+    plot_100_vandermonde_fittings::<12>()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_normal_equations() {
+        // fitting a line to (0, 1), (1, 2), (2, 2)
+        let a = Matrix::<3, 2>::from([
+            [1.0, 0.0],
+            [1.0, 1.0],
+            [1.0, 2.0],
+        ]);
+        let b = Vector::<3>::from([[1.0], [2.0], [2.0]]);
+        let x = solve_least_squares_normal(&a, &b).unwrap();
+        chapter2::assert_matrix_approx_eq!(x, Vector::<2>::from([[7.0 / 6.0], [0.5]]));
+        
+        let stat = LeastSquaresSolver::<30, 5>::new_normal_equations().experiment_randomly();
+        assert!(stat.relative_error < 1e-10);
+        assert!(stat.residual_norm <= stat.reference_residual_norm * (1.0 + 1e-10));
+    }
+    
+    #[test]
+    fn test_normal_equations_on_rank_deficient() {
+        let a = Matrix::<3, 2>::from([
+            [1.0, 2.0],
+            [2.0, 4.0],
+            [3.0, 6.0],
+        ]);
+        assert!(solve_least_squares_normal(&a, &Vector::<3>::random_uniform(-1.0, 1.0)).is_err());
+    }
+    
+    #[test]
+    fn test_qr_least_squares() {
+        let a = Matrix::<3, 2>::from([
+            [1.0, 0.0],
+            [1.0, 1.0],
+            [1.0, 2.0],
+        ]);
+        let b = Vector::<3>::from([[1.0], [2.0], [2.0]]);
+        chapter2::assert_matrix_approx_eq!(solve_least_squares_qr(&a, &b).unwrap(), Vector::<2>::from([[7.0 / 6.0], [0.5]]));
+        
+        let stat = LeastSquaresSolver::<30, 5>::new_qr().experiment_randomly();
+        assert!(stat.relative_error < 1e-10);
+    }
+    
+    #[test]
+    fn test_qr_outperforms_normal_equations_on_vandermonde() {
+        let xs = Vector::<100>::from_fn(|i, _| i as f64 / 99.0);
+        let v: Matrix<100, 10> = chapter2::test_matrices::vandermonde(&xs);
+        let c = Vector::<10>::random_uniform(-1.0, 1.0);
+        let b = &v * &c;
+        let normal_error = (&solve_least_squares_normal(&v, &b).unwrap() - &c).norm();
+        let qr_error = (&solve_least_squares_qr(&v, &b).unwrap() - &c).norm();
+        assert!(qr_error < 1e-2 * normal_error, "{qr_error} vs {normal_error}");
+    }
+    
+    #[test]
+    fn test_minimum_norm() {
+        // x_0 + x_1 = 2 is solved by (1, 1) of the minimum norm
+        let a = Matrix::<1, 2>::from([[1.0, 1.0]]);
+        let b = Vector::<1>::from([[2.0]]);
+        chapter2::assert_matrix_approx_eq!(chapter2::solve_minimum_norm(&a, &b).unwrap(), Vector::<2>::from([[1.0], [1.0]]));
+        
+        let a = Matrix::<3, 6>::random_uniform(-1.0, 1.0);
+        let b = Vector::<3>::random_uniform(-1.0, 1.0);
+        let x = chapter2::solve_least_squares(&a, &b).unwrap();
+        chapter2::assert_matrix_approx_eq!(&a * &x, b);
+        // x is in the row space of A, orthogonal to its null space spanned by the last 3 columns of Q of A^T
+        let q = chapter2::HouseholderQr::new(&a.transpose()).q();
+        assert!((3..6).all(|j| q.column_vector(j).dot(&x).abs() < 1e-12));
+        
+        let stat = LeastSquaresSolver::<5, 20>::new_minimum_norm().experiment_randomly();
+        assert!(stat.relative_error < 1e-10);
+        assert!(stat.residual_norm < 1e-12);
+    }
+    
+    #[test]
+    fn test_pinv() {
+        let a = Matrix::<4, 3>::random_uniform(-1.0, 1.0);
+        let pinv = a.pinv(1e-12);
+        chapter2::assert_matrix_approx_eq!(&(&a * &pinv) * &a, a);
+        chapter2::assert_matrix_approx_eq!(&pinv * &a, Matrix::<3, 3>::identity());
+        
+        // rank 1, where the normal equations break down
+        let a = Matrix::<3, 2>::from([
+            [1.0, 2.0],
+            [2.0, 4.0],
+            [3.0, 6.0],
+        ]);
+        let b = Vector::<3>::from([[1.0], [2.0], [3.0]]);
+        // the least squares solutions x_0 + 2 x_1 = 1, of the minimum norm (1, 2) / 5
+        let x = &a.pinv(1e-12) * &b;
+        chapter2::assert_matrix_approx_eq!(x, Vector::<2>::from([[0.2], [0.4]]));
+        
+        let stat = LeastSquaresSolver::<30, 5>::new_pinv().experiment_randomly();
+        assert!(stat.relative_error < 1e-10);
+    }
+}
@@ -0,0 +1,98 @@
+use chapter2::{Matrix, Vector, EquationSolver};
+use chapter2::lu::{lu_decomposition, solve_mixed_precision};
+
+const MAX_REFINEMENTS: usize = 10;
+
+fn plot_100_experiments<const N: usize>(name: &str, solver: EquationSolver<N>) -> Result<(), Box<dyn std::error::Error>> {
+    let stats = (0..100).map(|_| solver.experiment_randomly()).collect::<Vec<_>>();
+    
+    chapter2::Plotter {
+        y_desc: "relative error",
+        data: stats.iter().map(|stat| stat.relative_error).collect(),
+    }.plot_into(format!("plot/mixed_precision/n{N}-{name}-relative_error.svg"))?;
+    chapter2::Plotter {
+        y_desc: "time elapsed (sec.)",
+        data: stats.iter().map(|stat| stat.elapsed.as_secs_f64()).collect(),
+    }.plot_into(format!("plot/mixed_precision/n{N}-{name}-time_elapsed.svg"))?;
+    // only with the refinement, as the plot is logarithmic
+    if stats.iter().all(|stat| stat.refinement_count > 0) {
+        chapter2::Plotter {
+            y_desc: "refinement count",
+            data: stats.iter().map(|stat| stat.refinement_count as f64).collect(),
+        }.plot_into(format!("plot/mixed_precision/n{N}-{name}-refinement_count.svg"))?;
+    }
+    
+    Ok(())
+}
+
+/// elapsed time of the `f64` LU solve over that of the mixed-precision solve on the same 100 random instances
+fn plot_100_speedups<const N: usize>() -> Result<(), Box<dyn std::error::Error>> {
+    let elapsed = |f: &dyn Fn()| {
+        let start = std::time::Instant::now();
+        f();
+        start.elapsed().as_secs_f64()
+    };
+    
+    chapter2::Plotter {
+        y_desc: "speedup over f64 LU",
+        data: (0..100).map(|_| {
+            let (a, b) = (Matrix::<N, N>::random_uniform(-1.0, 1.0), Vector::<N>::random_uniform(-1.0, 1.0));
+            let double = elapsed(&|| { lu_decomposition(&a).expect("Matrix is singular").solve(&b); });
+            let mixed = elapsed(&|| { solve_mixed_precision(&a, &b, MAX_REFINEMENTS).expect("Matrix is singular"); });
+            double / mixed
+        }).collect(),
+    }.plot_into(format!("plot/mixed_precision/n{N}-speedup.svg"))?;
+    
+    Ok(())
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    std::fs::create_dir_all("plot/mixed_precision")?;
+    plot_100_experiments("f64", EquationSolver::<200>::new_lu())?;
+    plot_100_experiments("mixed", EquationSolver::<200>::new_mixed_precision(MAX_REFINEMENTS))?;
+    plot_100_experiments("f64", EquationSolver::<400>::new_lu())?;
+    plot_100_experiments("mixed", EquationSolver::<400>::new_mixed_precision(MAX_REFINEMENTS))?;
+    plot_100_speedups::<200>()?;
+    plot_100_speedups::<400>()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_mixed_precision_recovers_f64_accuracy() {
+        let a = Matrix::<30, 30>::random_uniform(-1.0, 1.0);
+        let b = Vector::<30>::random_uniform(-1.0, 1.0);
+        let double = lu_decomposition(&a).unwrap().solve(&b);
+        
+        let (unrefined, _) = solve_mixed_precision(&a, &b, 0).unwrap();
+        let (mixed, count) = solve_mixed_precision(&a, &b, MAX_REFINEMENTS).unwrap();
+        assert!(count >= 1);
+        assert!((&mixed - &double).norm() < 1e-3 * (&unrefined - &double).norm());
+        
+        let stat = EquationSolver::<30>::new_mixed_precision(MAX_REFINEMENTS).experiment_randomly();
+        assert!(stat.relative_error < 1e-10, "{stat:?}");
+    }
+    
+    #[test]
+    fn test_double_double_arithmetic() {
+        use chapter2::{DoubleDouble, Float, Scalar};
+        
+        // 2^{-60} is lost by `f64` but kept by the lower part
+        let (one, tiny) = (DoubleDouble::ONE, DoubleDouble::from_f64(2f64.powi(-60)));
+        assert_eq!((1.0 + 2f64.powi(-60)) - 1.0, 0.0);
+        assert_ne!((one + tiny) - one, DoubleDouble::ZERO);
+        assert_eq!(((one + tiny) - one).to_f64(), 2f64.powi(-60));
+        
+        let x = one / DoubleDouble::from_f64(3.0);
+        assert_ne!(x.lo(), 0.0);
+        for y in [7.0, -1e-5, 3f64.sqrt(), 1e10] {
+            let y = DoubleDouble::from_f64(y);
+            let tolerance = DoubleDouble::from_f64(4.0) * DoubleDouble::MACHINE_EPSILON * x;
+            assert!(((x * y) / y - x).abs() <= tolerance, "{y}");
+            assert!(((x / y) * y - x).abs() <= tolerance, "{y}");
+        }
+    }
+}
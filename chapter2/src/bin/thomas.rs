@@ -0,0 +1,98 @@
+use chapter2::{Vector, BandedMatrix};
+use chapter2::lu::{lu_decomposition, band_lu};
+use std::f64::consts::PI;
+
+/// Discretization of the two-point boundary value problem
+/// 
+/// ```text
+/// -u'' + q u = f  on (0, 1),  u(0) = u(1) = 0
+/// ```
+/// 
+/// by the central difference on the N interior points x_i = (i + 1) h, h = 1 / (N + 1):
+/// 
+/// ```text
+/// (-u_{i-1} + 2 u_i - u_{i+1}) / h^2 + q u_i = f(x_i)
+/// ```
+/// 
+/// with f manufactured for the exact solution u = sin(pi x), returning `(A, b, u)`
+fn two_point_bvp<const N: usize>(q: f64) -> (BandedMatrix<N>, Vector<N>, Vector<N>) {
+    let h = 1.0 / (N + 1) as f64;
+    let x = |i: usize| (i + 1) as f64 * h;
+    let a = BandedMatrix::<N>::tridiagonal(-1.0 / (h * h), 2.0 / (h * h) + q, -1.0 / (h * h));
+    let b = Vector::<N>::from_fn(|i, _| (PI * PI + q) * (PI * x(i)).sin());
+    let u = Vector::<N>::from_fn(|i, _| (PI * x(i)).sin());
+    (a, b, u)
+}
+
+/// max-norm error against the exact solution
+fn max_error<const N: usize>(x: &Vector<N>, u: &Vector<N>) -> f64 {
+    (0..N).map(|i| (x[i] - u[i]).abs()).fold(0.0, f64::max)
+}
+
+/// solves the discretized BVP
+type BvpSolver<const N: usize> = fn(&BandedMatrix<N>, &Vector<N>) -> Vector<N>;
+
+fn plot_100_experiments<const N: usize>() -> Result<(), Box<dyn std::error::Error>> {
+    let instances = (0..100).map(|_| two_point_bvp::<N>(rand::random::<f64>() * 100.0)).collect::<Vec<_>>();
+    
+    let methods: [(&str, BvpSolver<N>); 3] = [
+        ("thomas", |a, b| a.solve_tridiagonal(b).expect("Matrix is singular")),
+        ("band_lu", |a, b| band_lu(a).expect("Matrix is singular").solve(b)),
+        ("dense_lu", |a, b| lu_decomposition(&a.to_dense()).expect("Matrix is singular").solve(b)),
+    ];
+    for (name, solve) in methods {
+        let (elapsed, errors): (Vec<_>, Vec<_>) = instances.iter().map(|(a, b, u)| {
+            let start = std::time::Instant::now();
+            let x = solve(a, b);
+            (start.elapsed().as_secs_f64(), max_error(&x, u))
+        }).unzip();
+        
+        chapter2::Plotter {
+            y_desc: "time elapsed (sec.)",
+            data: elapsed,
+        }.plot_into(format!("plot/thomas/n{N}-{name}-time_elapsed.svg"))?;
+        chapter2::Plotter {
+            y_desc: "max error against sin(pi x)",
+            data: errors,
+        }.plot_into(format!("plot/thomas/n{N}-{name}-error.svg"))?;
+    }
+    
+    Ok(())
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    std::fs::create_dir_all("plot/thomas")?;
+    plot_100_experiments::<100>()?;
+    plot_100_experiments::<400>()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_thomas() {
+        let (a, b, _) = two_point_bvp::<10>(1.0);
+        let x = a.solve_tridiagonal(&b).unwrap();
+        chapter2::assert_matrix_approx_eq!(&a.to_dense() * &x, b);
+        
+        let lower = Vector::<10>::from_fn(|i, _| if i > 0 { a.get(i, i - 1) } else { 0.0 });
+        let diagonal = Vector::<10>::from_fn(|i, _| a.get(i, i));
+        let upper = Vector::<10>::from_fn(|i, _| if i < 9 { a.get(i, i + 1) } else { 0.0 });
+        chapter2::assert_matrix_approx_eq!(chapter2::thomas(&lower, &diagonal, &upper, &b).unwrap(), x);
+        
+        let singular = BandedMatrix::<3>::from_fn(1, 1, |i, j| if i == j { 0.0 } else { 1.0 });
+        assert!(singular.solve_tridiagonal(&Vector::<3>::from_fn(|_, _| 1.0)).is_none());
+    }
+    
+    #[test]
+    fn test_discretization_error_is_second_order() {
+        let (a, b, u) = two_point_bvp::<19>(0.0);
+        let coarse = max_error(&a.solve_tridiagonal(&b).unwrap(), &u);
+        let (a, b, u) = two_point_bvp::<39>(0.0);
+        let fine = max_error(&a.solve_tridiagonal(&b).unwrap(), &u);
+        // h halves, so the error should be quartered
+        assert!((coarse / fine - 4.0).abs() < 0.1, "{coarse} / {fine}");
+    }
+}
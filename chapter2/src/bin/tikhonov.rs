@@ -0,0 +1,73 @@
+use chapter2::{Matrix, Vector, solve_tikhonov};
+
+/// Hilbert system H x = b with the exact solution x = (1, ..., 1)
+/// and the data b perturbed by the relative noise of `noise`
+fn noisy_hilbert_system<const N: usize>(noise: f64) -> (Matrix<N, N>, Vector<N>, Vector<N>) {
+    let a = chapter2::test_matrices::hilbert::<N>();
+    let x = Vector::<N>::from_fn(|_, _| 1.0);
+    let b = &a * &x;
+    let b = &b + &(noise * b.norm() / (N as f64).sqrt() * &Vector::<N>::random_uniform(-1.0, 1.0));
+    (a, b, x)
+}
+
+/// lambda = 10^{-16}, ..., 10^0 on 100 logarithmically equispaced points
+fn lambdas() -> impl Iterator<Item = f64> {
+    (0..100).map(|i| 10f64.powf(-16.0 + 16.0 * i as f64 / 99.0))
+}
+
+fn plot_l_curve<const N: usize>(noise: f64) -> Result<(), Box<dyn std::error::Error>> {
+    let (a, b, x) = noisy_hilbert_system::<N>(noise);
+    let solutions = lambdas()
+        .map(|lambda| (lambda, solve_tikhonov(&a, &b, lambda).expect("Matrix is not positive definite")))
+        .collect::<Vec<_>>();
+    
+    chapter2::CurvePlotter {
+        x_desc: "residual norm ||b - Ax||",
+        y_desc: "solution norm ||x||",
+        points: solutions.iter().map(|(_, x_lambda)| ((&b - &a * x_lambda).norm(), x_lambda.norm())).collect(),
+        log_x: true,
+    }.plot_into(format!("plot/tikhonov/n{N}-noise{noise:e}-l_curve.svg"))?;
+    
+    chapter2::CurvePlotter {
+        x_desc: "lambda",
+        y_desc: "relative error",
+        points: solutions.iter().map(|(lambda, x_lambda)| (*lambda, (x_lambda - &x).norm() / x.norm())).collect(),
+        log_x: true,
+    }.plot_into(format!("plot/tikhonov/n{N}-noise{noise:e}-relative_error.svg"))?;
+    
+    Ok(())
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    std::fs::create_dir_all("plot/tikhonov")?;
+    plot_l_curve::<12>(1e-8)?;
+    plot_l_curve::<12>(1e-4)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_tikhonov() {
+        let a = Matrix::<4, 3>::random_uniform(-1.0, 1.0);
+        let b = Vector::<4>::random_uniform(-1.0, 1.0);
+        // tiny lambda agrees with the plain least squares
+        chapter2::assert_matrix_approx_eq!(
+            solve_tikhonov(&a, &b, 1e-14).unwrap(),
+            chapter2::solve_least_squares_qr(&a, &b).unwrap(),
+            1e-8
+        );
+        // the solution norm shrinks as lambda grows
+        let norms = [1e-2, 1e0, 1e2].map(|lambda| solve_tikhonov(&a, &b, lambda).unwrap().norm());
+        assert!(norms[0] > norms[1] && norms[1] > norms[2]);
+    }
+    
+    #[test]
+    fn test_tikhonov_regularizes_noisy_hilbert() {
+        let (a, b, x) = noisy_hilbert_system::<12>(1e-6);
+        let error = |lambda| (&solve_tikhonov(&a, &b, lambda).unwrap() - &x).norm();
+        assert!(lambdas().any(|lambda| error(lambda) < 1e-2 * error(1e-16)));
+    }
+}
@@ -0,0 +1,49 @@
+use crate::{Float, Matrix, LowerTriangular, count_flops};
+
+/// The Cholesky decomposition broke down at the `column`-th pivot,
+/// which was not positive (or was NaN)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NotPositiveDefinite {
+    pub column: usize,
+}
+
+impl std::fmt::Display for NotPositiveDefinite {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Matrix is not positive definite (non-positive pivot at column {})", self.column)
+    }
+}
+
+impl std::error::Error for NotPositiveDefinite {}
+
+/// Cholesky decomposition A = L L^T of a symmetric positive definite A:
+/// 
+/// ```text
+/// l_{jj} = sqrt(a_{jj} - sum_{k=0}^{j-1} l_{jk}^2)
+/// l_{ij} = (a_{ij} - sum_{k=0}^{j-1} l_{ik} l_{jk}) / l_{jj}  for i > j
+/// ```
+/// 
+/// Only the lower triangular part of A is read.
+pub fn cholesky<const N: usize, T: Float>(a: &Matrix<N, N, T>) -> Result<LowerTriangular<N, T>, NotPositiveDefinite> {
+    cholesky_with_tolerance(a, T::ZERO)
+}
+
+/// `cholesky` failing unless every pivot exceeds `tolerance`
+pub(crate) fn cholesky_with_tolerance<const N: usize, T: Float>(
+    a: &Matrix<N, N, T>,
+    tolerance: T,
+) -> Result<LowerTriangular<N, T>, NotPositiveDefinite> {
+    let mut l = Matrix::<N, N, T>::zeroed();
+    for j in 0..N {
+        let pivot = a[(j, j)] - (0..j).map(|k| l[(j, k)] * l[(j, k)]).sum::<T>();
+        if pivot.is_nan() || pivot <= tolerance {
+            return Err(NotPositiveDefinite { column: j });
+        }
+        l[(j, j)] = pivot.sqrt();
+        count_flops(2 * j + 2);
+        for i in (j + 1)..N {
+            l[(i, j)] = (a[(i, j)] - (0..j).map(|k| l[(i, k)] * l[(j, k)]).sum::<T>()) / l[(j, j)];
+            count_flops(2 * j + 2);
+        }
+    }
+    Ok(LowerTriangular::from_lower_part(l))
+}
@@ -0,0 +1,86 @@
+use crate::{Scalar, Matrix, Vector};
+
+/// N x N diagonal matrix of `T` (`f64` by default) storing only
+/// the N diagonal entries, so that products and inversion are O(N)
+/// per row or column instead of dense O(N^2) or O(N^3)
+#[derive(Clone)]
+pub struct DiagonalMatrix<const N: usize, T = f64> {
+    diagonal: Vector<N, T>,
+}
+
+impl<const N: usize, T: Scalar> DiagonalMatrix<N, T> {
+    pub fn identity() -> Self {
+        Self { diagonal: Vector::<N, T>::filled_with(T::ONE) }
+    }
+    
+    /// diagonal part D of `A`, e.g. the Jacobi preconditioner
+    pub fn diagonal_part_of(a: &Matrix<N, N, T>) -> Self {
+        Self { diagonal: a.diagonal() }
+    }
+    
+    pub fn diagonal(&self) -> &Vector<N, T> {
+        &self.diagonal
+    }
+    
+    pub fn to_dense(&self) -> Matrix<N, N, T> {
+        Matrix::<N, N, T>::from_diagonal(&self.diagonal)
+    }
+    
+    /// `None` if some diagonal entry is zero
+    pub fn inverse(&self) -> Option<Self> {
+        (0..N).all(|i| self.diagonal[i] != T::ZERO)
+            .then(|| Self { diagonal: self.diagonal.map(|d| T::ONE / d) })
+    }
+    
+    /// x = D^{-1} b, or `None` if D is singular
+    pub fn solve(&self, b: &Vector<N, T>) -> Option<Vector<N, T>> {
+        (0..N).all(|i| self.diagonal[i] != T::ZERO)
+            .then(|| Vector::<N, T>::from_fn(|i, _| b[i] / self.diagonal[i]))
+    }
+}
+
+impl<const N: usize, T: Scalar> From<Vector<N, T>> for DiagonalMatrix<N, T> {
+    fn from(diagonal: Vector<N, T>) -> Self {
+        Self { diagonal }
+    }
+}
+
+impl<const N: usize, T: Scalar> std::fmt::Debug for DiagonalMatrix<N, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("DiagonalMatrix").field(&self.diagonal).finish()
+    }
+}
+
+impl<const N: usize, T: Scalar> std::ops::Index<usize> for DiagonalMatrix<N, T> {
+    type Output = T;
+    fn index(&self, i: usize) -> &Self::Output {
+        &self.diagonal[i]
+    }
+}
+impl<const N: usize, T: Scalar> std::ops::IndexMut<usize> for DiagonalMatrix<N, T> {
+    fn index_mut(&mut self, i: usize) -> &mut Self::Output {
+        &mut self.diagonal[i]
+    }
+}
+
+/// D A: scales the i-th row of A by d_i, including D x for `Vector`s
+impl<const N: usize, const M: usize, T: Scalar> std::ops::Mul<&Matrix<N, M, T>> for &DiagonalMatrix<N, T> {
+    type Output = Matrix<N, M, T>;
+    fn mul(self, rhs: &Matrix<N, M, T>) -> Self::Output {
+        Matrix::<N, M, T>::from_fn(|i, j| self.diagonal[i] * rhs[(i, j)])
+    }
+}
+/// A D: scales the j-th column of A by d_j
+impl<const N: usize, const M: usize, T: Scalar> std::ops::Mul<&DiagonalMatrix<M, T>> for &Matrix<N, M, T> {
+    type Output = Matrix<N, M, T>;
+    fn mul(self, rhs: &DiagonalMatrix<M, T>) -> Self::Output {
+        Matrix::<N, M, T>::from_fn(|i, j| self[(i, j)] * rhs.diagonal[j])
+    }
+}
+
+impl<const N: usize, T: Scalar> std::ops::Mul for &DiagonalMatrix<N, T> {
+    type Output = DiagonalMatrix<N, T>;
+    fn mul(self, rhs: Self) -> Self::Output {
+        DiagonalMatrix { diagonal: Vector::<N, T>::from_fn(|i, _| self.diagonal[i] * rhs.diagonal[i]) }
+    }
+}
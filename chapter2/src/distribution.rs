@@ -0,0 +1,151 @@
+//! Distributions of the random instances for `experiment_randomly_from` of the solvers
+
+use crate::{Matrix, Vector, DiagonalMatrix};
+
+/// random N x N matrices to experiment with
+pub trait MatrixDistribution<const N: usize> {
+    fn sample(&self) -> Matrix<N, N>;
+}
+
+/// any generator `|| -> Matrix`, e.g. `|| Matrix::random_with_spectrum(&eigenvalues)`
+impl<const N: usize, F: Fn() -> Matrix<N, N>> MatrixDistribution<N> for F {
+    fn sample(&self) -> Matrix<N, N> {
+        self()
+    }
+}
+
+/// entries uniform on [lo, hi] by `Matrix::random_uniform`, on [-1, 1] by default
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Uniform {
+    pub lo: f64,
+    pub hi: f64,
+}
+
+impl Default for Uniform {
+    fn default() -> Self {
+        Self { lo: -1.0, hi: 1.0 }
+    }
+}
+
+impl<const N: usize> MatrixDistribution<N> for Uniform {
+    fn sample(&self) -> Matrix<N, N> {
+        Matrix::random_uniform(self.lo, self.hi)
+    }
+}
+
+/// entries from N(mu, sigma^2) by `Matrix::random_normal`, the standard one by default
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Gaussian {
+    pub mu: f64,
+    pub sigma: f64,
+}
+
+impl Default for Gaussian {
+    fn default() -> Self {
+        Self { mu: 0.0, sigma: 1.0 }
+    }
+}
+
+impl<const N: usize> MatrixDistribution<N> for Gaussian {
+    fn sample(&self) -> Matrix<N, N> {
+        Matrix::random_normal(self.mu, self.sigma)
+    }
+}
+
+/// symmetric positive definite by `Matrix::random_spd`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Spd;
+
+impl<const N: usize> MatrixDistribution<N> for Spd {
+    fn sample(&self) -> Matrix<N, N> {
+        Matrix::random_spd()
+    }
+}
+
+/// strictly diagonally dominant by `Matrix::random_diagonally_dominant`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DiagonallyDominant;
+
+impl<const N: usize> MatrixDistribution<N> for DiagonallyDominant {
+    fn sample(&self) -> Matrix<N, N> {
+        Matrix::random_diagonally_dominant()
+    }
+}
+
+/// Haar-distributed orthogonal by `Matrix::random_orthogonal`, perfectly conditioned (κ_2 = 1)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Orthogonal;
+
+impl<const N: usize> MatrixDistribution<N> for Orthogonal {
+    fn sample(&self) -> Matrix<N, N> {
+        Matrix::random_orthogonal()
+    }
+}
+
+/// A + A^T of A from the inner distribution, symmetric to ensure the real eigenvalues
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Symmetrized<D>(pub D);
+
+impl<const N: usize, D: MatrixDistribution<N>> MatrixDistribution<N> for Symmetrized<D> {
+    fn sample(&self) -> Matrix<N, N> {
+        let a = self.0.sample();
+        &a + a.transpose()
+    }
+}
+
+/// symmetric of the given eigenvalues by `Matrix::random_with_spectrum`
+#[derive(Clone, Debug)]
+pub struct WithSpectrum<const N: usize>(pub DiagonalMatrix<N>);
+
+impl<const N: usize> MatrixDistribution<N> for WithSpectrum<N> {
+    fn sample(&self) -> Matrix<N, N> {
+        Matrix::random_with_spectrum(&self.0)
+    }
+}
+
+/// how the singular values σ_0 >= ... >= σ_{N-1} are graded from 1 down to 1 / κ
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum SingularValueProfile {
+    /// ```text
+    /// σ_i = 1 - (1 - 1 / κ) i / (N - 1)
+    /// ```
+    /// 
+    /// with most of them O(1), only the last one as small as 1 / κ
+    Linear,
+    /// ```text
+    /// σ_i = κ^{-i / (N - 1)}
+    /// ```
+    /// 
+    /// with the ratios σ_{i+1} / σ_i all the same, spreading them over all the scales
+    #[default]
+    Geometric,
+}
+
+impl SingularValueProfile {
+    pub fn singular_values<const N: usize>(self, condition_number: f64) -> DiagonalMatrix<N> {
+        DiagonalMatrix::from(Vector::<N>::from_fn(|i, _| {
+            if N == 1 {
+                return 1.0;
+            }
+            let t = i as f64 / (N - 1) as f64;
+            match self {
+                Self::Linear => 1.0 - (1.0 - condition_number.recip()) * t,
+                Self::Geometric => condition_number.powf(-t),
+            }
+        }))
+    }
+}
+
+/// `Matrix::random_with_condition_number`, the singular values graded by `profile`
+/// so that κ_2(A) = `condition_number` exactly
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct WithConditionNumber {
+    pub condition_number: f64,
+    pub profile: SingularValueProfile,
+}
+
+impl<const N: usize> MatrixDistribution<N> for WithConditionNumber {
+    fn sample(&self) -> Matrix<N, N> {
+        Matrix::random_with_condition_number(self.condition_number, self.profile)
+    }
+}
@@ -0,0 +1,172 @@
+use crate::{Float, Scalar};
+
+/// Double-double scalar `hi + lo` (|lo| <= ulp(hi) / 2), giving about
+/// 32 significant digits with `f64` hardware arithmetic, used to compute
+/// "ground truth" solutions against which the forward errors of
+/// the `f64` solvers are measured.
+/// 
+/// Only the field operations and `sqrt` are double-double accurate;
+/// `powf` and `acos` are computed in `f64`.
+#[derive(Clone, Copy, PartialEq, PartialOrd, Debug, Default)]
+pub struct DoubleDouble {
+    hi: f64,
+    lo: f64,
+}
+
+/// s + e = a + b exactly, assuming |a| >= |b|
+fn quick_two_sum(a: f64, b: f64) -> DoubleDouble {
+    let s = a + b;
+    DoubleDouble { hi: s, lo: b - (s - a) }
+}
+
+/// s + e = a + b exactly
+fn two_sum(a: f64, b: f64) -> DoubleDouble {
+    let s = a + b;
+    let bb = s - a;
+    DoubleDouble { hi: s, lo: (a - (s - bb)) + (b - bb) }
+}
+
+/// p + e = a b exactly
+fn two_prod(a: f64, b: f64) -> DoubleDouble {
+    let p = a * b;
+    DoubleDouble { hi: p, lo: a.mul_add(b, -p) }
+}
+
+impl DoubleDouble {
+    pub const fn new(hi: f64, lo: f64) -> Self {
+        Self { hi, lo }
+    }
+    
+    pub fn hi(self) -> f64 {
+        self.hi
+    }
+    pub fn lo(self) -> f64 {
+        self.lo
+    }
+}
+
+/// Rounded to `f64` (the precision can be specified as `{:.3}`)
+impl std::fmt::Display for DoubleDouble {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Display::fmt(&self.to_f64(), f)
+    }
+}
+
+impl std::iter::Sum for DoubleDouble {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, |sum, x| sum + x)
+    }
+}
+
+impl std::ops::Neg for DoubleDouble {
+    type Output = Self;
+    fn neg(self) -> Self::Output {
+        Self { hi: -self.hi, lo: -self.lo }
+    }
+}
+
+impl std::ops::Add for DoubleDouble {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output {
+        let s = two_sum(self.hi, rhs.hi);
+        let t = two_sum(self.lo, rhs.lo);
+        let s = quick_two_sum(s.hi, s.lo + t.hi);
+        quick_two_sum(s.hi, s.lo + t.lo)
+    }
+}
+impl std::ops::Sub for DoubleDouble {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + (-rhs)
+    }
+}
+impl std::ops::Mul for DoubleDouble {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self::Output {
+        let p = two_prod(self.hi, rhs.hi);
+        quick_two_sum(p.hi, p.lo + (self.hi * rhs.lo + self.lo * rhs.hi))
+    }
+}
+impl std::ops::Div for DoubleDouble {
+    type Output = Self;
+    /// long division refining the `f64` quotient twice
+    fn div(self, rhs: Self) -> Self::Output {
+        let q1 = self.hi / rhs.hi;
+        let r = self - rhs * Self::from_f64(q1);
+        let q2 = r.hi / rhs.hi;
+        let r = r - rhs * Self::from_f64(q2);
+        let q3 = r.hi / rhs.hi;
+        quick_two_sum(q1, q2) + Self::from_f64(q3)
+    }
+}
+
+macro_rules! impl_assign_ops {
+    ($($op_assign:ident::$f_assign:ident => $op:tt),*) => {$(
+        impl std::ops::$op_assign for DoubleDouble {
+            fn $f_assign(&mut self, rhs: Self) {
+                *self = *self $op rhs;
+            }
+        }
+    )*};
+}
+impl_assign_ops! {
+    AddAssign::add_assign => +,
+    SubAssign::sub_assign => -,
+    MulAssign::mul_assign => *,
+    DivAssign::div_assign => /
+}
+
+impl Scalar for DoubleDouble {
+    type Real = Self;
+    
+    const ZERO: Self = Self::new(0.0, 0.0);
+    const ONE: Self = Self::new(1.0, 0.0);
+    
+    fn from_real(x: Self) -> Self { x }
+    fn conj(self) -> Self { self }
+    fn modulus(self) -> Self { self.abs() }
+}
+
+impl Float for DoubleDouble {
+    const INFINITY: Self = Self::new(f64::INFINITY, 0.0);
+    /// 2^{-104}
+    const MACHINE_EPSILON: Self = Self::new(4.930380657631324e-32, 0.0);
+    const TOLERANCE: Self = Self::new(1e-20, 0.0);
+    
+    fn from_f64(x: f64) -> Self { Self::new(x, 0.0) }
+    fn to_f64(self) -> f64 { self.hi + self.lo }
+    
+    fn abs(self) -> Self { if self.hi < 0.0 { -self } else { self } }
+    /// one Newton step from the `f64` square root:
+    /// 
+    /// ```text
+    /// sqrt(x) ≈ q + (x - q^2) / 2q  where q = sqrt(hi)
+    /// ```
+    fn sqrt(self) -> Self {
+        if self.hi <= 0.0 {
+            return Self::from_f64(self.hi.sqrt());
+        }
+        let q = self.hi.sqrt();
+        let r = self - two_prod(q, q);
+        quick_two_sum(q, r.hi / (2.0 * q))
+    }
+    fn powi(self, n: i32) -> Self {
+        let (mut base, mut exponent, mut result) = (self, n.unsigned_abs(), Self::ONE);
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result *= base;
+            }
+            base *= base;
+            exponent >>= 1;
+        }
+        if n < 0 { Self::ONE / result } else { result }
+    }
+    fn powf(self, p: Self) -> Self { Self::from_f64(self.to_f64().powf(p.to_f64())) }
+    fn acos(self) -> Self { Self::from_f64(self.to_f64().acos()) }
+    fn max(self, other: Self) -> Self { if other > self { other } else { self } }
+    fn min(self, other: Self) -> Self { if other < self { other } else { self } }
+    fn clamp(self, min: Self, max: Self) -> Self { self.max(min).min(max) }
+    fn signum(self) -> Self { Self::from_f64(self.hi.signum()) }
+    fn is_nan(self) -> bool { self.hi.is_nan() }
+    fn is_infinite(self) -> bool { self.hi.is_infinite() }
+}
@@ -0,0 +1,431 @@
+use crate::{Float, Scalar, Matrix};
+
+/// Matrix of `T` (`f64` by default) whose size is given at runtime,
+/// mirroring the API of `Matrix<N, M, T>` so that experiments can
+/// iterate over arbitrary sizes in a loop
+#[derive(Clone, PartialEq)]
+pub struct DynMatrix<T = f64> {
+    nrows: usize,
+    ncols: usize,
+    /// column-major like `Matrix`
+    entries: Vec<T>,
+}
+
+/// `DynMatrix` with exactly one column
+pub type DynVector<T = f64> = DynMatrix<T>;
+
+impl<const N: usize, const M: usize, T: Scalar> From<&Matrix<N, M, T>> for DynMatrix<T> {
+    fn from(matrix: &Matrix<N, M, T>) -> Self {
+        Self::from_fn(N, M, |i, j| matrix[(i, j)])
+    }
+}
+impl<const N: usize, const M: usize, T: Scalar> TryFrom<&DynMatrix<T>> for Matrix<N, M, T> {
+    type Error = &'static str;
+    fn try_from(matrix: &DynMatrix<T>) -> Result<Self, Self::Error> {
+        (matrix.shape() == (N, M))
+            .then(|| Self::from_fn(|i, j| matrix[(i, j)]))
+            .ok_or("dynamic matrix dimensions do not match matrix size")
+    }
+}
+
+impl<T: Scalar> TryFrom<&[&[T]]> for DynMatrix<T> {
+    type Error = &'static str;
+    fn try_from(slice_of_rows: &[&[T]]) -> Result<Self, Self::Error> {
+        let ncols = slice_of_rows.first().map_or(0, |row| row.len());
+        slice_of_rows.iter().all(|row| row.len() == ncols)
+            .then(|| Self::from_fn(slice_of_rows.len(), ncols, |i, j| slice_of_rows[i][j]))
+            .ok_or("rows have different lengths")
+    }
+}
+
+impl<T: Scalar> From<Vec<T>> for DynVector<T> {
+    fn from(entries: Vec<T>) -> Self {
+        Self { nrows: entries.len(), ncols: 1, entries }
+    }
+}
+impl<T: Scalar> From<&[T]> for DynVector<T> {
+    fn from(slice: &[T]) -> Self {
+        Self::from(slice.to_vec())
+    }
+}
+impl<T: Scalar> FromIterator<T> for DynVector<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self::from(iter.into_iter().collect::<Vec<_>>())
+    }
+}
+
+impl<T: Scalar> DynMatrix<T> {
+    pub fn from_fn(nrows: usize, ncols: usize, mut f: impl FnMut(usize, usize) -> T) -> Self {
+        let entries = (0..ncols).flat_map(|j| (0..nrows).map(move |i| (i, j))).map(|(i, j)| f(i, j)).collect();
+        Self { nrows, ncols, entries }
+    }
+    
+    pub fn filled_with(nrows: usize, ncols: usize, value: T) -> Self {
+        Self { nrows, ncols, entries: vec![value; nrows * ncols] }
+    }
+    
+    pub fn zeroed(nrows: usize, ncols: usize) -> Self {
+        Self::filled_with(nrows, ncols, T::ZERO)
+    }
+    
+    pub fn identity(n: usize) -> Self {
+        Self::from_fn(n, n, |i, j| if i == j { T::ONE } else { T::ZERO })
+    }
+    
+    pub fn from_diagonal(diagonal: &DynVector<T>) -> Self {
+        let n = diagonal.nrows();
+        Self::from_fn(n, n, |i, j| if i == j { diagonal[i] } else { T::ZERO })
+    }
+    
+    pub fn nrows(&self) -> usize {
+        self.nrows
+    }
+    pub fn ncols(&self) -> usize {
+        self.ncols
+    }
+    /// `(nrows, ncols)`
+    pub fn shape(&self) -> (usize, usize) {
+        (self.nrows, self.ncols)
+    }
+    
+    pub fn transpose(&self) -> Self {
+        Self::from_fn(self.ncols, self.nrows, |i, j| self[(j, i)])
+    }
+    
+    /// conjugate transpose A^*, i.e. `transpose` for real matrices
+    pub fn adjoint(&self) -> Self {
+        Self::from_fn(self.ncols, self.nrows, |i, j| self[(j, i)].conj())
+    }
+    
+    /// j-th column as a slice
+    pub fn column(&self, j: usize) -> &[T] {
+        &self.entries[(j * self.nrows)..((j + 1) * self.nrows)]
+    }
+    pub fn column_mut(&mut self, j: usize) -> &mut [T] {
+        &mut self.entries[(j * self.nrows)..((j + 1) * self.nrows)]
+    }
+    
+    pub fn row(&self, i: usize) -> impl Iterator<Item = T> + '_ {
+        assert!(i < self.nrows, "row index out of bounds");
+        (0..self.ncols).map(move |j| self[(i, j)])
+    }
+    
+    /// j-th column as an owned vector
+    pub fn column_vector(&self, j: usize) -> DynVector<T> {
+        DynVector::from(self.column(j))
+    }
+    
+    pub fn swap_rows(&mut self, i: usize, k: usize) {
+        (0..self.ncols).for_each(|j| self.column_mut(j).swap(i, k));
+    }
+    
+    /// apply `f` to each entry
+    pub fn map(&self, f: impl Fn(T) -> T) -> Self {
+        Self { nrows: self.nrows, ncols: self.ncols, entries: self.entries.iter().map(|x| f(*x)).collect() }
+    }
+    
+    /// apply `f` to each pair of entries at the same position
+    pub fn zip_map(&self, other: &Self, f: impl Fn(T, T) -> T) -> Self {
+        assert!(self.shape() == other.shape(), "matrix sizes do not match");
+        Self {
+            nrows: self.nrows,
+            ncols: self.ncols,
+            entries: std::iter::zip(&self.entries, &other.entries).map(|(x, y)| f(*x, *y)).collect(),
+        }
+    }
+    
+    /// whether every entry differs from the corresponding one of `other`
+    /// by at most `tolerance` in absolute value
+    pub fn approx_eq(&self, other: &Self, tolerance: T::Real) -> bool {
+        self.first_approx_mismatch(other, tolerance).is_none()
+    }
+    
+    /// position of the first entry (in column-major order) differing from
+    /// the corresponding one of `other` by more than `tolerance`
+    #[doc(hidden)]
+    pub fn first_approx_mismatch(&self, other: &Self, tolerance: T::Real) -> Option<(usize, usize)> {
+        assert!(self.shape() == other.shape(), "matrix sizes do not match");
+        std::iter::zip(&self.entries, &other.entries)
+            .position(|(x, y)| {
+                let difference = (*x - *y).modulus();
+                difference.is_nan() || difference > tolerance
+            })
+            .map(|k| (k % self.nrows, k / self.nrows))
+    }
+    
+    /// ```text
+    /// ||A||_F = sqrt(sum_{i,j} |a_{ij}|^2)
+    /// ```
+    pub fn frobenius_norm(&self) -> T::Real {
+        self.entries.iter().map(|x| x.modulus().powi(2)).sum::<T::Real>().sqrt()
+    }
+    
+    /// maximum absolute column sum
+    pub fn norm_1(&self) -> T::Real {
+        (0..self.ncols)
+            .map(|j| self.column(j).iter().map(|x| x.modulus()).sum::<T::Real>())
+            .fold(T::Real::ZERO, T::Real::max)
+    }
+    
+    /// maximum absolute row sum
+    pub fn norm_inf(&self) -> T::Real {
+        (0..self.nrows)
+            .map(|i| self.row(i).map(|x| x.modulus()).sum::<T::Real>())
+            .fold(T::Real::ZERO, T::Real::max)
+    }
+    
+    pub fn diagonal(&self) -> DynVector<T> {
+        (0..self.nrows.min(self.ncols)).map(|i| self[(i, i)]).collect()
+    }
+    
+    pub fn trace(&self) -> T {
+        self.diagonal().iter().copied().sum()
+    }
+    
+    /// Solve Ax = b by Gaussian elimination with partial pivoting
+    /// like `Matrix::solve`.
+    /// 
+    /// Returns `None` if A is singular.
+    pub fn solve(&self, b: &DynVector<T>) -> Option<DynVector<T>> {
+        let n = self.nrows;
+        assert!(self.ncols == n, "Matrix is not square");
+        assert!(b.shape() == (n, 1), "vector length does not match matrix size");
+        
+        let (mut a, mut x) = (self.clone(), b.clone());
+        for k in 0..n {
+            let (i, _pivot) = (k..n)
+                .map(|i| (i, a[(i, k)]))
+                .filter(|(_, value)| value.modulus() > T::Real::TOLERANCE)
+                .max_by(|(_, p), (_, q)| T::Real::partial_cmp(&p.modulus(), &q.modulus()).expect("found NaN or Inf"))?;
+            if i != k {
+                a.swap_rows(i, k);
+                x.swap_rows(i, k);
+            }
+            
+            for i in (k + 1)..n {
+                let factor = a[(i, k)] / a[(k, k)];
+                for j in k..n {
+                    let update = factor * a[(k, j)];
+                    a[(i, j)] -= update;
+                }
+                let update = factor * x[k];
+                x[i] -= update;
+            }
+        }
+        for i in (0..n).rev() {
+            let sum = ((i + 1)..n).map(|j| a[(i, j)] * x[j]).sum::<T>();
+            x[i] = (x[i] - sum) / a[(i, i)];
+        }
+        Some(x)
+    }
+    
+    /// Compute A^{-1} by solving AX = I column by column.
+    /// 
+    /// Returns `None` if A is singular.
+    pub fn inverse(&self) -> Option<Self> {
+        let n = self.nrows;
+        let mut inverse = Self::zeroed(n, n);
+        for j in 0..n {
+            let e = DynVector::from_fn(n, 1, |i, _| if i == j { T::ONE } else { T::ZERO });
+            inverse.column_mut(j).copy_from_slice(self.solve(&e)?.as_ref());
+        }
+        Some(inverse)
+    }
+}
+
+impl<T: Scalar> DynVector<T> {
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.as_ref().iter()
+    }
+    
+    /// in-place update without allocation:
+    /// 
+    /// ```text
+    /// y <- alpha x + y
+    /// ```
+    pub fn axpy(&mut self, alpha: T, x: &Self) {
+        assert!(self.shape() == x.shape(), "vector lengths do not match");
+        std::iter::zip(&mut self.entries, x.iter()).for_each(|(y_i, x_i)| *y_i += alpha * *x_i);
+    }
+    
+    /// index of the entry with the largest absolute value
+    pub fn argmax_abs(&self) -> usize {
+        let (i, _max_abs) = self
+            .iter()
+            .enumerate()
+            .max_by(|(_, p), (_, q)| T::Real::partial_cmp(&p.modulus(), &q.modulus()).expect("found NaN"))
+            .expect("Vector is empty");
+        i
+    }
+    
+    pub fn norm(&self) -> T::Real {
+        self.frobenius_norm()
+    }
+    
+    pub fn normalize(&mut self) {
+        let norm = T::from_real(self.norm());
+        self.entries.iter_mut().for_each(|x| *x /= norm);
+    }
+    pub fn normalized(&self) -> Self {
+        let mut result = self.clone();
+        result.normalize();
+        result
+    }
+    
+    /// inner product conjugating `self`:
+    /// 
+    /// ```text
+    /// (x, y) = x^* y = sum_i conj(x_i) y_i
+    /// ```
+    pub fn dot(&self, rhs: &Self) -> T {
+        assert!(self.shape() == rhs.shape(), "vector lengths do not match");
+        std::iter::zip(self.iter(), rhs.iter()).map(|(x, y)| x.conj() * *y).sum()
+    }
+}
+impl<T: Scalar> AsRef<[T]> for DynVector<T> {
+    fn as_ref(&self) -> &[T] {
+        assert!(self.ncols == 1, "DynMatrix is not a vector");
+        &self.entries
+    }
+}
+
+/// random matrix constructors
+impl DynMatrix {
+    /// entries independently drawn from the uniform distribution on [lo, hi]
+    pub fn random_uniform(nrows: usize, ncols: usize, lo: f64, hi: f64) -> Self {
+        use rand::Rng;
+        crate::random::with_rng(|rng| Self::from_fn(nrows, ncols, |_, _| rng.random_range(lo..=hi)))
+    }
+}
+
+impl<T: Scalar> std::fmt::Debug for DynMatrix<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.ncols == 1 {
+            self.entries.fmt(f)
+        } else {
+            // row-major, unlike the internal storage
+            f.debug_list().entries((0..self.nrows).map(|i| self.row(i).collect::<Vec<_>>())).finish()
+        }
+    }
+}
+
+/// Row by row with aligned columns like `Matrix`
+impl<T: Scalar> std::fmt::Display for DynMatrix<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let precision = f.precision().unwrap_or(6);
+        let formatted = (0..self.nrows)
+            .map(|i| self.row(i).map(|x| format!("{x:.precision$}")).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        let width = formatted.iter().flatten().map(|s| s.chars().count()).max().unwrap_or(0);
+        for (i, row) in formatted.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "[")?;
+            for (j, x) in row.iter().enumerate() {
+                if j > 0 {
+                    write!(f, " ")?;
+                }
+                write!(f, "{x:>width$}")?;
+            }
+            write!(f, "]")?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: Scalar> std::ops::Index<(usize, usize)> for DynMatrix<T> {
+    type Output = T;
+    fn index(&self, (i, j): (usize, usize)) -> &Self::Output {
+        assert!(i < self.nrows && j < self.ncols, "index out of bounds");
+        &self.entries[j * self.nrows + i]
+    }
+}
+impl<T: Scalar> std::ops::IndexMut<(usize, usize)> for DynMatrix<T> {
+    fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut Self::Output {
+        assert!(i < self.nrows && j < self.ncols, "index out of bounds");
+        &mut self.entries[j * self.nrows + i]
+    }
+}
+
+impl<T: Scalar> std::ops::Index<usize> for DynVector<T> {
+    type Output = T;
+    fn index(&self, i: usize) -> &Self::Output {
+        &self.as_ref()[i]
+    }
+}
+impl<T: Scalar> std::ops::IndexMut<usize> for DynVector<T> {
+    fn index_mut(&mut self, i: usize) -> &mut Self::Output {
+        assert!(self.ncols == 1, "DynMatrix is not a vector");
+        &mut self.entries[i]
+    }
+}
+
+impl<T: Scalar> std::ops::Add for &DynMatrix<T> {
+    type Output = DynMatrix<T>;
+    fn add(self, rhs: Self) -> Self::Output {
+        self.zip_map(rhs, |x, y| x + y)
+    }
+}
+impl<T: Scalar> std::ops::Sub for &DynMatrix<T> {
+    type Output = DynMatrix<T>;
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.zip_map(rhs, |x, y| x - y)
+    }
+}
+impl<T: Scalar> std::ops::Neg for &DynMatrix<T> {
+    type Output = DynMatrix<T>;
+    fn neg(self) -> Self::Output {
+        self.map(|x| -x)
+    }
+}
+
+impl<T: Scalar> std::ops::AddAssign<&DynMatrix<T>> for DynMatrix<T> {
+    fn add_assign(&mut self, rhs: &DynMatrix<T>) {
+        assert!(self.shape() == rhs.shape(), "matrix sizes do not match");
+        std::iter::zip(&mut self.entries, &rhs.entries).for_each(|(x, y)| *x += *y);
+    }
+}
+impl<T: Scalar> std::ops::SubAssign<&DynMatrix<T>> for DynMatrix<T> {
+    fn sub_assign(&mut self, rhs: &DynMatrix<T>) {
+        assert!(self.shape() == rhs.shape(), "matrix sizes do not match");
+        std::iter::zip(&mut self.entries, &rhs.entries).for_each(|(x, y)| *x -= *y);
+    }
+}
+
+impl<T: Scalar> std::ops::Mul for &DynMatrix<T> {
+    type Output = DynMatrix<T>;
+    fn mul(self, rhs: Self) -> Self::Output {
+        assert!(self.ncols == rhs.nrows, "matrix sizes do not match for multiplication");
+        let mut product = DynMatrix::zeroed(self.nrows, rhs.ncols);
+        for j in 0..rhs.ncols {
+            for (k, b_kj) in rhs.column(j).iter().enumerate() {
+                std::iter::zip(product.column_mut(j), self.column(k)).for_each(|(c_ij, a_ik)| *c_ij += *a_ik * *b_kj);
+            }
+        }
+        product
+    }
+}
+
+impl<T: Scalar> std::ops::Mul<T> for &DynMatrix<T> {
+    type Output = DynMatrix<T>;
+    fn mul(self, rhs: T) -> Self::Output {
+        self.map(|x| x * rhs)
+    }
+}
+impl<T: Scalar> std::ops::MulAssign<T> for DynMatrix<T> {
+    fn mul_assign(&mut self, rhs: T) {
+        self.entries.iter_mut().for_each(|x| *x *= rhs);
+    }
+}
+impl<T: Scalar> std::ops::Div<T> for &DynMatrix<T> {
+    type Output = DynMatrix<T>;
+    fn div(self, rhs: T) -> Self::Output {
+        self.map(|x| x / rhs)
+    }
+}
+impl<T: Scalar> std::ops::DivAssign<T> for DynMatrix<T> {
+    fn div_assign(&mut self, rhs: T) {
+        self.entries.iter_mut().for_each(|x| *x /= rhs);
+    }
+}
@@ -0,0 +1,770 @@
+use crate::{Matrix, Vector, DynMatrix, SparseMatrix, BandedMatrix, Complex64, HouseholderQr, GivensRotation, tridiagonalize, qr_modified_gram_schmidt};
+use crate::{EPSILON, DominantEigenvalueSolution, AllEigenvaluesSolution, check_deadline};
+
+const MAX_ITERATIONS: usize = 10000;
+
+/// Eigenpair (λ, x) of A with λ the nearest to the shift `sigma`, as the dominant one of
+/// (A - σI)^{-1} by the power iteration on it, factorizing A - σI once:
+/// 
+/// ```text
+/// (A - σI) y_{k+1} = x_k,  x_{k+1} = y_{k+1} / ||y_{k+1}||,  λ_{k+1} = x_{k+1}^T A x_{k+1}
+/// ```
+/// 
+/// converging by the ratio |λ - σ| / |λ' - σ| with λ' the second nearest, so fast for σ
+/// close to λ like a coarse estimate by the power iteration or the Gershgorin discs.
+/// Stops when ||A x_k - λ_k x_k|| <= `tolerance` ||A||_F, or returns `None`
+/// if A - σI is singular or not converging within 10000 iterations.
+pub fn shifted_inverse_iteration<const N: usize>(
+    a: &Matrix<N, N>,
+    sigma: f64,
+    tolerance: f64,
+) -> Option<DominantEigenvalueSolution<N>> {
+    let mut shifted = a.clone();
+    (0..N).for_each(|i| shifted[(i, i)] -= sigma);
+    let lu = crate::lu::lu_decomposition(&shifted)?;
+    
+    // random, as any regular vector may be orthogonal to the target eigenvector
+    let mut x = Vector::<N>::random_uniform(-1.0, 1.0).normalized();
+    let threshold = tolerance * a.frobenius_norm();
+    for iteration_count in 1..=MAX_ITERATIONS {
+        check_deadline();
+        x = lu.solve(&x).normalized();
+        let ax = a * &x;
+        let eigenvalue = x.dot(&ax);
+        if (&ax - &(eigenvalue * &x)).norm() <= threshold {
+            return Some(DominantEigenvalueSolution {
+                eigenvalue,
+                eigenvector: x,
+                iteration_count,
+            });
+        }
+    }
+    None
+}
+
+/// Dominant eigenpair (λ, x) of A, i.e. with the largest |λ|, by the power iteration
+/// from a random x_0 with the Rayleigh quotients:
+/// 
+/// ```text
+/// x_{k+1} = A x_k / ||A x_k||,  λ_{k+1} = x_{k+1}^T A x_{k+1}
+/// ```
+/// 
+/// converging by the ratio |λ_2| / |λ_1| of the two largest eigenvalues (in modulus).
+/// Stops when ||A x_k - λ_k x_k|| <= `tolerance` ||A||_F, or returns `None`
+/// if not converging within 10000 iterations, e.g. for |λ_1| = |λ_2|.
+pub fn power_iteration<const N: usize>(
+    a: &Matrix<N, N>,
+    tolerance: f64,
+) -> Option<DominantEigenvalueSolution<N>> {
+    let mut x = Vector::<N>::random_uniform(-1.0, 1.0).normalized();
+    let threshold = tolerance * a.frobenius_norm();
+    for iteration_count in 1..=MAX_ITERATIONS {
+        check_deadline();
+        x = (a * &x).normalized();
+        let ax = a * &x;
+        let eigenvalue = x.dot(&ax);
+        if (&ax - &(eigenvalue * &x)).norm() <= threshold {
+            return Some(DominantEigenvalueSolution {
+                eigenvalue,
+                eigenvector: x,
+                iteration_count,
+            });
+        }
+    }
+    None
+}
+
+/// the dominant eigenpair by `aitken_power_iteration` with the steps of both sequences
+#[derive(Debug)]
+pub struct AitkenPowerIteration<const N: usize> {
+    /// the extrapolated eigenvalue and x_k, where `iteration_count` is the accelerated one
+    pub solution: DominantEigenvalueSolution<N>,
+    /// steps until the plain Rayleigh quotients λ_k would stop
+    pub plain_iteration_count: usize,
+}
+
+/// `power_iteration` accelerated by the Aitken Δ² extrapolation of its Rayleigh quotients:
+/// 
+/// ```text
+/// λ'_k = λ_k - (λ_k - λ_{k-1})^2 / (λ_k - 2 λ_{k-1} + λ_{k-2})
+/// ```
+/// 
+/// removing the leading error term c r^k of the linear convergence λ_k - λ ~ c r^k, so that
+/// λ'_k converges by a higher rate. Both λ_k and λ'_k are run until each of them changes by
+/// at most `tolerance` relatively, recording both the steps. Only the eigenvalue is accelerated;
+/// x_k at the accelerated stop is no more accurate than that of the plain iteration at the same step.
+/// `None` if either doesn't stop within 10000 iterations.
+pub fn aitken_power_iteration<const N: usize>(
+    a: &Matrix<N, N>,
+    tolerance: f64,
+) -> Option<AitkenPowerIteration<N>> {
+    let mut x = Vector::<N>::random_uniform(-1.0, 1.0).normalized();
+    let mut plain = Vec::<f64>::with_capacity(3);
+    let mut accelerated = None::<f64>;
+    let (mut plain_iteration_count, mut solution) = (None, None);
+    for iteration_count in 1..=MAX_ITERATIONS {
+        check_deadline();
+        x = (a * &x).normalized();
+        let lambda = x.dot(&(a * &x));
+        let previous = plain.last().copied();
+        if plain.len() == 3 {
+            plain.remove(0);
+        }
+        plain.push(lambda);
+        
+        if plain_iteration_count.is_none() && previous.is_some_and(|previous| (lambda - previous).abs() <= tolerance * lambda.abs()) {
+            plain_iteration_count = Some(iteration_count);
+        }
+        if let (None, &[l0, l1, l2]) = (&solution, &plain[..]) {
+            let second_difference = l2 - 2.0 * l1 + l0;
+            let extrapolated = if second_difference == 0.0 { l2 } else { l2 - (l2 - l1).powi(2) / second_difference };
+            if accelerated.is_some_and(|previous| (extrapolated - previous).abs() <= tolerance * extrapolated.abs()) {
+                solution = Some(DominantEigenvalueSolution {
+                    eigenvalue: extrapolated,
+                    eigenvector: x.clone(),
+                    iteration_count,
+                });
+            }
+            accelerated = Some(extrapolated);
+        }
+        
+        if plain_iteration_count.is_some() && solution.is_some() {
+            return plain_iteration_count.zip(solution).map(|(plain_iteration_count, solution)| AitkenPowerIteration {
+                solution,
+                plain_iteration_count,
+            });
+        }
+    }
+    None
+}
+
+/// ||A x_k|| of the power iteration x_{k+1} = A x_k / ||A x_k|| over `apply: x -> Ax`
+/// until its relative change gets at most `tolerance`
+fn power_iteration_norm<const N: usize>(apply: impl Fn(&Vector<N>) -> Vector<N>, tolerance: f64) -> Option<f64> {
+    let mut x = Vector::<N>::random_uniform(-1.0, 1.0).normalized();
+    let mut estimate = 0.0;
+    for _ in 0..MAX_ITERATIONS {
+        check_deadline();
+        let y = apply(&x);
+        let next = y.norm();
+        if next == 0.0 {
+            return Some(0.0);
+        }
+        x = &y / next;
+        if (next - estimate).abs() <= tolerance * next {
+            return Some(next);
+        }
+        estimate = next;
+    }
+    None
+}
+
+/// ρ(A) = max |λ_i| estimated by ||A x_k|| of the power iteration, in O(N^2) per step
+/// without any decomposition, e.g. for the convergence rate of a stationary iteration.
+/// Converges by |λ_2| / |λ_1| if the dominant eigenvalue is unique in modulus or a ±λ pair
+/// of a symmetric A, but may oscillate for a complex pair or a non-normal A (`None`
+/// if not settling within 10000 iterations). Stops when ||A x_k|| changes by at most `tolerance` relatively.
+pub fn spectral_radius_estimate<const N: usize>(a: &Matrix<N, N>, tolerance: f64) -> Option<f64> {
+    power_iteration_norm(|x| a * x, tolerance)
+}
+
+/// ||A||_2 = σ_max(A) = sqrt(λ_max(A^T A)) estimated by the power iteration on A^T A
+/// applied as A^T (A x) without forming it, in O(NM) per step instead of a full SVD,
+/// e.g. for κ_2(A) = ||A||_2 ||A^{-1}||_2. Converges by (σ_2 / σ_1)^2 from below,
+/// stopping when the estimate of λ_max(A^T A) changes by at most `tolerance` relatively.
+pub fn norm2_estimate<const N: usize, const M: usize>(a: &Matrix<N, M>, tolerance: f64) -> Option<f64> {
+    let a_t = a.transpose();
+    power_iteration_norm(|x| &a_t * &(a * x), tolerance).map(f64::sqrt)
+}
+
+/// The `k` largest eigenpairs (in modulus) of a symmetric A in descending order
+/// by `power_iteration` and the Hotelling deflation removing each found (λ_j, x_j):
+/// 
+/// ```text
+/// A_{j+1} = A_j - λ_j x_j x_j^T
+/// ```
+/// 
+/// which keeps the other eigenpairs of A_j as they are by the orthogonality of the eigenvectors.
+/// The errors of the earlier eigenpairs remain in A_{j+1}, so that the later ones get less accurate.
+/// `None` if any of the power iterations doesn't converge.
+pub fn deflated_power_iteration<const N: usize>(
+    a: &Matrix<N, N>,
+    k: usize,
+    tolerance: f64,
+) -> Option<Vec<DominantEigenvalueSolution<N>>> {
+    assert!(k <= N, "more eigenpairs than the dimension");
+    
+    let mut deflated = a.clone();
+    let mut eigenpairs = Vec::with_capacity(k);
+    for _ in 0..k {
+        let mut eigenpair = power_iteration(&deflated, tolerance)?;
+        for j in 0..N {
+            for i in 0..N {
+                deflated[(i, j)] -= eigenpair.eigenvalue * eigenpair.eigenvector[i] * eigenpair.eigenvector[j];
+            }
+        }
+        // the Rayleigh quotient of A itself, not to accumulate the errors of the deflations
+        eigenpair.eigenvalue = eigenpair.eigenvector.dot(&(a * &eigenpair.eigenvector));
+        eigenpairs.push(eigenpair);
+    }
+    Some(eigenpairs)
+}
+
+/// the dominant invariant subspace of dimension P found by `subspace_iteration`
+#[derive(Clone, Debug)]
+pub struct SubspaceIteration<const N: usize, const P: usize> {
+    /// the Ritz values in descending order
+    pub eigenvalues: Vector<P>,
+    /// the Ritz vectors as the orthonormal basis of the subspace
+    pub eigenvectors: Matrix<N, P>,
+    pub iteration_count: usize,
+    /// ||A Q_k - Q_k (Q_k^T A Q_k)||_F / ||A||_F at each re-orthonormalization
+    pub residual_norms: Vec<f64>,
+}
+
+/// The P largest eigenpairs (in modulus) of a symmetric A by the simultaneous (block power) iteration
+/// from a random orthonormal Q_0, re-orthonormalized by `qr_modified_gram_schmidt`
+/// every `orthonormalization_interval` steps:
+/// 
+/// ```text
+/// Z_k = A Q_{k-1},  Q_k = Z_k  or  Q_k R_k = Z_k
+/// ```
+/// 
+/// and finally the Rayleigh-Ritz on Q_k^T A Q_k by `symmetric_qr_algorithm`.
+/// span(Q_k) approaches the invariant subspace by |λ_{P+1}| / |λ_P| per step, however close
+/// the eigenvalues within it are, while the columns without the re-orthonormalization all tend
+/// to the dominant eigenvector (and overflow), so that the interval is to be small enough.
+/// Stops when the relative residual of the subspace is at most `tolerance`, or returns `None`
+/// if not converging within 10000 iterations.
+pub fn subspace_iteration<const N: usize, const P: usize>(
+    a: &Matrix<N, N>,
+    orthonormalization_interval: usize,
+    tolerance: f64,
+) -> Option<SubspaceIteration<N, P>> {
+    assert!(P <= N, "larger subspace than the dimension");
+    assert!(orthonormalization_interval > 0, "orthonormalization_interval must be positive");
+    
+    let scale = a.frobenius_norm();
+    let mut q = qr_modified_gram_schmidt(&Matrix::<N, P>::random_uniform(-1.0, 1.0)).0;
+    let mut aq = a * &q;
+    let mut residual_norms = vec![];
+    for iteration_count in 1..=MAX_ITERATIONS {
+        check_deadline();
+        if iteration_count % orthonormalization_interval != 0 {
+            q = aq;
+            aq = a * &q;
+            continue;
+        }
+        q = qr_modified_gram_schmidt(&aq).0;
+        aq = a * &q;
+        let h = &q.transpose() * &aq;
+        residual_norms.push((&aq - &(&q * &h)).frobenius_norm() / scale);
+        if *residual_norms.last().unwrap() <= tolerance {
+            let ritz = symmetric_qr_algorithm(&h, f64::EPSILON)?;
+            return Some(SubspaceIteration {
+                eigenvalues: ritz.eigenvalues,
+                eigenvectors: &q * &ritz.eigenvectors,
+                iteration_count,
+                residual_norms,
+            });
+        }
+    }
+    None
+}
+
+/// All the eigenvalues of A with the real ones by the (unshifted) QR algorithm
+/// accumulating the orthogonal similarity transformations:
+/// 
+/// ```text
+/// A_k = Q_k R_k,  A_{k+1} = R_k Q_k = Q_k^T A_k Q_k,  V_{k+1} = V_k Q_k
+/// ```
+/// 
+/// where A_k tends to the upper triangular Schur form of A = V T V^T, so that the eigenvalues
+/// come on the diagonal in descending order of modulus and V has the eigenvectors if A is symmetric
+/// (otherwise only the Schur vectors). The (i + 1, i) entry decays by |λ_{i+1}| / |λ_i|,
+/// slowly for close eigenvalues. Stops when the strictly lower part is at most `tolerance` ||A||_F,
+/// or returns `None` if not converging within 10000 iterations, e.g. for complex or ±λ pairs.
+pub fn qr_algorithm<const N: usize>(
+    a: &Matrix<N, N>,
+    tolerance: f64,
+) -> Option<AllEigenvaluesSolution<N>> {
+    let threshold = tolerance * a.frobenius_norm();
+    let mut a_k = a.clone();
+    let mut v = Matrix::<N, N>::identity();
+    for iteration_count in 0..=MAX_ITERATIONS {
+        check_deadline();
+        if (0..N).all(|j| (j + 1..N).all(|i| a_k[(i, j)].abs() <= threshold)) {
+            return Some(AllEigenvaluesSolution {
+                eigenvalues: a_k.diagonal(),
+                eigenvectors: v,
+                iteration_count,
+            });
+        }
+        let qr = HouseholderQr::new(&a_k);
+        let q = qr.q();
+        a_k = &qr.r() * &q;
+        v = &v * &q;
+    }
+    None
+}
+
+/// All the eigenpairs of a symmetric A by the symmetric QR algorithm on the tridiagonal T
+/// of `tridiagonalize` (A = Q T Q^T), each step taking the Wilkinson shift μ, the eigenvalue of
+/// the trailing 2 x 2 block of T nearer to its last diagonal entry, implicitly by the bulge chasing
+/// of Givens rotations G_k in the (k, k+1)-planes in O(N) (and O(N^2) for the eigenvectors):
+/// 
+/// ```text
+/// δ = (d_{n-1} - d_n) / 2,  μ = d_n - e_{n-1}^2 / (δ + sign(δ) sqrt(δ^2 + e_{n-1}^2)),
+/// T <- G_{n-1} ... G_1 T G_1^T ... G_{n-1}^T  (G_1 zeroing (d_1 - μ, e_1)),  V <- V G_1^T ... G_{n-1}^T
+/// ```
+/// 
+/// converging cubically in general. The subdiagonal entries with |e_i| <= `tolerance` (|d_i| + |d_{i+1}|)
+/// are set to zero, deflating T into the unreduced blocks iterated separately. Only the lower triangular part
+/// of A is read. Returns the eigenvalues in descending order, or `None` if not converging within 10000 steps.
+pub fn symmetric_qr_algorithm<const N: usize>(
+    a: &Matrix<N, N>,
+    tolerance: f64,
+) -> Option<AllEigenvaluesSolution<N>> {
+    let (t, mut v) = tridiagonalize(a);
+    let mut d = (0..N).map(|i| t[(i, i)]).collect::<Vec<_>>();
+    let mut e = (0..N.saturating_sub(1)).map(|i| t[(i + 1, i)]).collect::<Vec<_>>();
+    
+    let mut iteration_count = 0;
+    loop {
+        check_deadline();
+        for i in 0..e.len() {
+            if e[i].abs() <= tolerance * (d[i].abs() + d[i + 1].abs()) {
+                e[i] = 0.0;
+            }
+        }
+        // the last unreduced block d[lo..=hi]
+        let Some(hi) = (1..N).rev().find(|&i| e[i - 1] != 0.0) else {
+            break;
+        };
+        let lo = (1..hi).rev().find(|&i| e[i - 1] == 0.0).unwrap_or(0);
+        
+        if iteration_count == MAX_ITERATIONS {
+            return None;
+        }
+        iteration_count += 1;
+        
+        let delta = (d[hi - 1] - d[hi]) / 2.0;
+        let mu = d[hi] - e[hi - 1].powi(2) / (delta + delta.signum() * delta.hypot(e[hi - 1]));
+        
+        // (x, z) to be zeroed into (r, 0): (d_lo - μ, e_lo) first, and then the bulge below e_{k-1}
+        let (mut x, mut z) = (d[lo] - mu, e[lo]);
+        for k in lo..hi {
+            let g = GivensRotation::zeroing(k, k + 1, x, z);
+            let (c, s) = (g.c, g.s);
+            if k > lo {
+                e[k - 1] = c * x + s * z;
+            }
+            let (a, b, d2) = (d[k], e[k], d[k + 1]);
+            d[k] = c * c * a + 2.0 * c * s * b + s * s * d2;
+            d[k + 1] = s * s * a - 2.0 * c * s * b + c * c * d2;
+            e[k] = c * s * (d2 - a) + (c * c - s * s) * b;
+            if k + 1 < hi {
+                z = s * e[k + 1];
+                e[k + 1] *= c;
+            }
+            x = e[k];
+            g.apply_right(&mut v, 0..N);
+        }
+    }
+    
+    Some(sorted_descending(&d, &v, iteration_count))
+}
+
+/// `eigenvalues` in descending order with the corresponding columns of `eigenvectors`
+fn sorted_descending<const N: usize>(
+    eigenvalues: &[f64],
+    eigenvectors: &Matrix<N, N>,
+    iteration_count: usize,
+) -> AllEigenvaluesSolution<N> {
+    let (eigenvalues, eigenvectors) = sort_eigenpairs(&Vector::<N>::from_fn(|i, _| eigenvalues[i]), eigenvectors, EigenvalueOrder::DescendingValue);
+    AllEigenvaluesSolution {
+        eigenvalues,
+        eigenvectors,
+        iteration_count,
+    }
+}
+
+/// Order of the eigenvalues for `sort_eigenpairs`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EigenvalueOrder {
+    /// λ_1 >= λ_2 >= ..., as returned by `symmetric_qr_algorithm` and `jacobi_eigenvalue_method`
+    DescendingValue,
+    AscendingValue,
+    /// |λ_1| >= |λ_2| >= ..., as found by the power iterations
+    DescendingModulus,
+}
+
+/// `eigenvalues` sorted in `order` with the columns of `eigenvectors` permuted consistently
+/// (stably for ties)
+pub fn sort_eigenpairs<const N: usize, const K: usize>(
+    eigenvalues: &Vector<K>,
+    eigenvectors: &Matrix<N, K>,
+    order: EigenvalueOrder,
+) -> (Vector<K>, Matrix<N, K>) {
+    let mut permutation = (0..K).collect::<Vec<_>>();
+    permutation.sort_by(|&i, &j| match order {
+        EigenvalueOrder::DescendingValue => eigenvalues[j].total_cmp(&eigenvalues[i]),
+        EigenvalueOrder::AscendingValue => eigenvalues[i].total_cmp(&eigenvalues[j]),
+        EigenvalueOrder::DescendingModulus => eigenvalues[j].abs().total_cmp(&eigenvalues[i].abs()),
+    });
+    (
+        Vector::<K>::from_fn(|j, _| eigenvalues[permutation[j]]),
+        Matrix::<N, K>::from_fn(|i, j| eigenvectors[(i, permutation[j])]),
+    )
+}
+
+/// The eigenpairs (`eigenvalues`, `eigenvectors`) aligned with the `reference` ones up to
+/// the ordering and the signs: the j-th pair is the closest one to the j-th reference eigenvalue
+/// (greedily over all the pairs in ascending order of |λ_i - μ_j|, breaking the ties
+/// like a multiple eigenvalue by |x_i^T y_j|), with the eigenvector flipped into x_j^T y_j >= 0,
+/// so that the errors can be taken entrywise whatever order and signs each method returns.
+pub fn match_eigenpairs<const N: usize, const K: usize>(
+    (eigenvalues, eigenvectors): (&Vector<K>, &Matrix<N, K>),
+    (reference_eigenvalues, reference_eigenvectors): (&Vector<K>, &Matrix<N, K>),
+) -> (Vector<K>, Matrix<N, K>) {
+    let alignment = |i: usize, j: usize| (0..N).map(|l| eigenvectors[(l, i)] * reference_eigenvectors[(l, j)]).sum::<f64>();
+    let mut candidates = (0..K).flat_map(|i| (0..K).map(move |j| (i, j))).collect::<Vec<_>>();
+    candidates.sort_by(|&(i, j), &(k, l)| {
+        (eigenvalues[i] - reference_eigenvalues[j]).abs().total_cmp(&(eigenvalues[k] - reference_eigenvalues[l]).abs())
+            .then_with(|| alignment(k, l).abs().total_cmp(&alignment(i, j).abs()))
+    });
+    
+    let mut permutation = vec![None::<usize>; K];
+    let mut used = vec![false; K];
+    for (i, j) in candidates {
+        if !used[i] && permutation[j].is_none() {
+            used[i] = true;
+            permutation[j] = Some(i);
+        }
+    }
+    let permutation = permutation.into_iter().map(Option::unwrap).collect::<Vec<_>>();
+    let signs = (0..K).map(|j| if alignment(permutation[j], j) < 0.0 { -1.0 } else { 1.0 }).collect::<Vec<_>>();
+    (
+        Vector::<K>::from_fn(|j, _| eigenvalues[permutation[j]]),
+        Matrix::<N, K>::from_fn(|l, j| signs[j] * eigenvectors[(l, permutation[j])]),
+    )
+}
+
+/// All the eigenpairs of a symmetric A by the cyclic Jacobi method, sweeping the off-diagonal
+/// (p, q) row by row and zeroing each by the rotation J in the (p, q)-plane:
+/// 
+/// ```text
+/// τ = (a_qq - a_pp) / (2 a_pq),  t = sign(τ) / (|τ| + sqrt(1 + τ^2)),  c = 1 / sqrt(1 + t^2),  s = t c,
+/// A <- J^T A J,  V <- V J
+/// ```
+/// 
+/// decreasing off(A) = sqrt(sum_{i != j} a_ij^2) monotonically, and quadratically after a few sweeps.
+/// Stops after the sweep with off(A) <= `tolerance` ||A||_F, returning `iteration_count` as the sweeps
+/// and off(A) before each of them and at the end, or `None` if not converging within 10000 sweeps.
+pub fn jacobi_eigenvalue_method<const N: usize>(
+    a: &Matrix<N, N>,
+    tolerance: f64,
+) -> Option<(AllEigenvaluesSolution<N>, Vec<f64>)> {
+    let off = |a: &Matrix<N, N>| (0..N)
+        .map(|j| (0..N).filter(|&i| i != j).map(|i| a[(i, j)].powi(2)).sum::<f64>())
+        .sum::<f64>()
+        .sqrt();
+    let threshold = tolerance * a.frobenius_norm();
+    
+    let mut a = a.clone();
+    let mut v = Matrix::<N, N>::identity();
+    let mut off_diagonal_norms = vec![off(&a)];
+    while off_diagonal_norms.last().is_some_and(|&norm| norm > threshold) {
+        check_deadline();
+        if off_diagonal_norms.len() > MAX_ITERATIONS {
+            return None;
+        }
+        for p in 0..N {
+            for q in p + 1..N {
+                if a[(p, q)] == 0.0 {
+                    continue;
+                }
+                let tau = (a[(q, q)] - a[(p, p)]) / (2.0 * a[(p, q)]);
+                let t = tau.signum() / (tau.abs() + tau.hypot(1.0));
+                let c = 1.0 / t.hypot(1.0);
+                // J as a `GivensRotation` is its transpose
+                let j = GivensRotation { i: p, k: q, c, s: t * c }.transpose();
+                j.apply_left(&mut a, 0..N);
+                j.apply_right(&mut a, 0..N);
+                j.apply_right(&mut v, 0..N);
+                a[(p, q)] = 0.0;
+                a[(q, p)] = 0.0;
+            }
+        }
+        off_diagonal_norms.push(off(&a));
+    }
+    
+    let sweep_count = off_diagonal_norms.len() - 1;
+    Some((sorted_descending(a.diagonal().as_ref(), &v, sweep_count), off_diagonal_norms))
+}
+
+/// K steps of the Lanczos process on a symmetric A from a random q_1,
+/// building the orthonormal basis Q_K of the Krylov subspace span{q_1, A q_1, ..., A^{K-1} q_1}:
+/// 
+/// ```text
+/// A Q_K = Q_K T_K + β_K q_{K+1} e_K^T,  T_K = Q_K^T A Q_K  tridiagonal
+/// ```
+#[derive(Clone, Debug)]
+pub struct Lanczos<const N: usize, const K: usize> {
+    /// T_K with the diagonal α_1, ..., α_K and the subdiagonal β_1, ..., β_{K-1}
+    pub tridiagonal: BandedMatrix<K>,
+    /// Q_K as the columns q_1, ..., q_K
+    pub basis: Matrix<N, K>,
+    /// β_K
+    pub residual_norm: f64,
+}
+
+/// Ritz pairs (θ_j, y_j) of `Lanczos`, approximating the eigenpairs of A
+#[derive(Clone, Debug)]
+pub struct RitzPairs<const N: usize, const K: usize> {
+    /// the eigenvalues θ_j of T_K in descending order
+    pub values: Vector<K>,
+    /// the Ritz vectors y_j = Q_K s_j as the columns, with T_K s_j = θ_j s_j
+    pub vectors: Matrix<N, K>,
+    /// ||A y_j - θ_j y_j|| = β_K |(s_j)_K|, bounding the distance from θ_j to the nearest eigenvalue of A
+    pub residual_norms: Vector<K>,
+}
+
+/// The Lanczos process on a symmetric sparse A in O(nnz(A)) per step:
+/// 
+/// ```text
+/// w = A q_j - β_{j-1} q_{j-1},  α_j = (q_j, w),  w <- w - α_j q_j,  β_j = ||w||,  q_{j+1} = w / β_j
+/// ```
+/// 
+/// whose Ritz values converge to the extreme eigenvalues first. The three-term recurrence loses
+/// the orthogonality of Q_K as they converge, producing spurious copies of them, unless
+/// `reorthogonalize` with all the previous q_i at O(N j) more per step.
+/// `None` at the breakdown β_j <= `EPSILON` ||A q_j|| before K steps, where span{q_1, ..., q_j} is invariant under A.
+pub fn lanczos<const N: usize, const K: usize>(
+    a: &SparseMatrix<N, N>,
+    reorthogonalize: bool,
+) -> Option<Lanczos<N, K>> {
+    let mut basis = Matrix::<N, K>::zeroed();
+    let mut alphas = Vec::with_capacity(K);
+    let mut betas = Vec::with_capacity(K);
+    
+    let mut q = Vector::<N>::random_uniform(-1.0, 1.0).normalized();
+    let mut q_prev = Vector::<N>::zeroed();
+    let mut beta = 0.0;
+    for j in 0..K {
+        basis.set_column(j, &q);
+        let mut w = a * &q;
+        let scale = w.norm();
+        w.axpy(-beta, &q_prev);
+        let alpha = q.dot(&w);
+        w.axpy(-alpha, &q);
+        if reorthogonalize {
+            for i in 0..=j {
+                let q_i = basis.column_vector(i);
+                w.axpy(-q_i.dot(&w), &q_i);
+            }
+        }
+        alphas.push(alpha);
+        beta = w.norm();
+        if j + 1 < K {
+            if beta <= EPSILON * scale {
+                return None;
+            }
+            betas.push(beta);
+        }
+        q_prev = std::mem::replace(&mut q, w.normalized());
+    }
+    
+    Some(Lanczos {
+        tridiagonal: BandedMatrix::<K>::from_fn(1, 1, |i, j| if i == j { alphas[i] } else { betas[i.min(j)] }),
+        basis,
+        residual_norm: beta,
+    })
+}
+
+impl<const N: usize, const K: usize> Lanczos<N, K> {
+    /// by `symmetric_qr_algorithm` on T_K
+    pub fn ritz_pairs(&self) -> RitzPairs<N, K> {
+        let AllEigenvaluesSolution { eigenvalues, eigenvectors, .. } =
+            symmetric_qr_algorithm(&self.tridiagonal.to_dense(), f64::EPSILON).expect("QR algorithm doesn't converge");
+        RitzPairs {
+            vectors: &self.basis * &eigenvectors,
+            residual_norms: Vector::<K>::from_fn(|j, _| self.residual_norm * eigenvectors[(K - 1, j)].abs()),
+            values: eigenvalues,
+        }
+    }
+}
+
+/// up to m steps of the Arnoldi process on a general A from q_1, building the orthonormal basis
+/// Q_m of the Krylov subspace span{q_1, A q_1, ..., A^{m-1} q_1}:
+/// 
+/// ```text
+/// A Q_m = Q_{m+1} H̄_m,  H̄_m = Q_{m+1}^T A Q_m  (m + 1) x m upper Hessenberg
+/// ```
+/// 
+/// as the common ground of GMRES and the Ritz values of nonsymmetric A
+#[derive(Clone, Debug)]
+pub struct Arnoldi<const N: usize> {
+    /// q_1, ..., q_{m+1}, without q_{m+1} if `is_invariant`
+    pub basis: Vec<Vector<N>>,
+    /// H̄_m, whose last row is zero if `is_invariant`
+    pub hessenberg: DynMatrix,
+}
+
+/// The Arnoldi process on a sparse A from q_1 = `v` / ||v|| by the modified Gram-Schmidt:
+/// 
+/// ```text
+/// w = A q_j,  h_{ij} = (q_i, w),  w <- w - h_{ij} q_i  (i = 1, ..., j),  h_{j+1,j} = ||w||,  q_{j+1} = w / h_{j+1,j}
+/// ```
+/// 
+/// in O(nnz(A) + N j) per step, stopping early at the breakdown h_{j+1,j} <= `EPSILON` ||A q_j||,
+/// where span{q_1, ..., q_j} is invariant under A. Reduces to `lanczos` for a symmetric A
+/// in exact arithmetic, with H̄_m tridiagonal.
+pub fn arnoldi<const N: usize>(
+    a: &SparseMatrix<N, N>,
+    v: &Vector<N>,
+    m: usize,
+) -> Arnoldi<N> {
+    arnoldi_by(|q| a * q, v, m)
+}
+
+/// `arnoldi` on the operator `apply: q -> A q`, e.g. A M^{-1} of right-preconditioned GMRES
+pub(crate) fn arnoldi_by<const N: usize>(
+    apply: impl Fn(&Vector<N>) -> Vector<N>,
+    v: &Vector<N>,
+    m: usize,
+) -> Arnoldi<N> {
+    let mut basis = vec![v.normalized()];
+    let mut columns = Vec::<Vec<f64>>::with_capacity(m);
+    for j in 0..m {
+        let mut w = apply(&basis[j]);
+        let scale = w.norm();
+        let mut h = (0..=j).map(|i| {
+            let h_ij = basis[i].dot(&w);
+            w.axpy(-h_ij, &basis[i]);
+            h_ij
+        }).collect::<Vec<_>>();
+        let h_next = w.norm();
+        if h_next <= EPSILON * scale {
+            h.push(0.0);
+            columns.push(h);
+            break;
+        }
+        h.push(h_next);
+        columns.push(h);
+        basis.push(&w / h_next);
+    }
+    
+    let steps = columns.len();
+    Arnoldi {
+        basis,
+        hessenberg: DynMatrix::from_fn(steps + 1, steps, |i, j| columns[j].get(i).copied().unwrap_or(0.0)),
+    }
+}
+
+impl<const N: usize> Arnoldi<N> {
+    /// m, fewer than the requested if `is_invariant`
+    pub fn steps(&self) -> usize {
+        self.hessenberg.ncols()
+    }
+    
+    /// whether broken down in an invariant subspace, where the Ritz values are exact eigenvalues
+    pub fn is_invariant(&self) -> bool {
+        self.basis.len() == self.steps()
+    }
+    
+    /// eigenvalues of the square H_m (H̄_m without the last row), possibly complex,
+    /// in descending order of modulus by nalgebra's Schur decomposition
+    pub fn ritz_values(&self) -> Vec<Complex64> {
+        let m = self.steps();
+        let mut ritz_values = nalgebra::DMatrix::from_fn(m, m, |i, j| self.hessenberg[(i, j)])
+            .complex_eigenvalues()
+            .iter()
+            .copied()
+            .collect::<Vec<_>>();
+        ritz_values.sort_by(|x, y| y.norm().total_cmp(&x.norm()));
+        ritz_values
+    }
+}
+
+/// the diagonal and the subdiagonal of a symmetric tridiagonal T
+fn tridiagonal_entries<const N: usize>(t: &BandedMatrix<N>) -> (Vec<f64>, Vec<f64>) {
+    assert!(t.lower() <= 1 && t.upper() <= 1, "Matrix is not tridiagonal");
+    let d = (0..N).map(|i| t[(i, i)]).collect();
+    let e = (0..N.saturating_sub(1)).map(|i| if t.lower() == 1 { t[(i + 1, i)] } else { 0.0 }).collect();
+    (d, e)
+}
+
+fn sturm_count_entries(d: &[f64], e: &[f64], x: f64) -> usize {
+    let mut count = 0;
+    let mut q = 1.0;
+    for i in 0..d.len() {
+        q = d[i] - x - if i == 0 { 0.0 } else { e[i - 1].powi(2) / q };
+        if q == 0.0 {
+            // a zero pivot perturbed as if x were shifted by O(ε)
+            q = -f64::EPSILON * (d[i].abs() + x.abs()).max(f64::MIN_POSITIVE);
+        }
+        if q < 0.0 {
+            count += 1;
+        }
+    }
+    count
+}
+
+/// the number of the eigenvalues of a symmetric tridiagonal T less than x,
+/// as that of the negative pivots q_i of the LDL^T factorization of T - xI by Sylvester's law of inertia:
+/// 
+/// ```text
+/// q_1 = d_1 - x,  q_i = d_i - x - e_{i-1}^2 / q_{i-1}
+/// ```
+/// 
+/// (equivalently the sign changes of the Sturm sequence of the leading principal minors) in O(N)
+pub fn sturm_count<const N: usize>(t: &BandedMatrix<N>, x: f64) -> usize {
+    let (d, e) = tridiagonal_entries(t);
+    sturm_count_entries(&d, &e, x)
+}
+
+/// [min(d_i - |e_{i-1}| - |e_i|), max(d_i + |e_{i-1}| + |e_i|)] by the Gershgorin circle theorem,
+/// half-open just above the max to contain all the eigenvalues of a symmetric tridiagonal T
+pub fn gershgorin_interval<const N: usize>(t: &BandedMatrix<N>) -> std::ops::Range<f64> {
+    let (d, e) = tridiagonal_entries(t);
+    let radius = |i: usize| (if i > 0 { e[i - 1].abs() } else { 0.0 }) + e.get(i).map_or(0.0, |e_i| e_i.abs());
+    let lower = (0..N).map(|i| d[i] - radius(i)).fold(f64::INFINITY, f64::min);
+    let upper = (0..N).map(|i| d[i] + radius(i)).fold(f64::NEG_INFINITY, f64::max);
+    lower..upper.next_up()
+}
+
+/// the eigenvalues of a symmetric tridiagonal T (e.g. `tridiagonalize`d) in `interval` = [a, b)
+/// in ascending order, each by the bisection on `sturm_count`:
+/// 
+/// ```text
+/// λ_k in [l, u)  ->  λ_k in [l, m) if sturm_count(m) > k else [m, u)  (m = (l + u) / 2)
+/// ```
+/// 
+/// until u - l <= `tolerance` max(|a|, |b|), in O(N log(1 / tolerance)) per eigenvalue
+/// without computing those outside `interval` unlike the QR algorithm.
+/// `gershgorin_interval` for all the eigenvalues.
+pub fn bisection_eigenvalues<const N: usize>(
+    t: &BandedMatrix<N>,
+    interval: std::ops::Range<f64>,
+    tolerance: f64,
+) -> Vec<f64> {
+    let (d, e) = tridiagonal_entries(t);
+    let width = tolerance * interval.start.abs().max(interval.end.abs());
+    let (first, last) = (sturm_count_entries(&d, &e, interval.start), sturm_count_entries(&d, &e, interval.end));
+    (first..last).map(|k| {
+        let (mut lower, mut upper) = (interval.start, interval.end);
+        while upper - lower > width {
+            let middle = (lower + upper) / 2.0;
+            if middle <= lower || upper <= middle {
+                break;
+            }
+            if sturm_count_entries(&d, &e, middle) > k {
+                upper = middle;
+            } else {
+                lower = middle;
+            }
+        }
+        (lower + upper) / 2.0
+    }).collect()
+}
@@ -0,0 +1,577 @@
+//! Common configuration of the random experiments of the solvers
+
+/// How many random instances (`trials`) of which sizes (`sizes`) to experiment with
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExperimentConfig {
+    /// random instances per size, 100 by default
+    pub trials: usize,
+    /// N to be experimented with among those in `sweep_sizes!`, all of them if empty (by default)
+    pub sizes: Vec<usize>,
+    /// `seed_rng` before each trial of `run` by this and the trial index to reproduce the instances
+    /// (also in parallel), not if `None` (by default)
+    pub seed: Option<u64>,
+    /// wall-clock limit of each trial of `try_run`, none by default
+    pub timeout: Option<std::time::Duration>,
+}
+
+impl Default for ExperimentConfig {
+    fn default() -> Self {
+        Self { trials: 100, sizes: vec![], seed: None, timeout: None }
+    }
+}
+
+impl ExperimentConfig {
+    pub fn with_trials(self, trials: usize) -> Self {
+        Self { trials, ..self }
+    }
+    
+    pub fn with_sizes(self, sizes: impl Into<Vec<usize>>) -> Self {
+        Self { sizes: sizes.into(), ..self }
+    }
+    
+    pub fn with_seed(self, seed: u64) -> Self {
+        Self { seed: Some(seed), ..self }
+    }
+    
+    pub fn with_timeout(self, timeout: std::time::Duration) -> Self {
+        Self { timeout: Some(timeout), ..self }
+    }
+    
+    /// whether N = `size` is to be experimented with
+    pub fn includes(&self, size: usize) -> bool {
+        self.sizes.is_empty() || self.sizes.contains(&size)
+    }
+    
+    /// the stats of `experiment` run `trials` times, in parallel with the `parallel` feature
+    /// (in the order of the trials, each timed on its worker thread, and so with the contention
+    /// of the others in the time)
+    pub fn run<S: Send>(&self, experiment: impl Fn() -> S + Sync) -> Vec<S> {
+        self.trials(experiment)
+    }
+    
+    /// `run` recording the trials that panicked or ran out of `timeout` instead of aborting all of them.
+    /// A trial is stopped at the first `check_deadline` after its `timeout`, and is taken as timed out
+    /// also when it completes later than that without checking.
+    pub fn try_run<S: Send>(&self, experiment: impl Fn() -> S + Sync) -> Vec<TrialOutcome<S>> {
+        self.trials(|| {
+            let start = std::time::Instant::now();
+            let previous = DEADLINE.replace(self.timeout.map(|timeout| start + timeout));
+            let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(&experiment));
+            DEADLINE.set(previous);
+            
+            let elapsed = start.elapsed();
+            match result {
+                Ok(_) if self.timeout.is_some_and(|timeout| elapsed > timeout) => TrialOutcome::TimedOut(elapsed),
+                Ok(stat) => TrialOutcome::Completed(stat),
+                Err(payload) if payload.is::<DeadlineExceeded>() => TrialOutcome::TimedOut(elapsed),
+                Err(payload) => TrialOutcome::Failed(
+                    payload.downcast_ref::<&str>().map(|message| message.to_string())
+                        .or_else(|| payload.downcast_ref::<String>().cloned())
+                        .unwrap_or_default()
+                ),
+            }
+        })
+    }
+    
+    fn trials<R: Send>(&self, experiment: impl Fn() -> R + Sync) -> Vec<R> {
+        let trial = |index: usize| {
+            if let Some(seed) = self.seed {
+                // spread over the seeds so that the nearby seeds don't share the trials
+                crate::seed_rng(seed ^ (index as u64).wrapping_mul(0x9e3779b97f4a7c15));
+            }
+            experiment()
+        };
+        
+        #[cfg(feature = "parallel")]
+        {
+            use rayon::prelude::*;
+            (0..self.trials).into_par_iter().map(trial).collect()
+        }
+        #[cfg(not(feature = "parallel"))]
+        {
+            (0..self.trials).map(trial).collect()
+        }
+    }
+    
+    /// `run` recorded with the metadata to be archived by `ExperimentRecord::write_json`,
+    /// seeded by a fresh random seed if `seed` is `None` so that the run can be reproduced anyway
+    pub fn record<S: Send>(&self, solver: impl Into<String>, n: usize, experiment: impl Fn() -> S + Sync) -> ExperimentRecord<S> {
+        let seed = self.seed.unwrap_or_else(rand::random);
+        let timestamp = std::time::SystemTime::now();
+        let stats = Self { seed: Some(seed), ..self.clone() }.run(experiment);
+        ExperimentRecord { solver: solver.into(), n, seed, timestamp, stats }
+    }
+}
+
+thread_local! {
+    // of the trial of `try_run` running on this thread
+    static DEADLINE: std::cell::Cell<Option<std::time::Instant>> = const { std::cell::Cell::new(None) };
+}
+
+/// the unwinding payload of `check_deadline`
+struct DeadlineExceeded;
+
+/// Stop the trial of `ExperimentConfig::try_run` running on this thread as timed out if it is past
+/// its `timeout`, to be called in each step of a possibly long iteration (a no-op outside `try_run`)
+pub fn check_deadline() {
+    if DEADLINE.with(std::cell::Cell::get).is_some_and(|deadline| std::time::Instant::now() >= deadline) {
+        // not `panic!` so as not to print the panic message of each timed-out trial
+        std::panic::resume_unwind(Box::new(DeadlineExceeded));
+    }
+}
+
+/// What became of a trial of `ExperimentConfig::try_run`
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TrialOutcome<S> {
+    Completed(S),
+    /// panicked with the message, e.g. at a zero pivot
+    Failed(String),
+    /// ran out of `ExperimentConfig::timeout`, stopped after the elapsed time
+    TimedOut(std::time::Duration),
+}
+
+impl<S> TrialOutcome<S> {
+    pub fn completed(&self) -> Option<&S> {
+        match self {
+            Self::Completed(stat) => Some(stat),
+            _ => None,
+        }
+    }
+    pub fn into_completed(self) -> Option<S> {
+        match self {
+            Self::Completed(stat) => Some(stat),
+            _ => None,
+        }
+    }
+}
+
+impl<S, E: std::fmt::Display> TrialOutcome<Result<S, E>> {
+    /// a completed trial of an error as failed by the error's message
+    pub fn flatten(self) -> TrialOutcome<S> {
+        match self {
+            Self::Completed(Ok(stat)) => TrialOutcome::Completed(stat),
+            Self::Completed(Err(error)) => TrialOutcome::Failed(error.to_string()),
+            Self::Failed(message) => TrialOutcome::Failed(message),
+            Self::TimedOut(elapsed) => TrialOutcome::TimedOut(elapsed),
+        }
+    }
+}
+
+/// the metrics of the completed stat (missing in the other trials) with `failed` and `timed_out`
+/// of 0 or 1, whose means in `ExperimentSummary` are the rates of the failures and the timeouts
+impl<S: ExperimentStat> ExperimentStat for TrialOutcome<S> {
+    fn metrics(&self) -> Vec<(&'static str, f64)> {
+        let (mut metrics, failed, timed_out) = match self {
+            Self::Completed(stat) => (stat.metrics(), 0.0, 0.0),
+            Self::Failed(_) => (vec![], 1.0, 0.0),
+            Self::TimedOut(_) => (vec![], 0.0, 1.0),
+        };
+        metrics.extend([("failed", failed), ("timed_out", timed_out)]);
+        metrics
+    }
+}
+
+/// Evaluate `body` for each of the literal sizes `config.includes`, with the const `N` bound to it,
+/// collecting `(N, body)` in the order of the literals, as the sizes of the const generic matrices
+/// can't be chosen at runtime:
+/// 
+/// ```text
+/// sweep_sizes!(&config, [100, 200, 400], |N| EquationSolver::<N>::new_lu().experiments(&config).len())
+/// ```
+/// 
+/// `body` is to be of the same type for all N (e.g. a summary or `()` by plotting the stats).
+#[macro_export]
+macro_rules! sweep_sizes {
+    ($config:expr, [$($size:literal),+ $(,)?], |$n:ident| $body:expr) => {{
+        let config: &$crate::ExperimentConfig = $config;
+        let mut results = ::std::vec::Vec::new();
+        $(
+            if config.includes($size) {
+                #[allow(non_upper_case_globals)]
+                const $n: usize = $size;
+                results.push(($size, $body));
+            }
+        )+
+        results
+    }};
+}
+
+/// The scalar metrics of a stat of an experiment, to be aggregated by `ExperimentSummary`
+pub trait ExperimentStat {
+    /// `(name, value)` of each metric
+    fn metrics(&self) -> Vec<(&'static str, f64)>;
+}
+
+/// How to time a solve: `warm_ups` untimed runs and then `repetitions` timed ones,
+/// as a single shot at a small N is dominated by the noise of the allocator and the cache
+/// (constructed by `new` to have at least one repetition)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Benchmark {
+    warm_ups: usize,
+    repetitions: usize,
+}
+
+/// a single shot without warm-up
+impl Default for Benchmark {
+    fn default() -> Self {
+        Self { warm_ups: 0, repetitions: 1 }
+    }
+}
+
+impl Benchmark {
+    pub fn new(warm_ups: usize, repetitions: usize) -> Self {
+        assert!(repetitions > 0, "no repetitions to time");
+        Self { warm_ups, repetitions }
+    }
+    
+    /// the result of the last run of `f` with the `Timing` of the repetitions
+    pub fn run<R>(&self, mut f: impl FnMut() -> R) -> (R, Timing) {
+        for _ in 0..self.warm_ups {
+            f();
+        }
+        
+        let mut times = Vec::with_capacity(self.repetitions);
+        let mut result = None;
+        for _ in 0..self.repetitions {
+            let t = std::time::Instant::now();
+            result = Some(f());
+            times.push(t.elapsed());
+        }
+        times.sort();
+        
+        let k = times.len();
+        let median = if k % 2 == 1 { times[k / 2] } else { (times[k / 2 - 1] + times[k / 2]) / 2 };
+        (result.expect("no repetitions to time"), Timing { median, min: times[0], max: times[k - 1] })
+    }
+}
+
+/// Times of the repetitions of a `Benchmark`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Timing {
+    pub median: std::time::Duration,
+    pub min: std::time::Duration,
+    pub max: std::time::Duration,
+}
+
+impl Timing {
+    /// max - min, 0 for a single shot
+    pub fn spread(&self) -> std::time::Duration {
+        self.max - self.min
+    }
+}
+
+/// Outputs of the stats of all the trials, e.g. `solver.experiments(&config).write_csv(path)`
+pub trait ExperimentStats {
+    /// one row per trial and one column per metric after `trial` (0, 1, ...),
+    /// in the shortest round-trip representation (`{:?}`), left empty for the metrics missing in the trial
+    fn write_csv(&self, path: impl AsRef<std::path::Path>) -> std::io::Result<()>;
+}
+
+impl<S: ExperimentStat> ExperimentStats for [S] {
+    fn write_csv(&self, path: impl AsRef<std::path::Path>) -> std::io::Result<()> {
+        use std::io::Write;
+        
+        let rows = self.iter().map(ExperimentStat::metrics).collect::<Vec<_>>();
+        let mut header: Vec<&'static str> = Vec::new();
+        for (name, _) in rows.iter().flatten() {
+            if !header.contains(name) {
+                header.push(name);
+            }
+        }
+        
+        let mut file = std::io::BufWriter::new(std::fs::File::create(path)?);
+        writeln!(file, "trial,{}", header.join(","))?;
+        for (trial, metrics) in rows.iter().enumerate() {
+            let cells = header.iter()
+                .map(|name| metrics.iter().find(|(metric, _)| metric == name).map(|(_, value)| format!("{value:?}")).unwrap_or_default())
+                .collect::<Vec<_>>();
+            writeln!(file, "{trial},{}", cells.join(","))?;
+        }
+        file.flush()
+    }
+}
+
+/// The stats of a run of `ExperimentConfig::record` with what to reproduce it by
+pub struct ExperimentRecord<S> {
+    /// the name of the solver, e.g. `"lu"`
+    pub solver: String,
+    pub n: usize,
+    pub seed: u64,
+    /// when the run started
+    pub timestamp: std::time::SystemTime,
+    pub stats: Vec<S>,
+}
+
+impl<S: ExperimentStat> ExperimentRecord<S> {
+    /// as the JSON object
+    /// 
+    /// ```text
+    /// {"solver": "lu", "n": 100, "seed": 42, "timestamp": "2025-01-01T00:00:00Z", "trials": [{"residual_norm": 1.2e-14, ...}, ...]}
+    /// ```
+    /// 
+    /// in a line per trial to be diffed between the revisions, the non-finite metrics as `null`
+    pub fn write_json(&self, path: impl AsRef<std::path::Path>) -> std::io::Result<()> {
+        use std::io::Write;
+        
+        let mut file = std::io::BufWriter::new(std::fs::File::create(path)?);
+        writeln!(file, "{{")?;
+        writeln!(file, "  \"solver\": {},", json_string(&self.solver))?;
+        writeln!(file, "  \"n\": {},", self.n)?;
+        writeln!(file, "  \"seed\": {},", self.seed)?;
+        writeln!(file, "  \"timestamp\": \"{}\",", rfc3339(self.timestamp))?;
+        writeln!(file, "  \"trials\": [")?;
+        for (trial, stat) in self.stats.iter().enumerate() {
+            let metrics = stat.metrics().into_iter()
+                .map(|(name, value)| format!("{}: {}", json_string(name), if value.is_finite() { format!("{value:?}") } else { "null".into() }))
+                .collect::<Vec<_>>();
+            let separator = if trial + 1 < self.stats.len() { "," } else { "" };
+            writeln!(file, "    {{{}}}{separator}", metrics.join(", "))?;
+        }
+        writeln!(file, "  ]")?;
+        writeln!(file, "}}")?;
+        file.flush()
+    }
+}
+
+fn json_string(s: &str) -> String {
+    let mut escaped = String::from('"');
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            c if c.is_control() => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+/// `YYYY-MM-DDThh:mm:ssZ` in UTC, the date by the days-to-civil algorithm of the proleptic Gregorian calendar
+fn rfc3339(time: std::time::SystemTime) -> String {
+    let secs = time.duration_since(std::time::UNIX_EPOCH).map_or(0, |elapsed| elapsed.as_secs());
+    let (days, secs) = ((secs / 86400) as i64, secs % 86400);
+    
+    // shifted to the eras of 400 years from 0000-03-01
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let day_of_era = z.rem_euclid(146097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_from_march = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_from_march + 2) / 5 + 1;
+    let month = if month_from_march < 10 { month_from_march + 3 } else { month_from_march - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    
+    format!("{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z", secs / 3600, secs % 3600 / 60, secs % 60)
+}
+
+/// Mean, median, standard deviation, min, max, and quantiles of a metric across the trials
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MetricSummary {
+    pub mean: f64,
+    pub median: f64,
+    /// the sample standard deviation (divided by n - 1), 0 for a single trial
+    pub std_dev: f64,
+    pub min: f64,
+    pub max: f64,
+    /// `(p, the p-quantile)` for each p of `ExperimentSummary::with_quantiles`
+    pub quantiles: Vec<(f64, f64)>,
+}
+
+impl MetricSummary {
+    /// of the non-empty `values`, the quantiles linearly interpolated between the order statistics
+    /// 
+    /// ```text
+    /// Q(p) = x_(k) + (h - k) (x_(k+1) - x_(k))  where h = (n - 1) p, k = floor(h)
+    /// ```
+    pub fn of(values: &[f64], quantiles: &[f64]) -> Self {
+        assert!(!values.is_empty(), "no values to summarize");
+        
+        let mut sorted = values.to_vec();
+        sorted.sort_by(f64::total_cmp);
+        let quantile = |p: f64| {
+            let h = (sorted.len() - 1) as f64 * p.clamp(0.0, 1.0);
+            let k = h.floor() as usize;
+            match sorted.get(k + 1) {
+                Some(next) => sorted[k] + (h - k as f64) * (next - sorted[k]),
+                None => sorted[k],
+            }
+        };
+        
+        let n = values.len() as f64;
+        let mean = values.iter().sum::<f64>() / n;
+        let std_dev = if values.len() > 1 {
+            (values.iter().map(|value| (value - mean).powi(2)).sum::<f64>() / (n - 1.0)).sqrt()
+        } else {
+            0.0
+        };
+        
+        Self {
+            mean,
+            median: quantile(0.5),
+            std_dev,
+            min: sorted[0],
+            max: sorted[sorted.len() - 1],
+            quantiles: quantiles.iter().map(|&p| (p, quantile(p))).collect(),
+        }
+    }
+}
+
+/// `MetricSummary` of each metric of the stats, in the order of `ExperimentStat::metrics`,
+/// to quote the numbers in the reports as
+/// 
+/// ```text
+/// println!("{}", ExperimentSummary::new(&solver.experiments(&config)));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ExperimentSummary {
+    pub trials: usize,
+    pub metrics: Vec<(&'static str, MetricSummary)>,
+}
+
+impl ExperimentSummary {
+    /// with the 5% and 95% quantiles
+    pub fn new<S: ExperimentStat>(stats: &[S]) -> Self {
+        Self::with_quantiles(stats, &[0.05, 0.95])
+    }
+    
+    /// with the p-quantiles for p in `quantiles`; the metrics missing in some stats
+    /// (e.g. `peak_memory_bytes` without `CountingAllocator`) are summarized over the others
+    pub fn with_quantiles<S: ExperimentStat>(stats: &[S], quantiles: &[f64]) -> Self {
+        let mut columns: Vec<(&'static str, Vec<f64>)> = Vec::new();
+        for (name, value) in stats.iter().flat_map(ExperimentStat::metrics) {
+            match columns.iter_mut().find(|(column, _)| *column == name) {
+                Some((_, values)) => values.push(value),
+                None => columns.push((name, vec![value])),
+            }
+        }
+        
+        Self {
+            trials: stats.len(),
+            metrics: columns.into_iter().map(|(name, values)| (name, MetricSummary::of(&values, quantiles))).collect(),
+        }
+    }
+    
+    pub fn metric(&self, name: &str) -> Option<&MetricSummary> {
+        self.metrics.iter().find(|(metric, _)| *metric == name).map(|(_, summary)| summary)
+    }
+    
+    /// the rate of the failed trials of `TrialOutcome`s (`None` for the plain stats)
+    pub fn failure_rate(&self) -> Option<f64> {
+        self.metric("failed").map(|failed| failed.mean)
+    }
+}
+
+impl std::fmt::Display for ExperimentSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let width = self.metrics.iter().map(|(name, _)| name.len()).max().unwrap_or(0).max("metric".len());
+        
+        write!(f, "{:<width$}  {:>10}  {:>10}  {:>10}  {:>10}  {:>10}", "metric", "mean", "std dev", "min", "median", "max")?;
+        if let Some((_, summary)) = self.metrics.first() {
+            for (p, _) in &summary.quantiles {
+                write!(f, "  {:>10}", format!("q{p}"))?;
+            }
+        }
+        writeln!(f, "    ({} trials)", self.trials)?;
+        
+        for (name, summary) in &self.metrics {
+            write!(f, "{name:<width$}")?;
+            for value in [summary.mean, summary.std_dev, summary.min, summary.median, summary.max] {
+                write!(f, "  {value:>10.3e}")?;
+            }
+            for (_, value) in &summary.quantiles {
+                write!(f, "  {value:>10.3e}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// Median times over the sizes with the empirical exponent p of the fitted `time ≈ C N^p`,
+/// e.g. to verify O(N^3) of a direct solver:
+/// 
+/// ```text
+/// let study = scaling_study!(&config, [100, 200, 400, 800], |N| EquationSolver::<N>::new_lu().experiments(&config));
+/// println!("{study}");
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScalingStudy {
+    /// `(N, median time in seconds)` in the order of the sizes
+    pub median_times: Vec<(usize, f64)>,
+}
+
+impl ScalingStudy {
+    pub fn new(median_times: Vec<(usize, f64)>) -> Self {
+        Self { median_times }
+    }
+    
+    /// the median of the `elapsed_secs` metric of the non-empty `stats`
+    pub fn median_time<S: ExperimentStat>(stats: &[S]) -> f64 {
+        ExperimentSummary::with_quantiles(stats, &[])
+            .metric("elapsed_secs")
+            .expect("no elapsed time in the stats")
+            .median
+    }
+    
+    /// (p, C) of the least squares fit of
+    /// 
+    /// ```text
+    /// log t = p log N + log C
+    /// ```
+    /// 
+    /// over the positive times (as a too fast run may be timed 0),
+    /// or `None` for less than 2 distinct sizes of them
+    pub fn fit(&self) -> Option<(f64, f64)> {
+        let points = self.median_times.iter()
+            .filter(|&&(_, time)| time > 0.0)
+            .map(|&(n, time)| ((n as f64).ln(), time.ln()))
+            .collect::<Vec<_>>();
+        if points.len() < 2 {
+            return None;
+        }
+        let k = points.len() as f64;
+        let (mean_x, mean_y) = (
+            points.iter().map(|&(x, _)| x).sum::<f64>() / k,
+            points.iter().map(|&(_, y)| y).sum::<f64>() / k,
+        );
+        let sxx = points.iter().map(|&(x, _)| (x - mean_x).powi(2)).sum::<f64>();
+        let sxy = points.iter().map(|&(x, y)| (x - mean_x) * (y - mean_y)).sum::<f64>();
+        
+        (sxx > 0.0).then(|| {
+            let exponent = sxy / sxx;
+            (exponent, (mean_y - exponent * mean_x).exp())
+        })
+    }
+    
+    /// p of `fit`
+    pub fn exponent(&self) -> Option<f64> {
+        self.fit().map(|(exponent, _)| exponent)
+    }
+}
+
+impl std::fmt::Display for ScalingStudy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{:>8}  {:>12}", "N", "median time")?;
+        for (n, time) in &self.median_times {
+            writeln!(f, "{n:>8}  {time:>12.3e}")?;
+        }
+        match self.fit() {
+            Some((exponent, coefficient)) => writeln!(f, "time ≈ {coefficient:.3e} N^{exponent:.3}"),
+            None => writeln!(f, "(no fit for less than 2 sizes)"),
+        }
+    }
+}
+
+/// `ScalingStudy` of the `ScalingStudy::median_time` of the stats by `$stats` for each size by `sweep_sizes!`
+#[macro_export]
+macro_rules! scaling_study {
+    ($config:expr, [$($size:literal),+ $(,)?], |$n:ident| $stats:expr) => {
+        $crate::ScalingStudy::new($crate::sweep_sizes!($config, [$($size),+], |$n| $crate::ScalingStudy::median_time(&$stats)))
+    };
+}
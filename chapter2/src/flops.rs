@@ -0,0 +1,42 @@
+//! Floating-point operation counting of the `flop-count` feature
+
+#[cfg(feature = "flop-count")]
+use std::cell::Cell;
+
+#[cfg(feature = "flop-count")]
+thread_local! {
+    static FLOPS: Cell<u64> = const { Cell::new(0) };
+}
+
+/// Add `flops` to the counter of this thread, a no-op without the `flop-count` feature.
+/// 
+/// Counted are the arithmetic operators, `axpy`, `gemv`, `gemm`, the row operations and the Hadamard
+/// ones of `Matrix` and its views, the triangular substitutions and the LU and Cholesky
+/// decompositions, a multiplication and an addition as 2 flops (so `A B` of N x M and M x L
+/// as 2NML, and the Gaussian elimination as about 2N^3 / 3). Not counted are `map` (and so
+/// the negation), the QR, LDL^T and Gauss-Jordan ones, the sparse and banded kernels and the
+/// loops of the stationary iterations; hand-written loops can count by this.
+#[inline]
+pub fn count_flops(flops: usize) {
+    #[cfg(feature = "flop-count")]
+    let _ = FLOPS.try_with(|count| count.set(count.get() + flops as u64));
+    #[cfg(not(feature = "flop-count"))]
+    let _ = flops;
+}
+
+/// `f()` with the flops it counted on this thread, or `None` without the `flop-count` feature
+pub(crate) fn with_flops<F, R>(f: F) -> (R, Option<u64>)
+where
+    F: FnOnce() -> R,
+{
+    #[cfg(feature = "flop-count")]
+    {
+        let before = FLOPS.with(Cell::get);
+        let result = f();
+        (result, Some(FLOPS.with(Cell::get) - before))
+    }
+    #[cfg(not(feature = "flop-count"))]
+    {
+        (f(), None)
+    }
+}
@@ -0,0 +1,52 @@
+use crate::{Float, Scalar, Matrix, Vector};
+
+/// Solve AX = B by the Gauss-Jordan elimination with partial pivoting,
+/// reducing [A | B] to [I | X] by eliminating each column both below and above the pivot,
+/// or `None` if A is singular. This takes about N^3 / 2 multiplications (for K << N)
+/// against N^3 / 3 of the Gaussian elimination, trading the back substitution for the extra work.
+pub fn gauss_jordan<const N: usize, const K: usize, T: Scalar>(a: &Matrix<N, N, T>, b: &Matrix<N, K, T>) -> Option<Matrix<N, K, T>> {
+    let mut a = a.clone();
+    let mut x = b.clone();
+    for k in 0..N {
+        let (p, _pivot) = (k..N)
+            .map(|i| (i, a[(i, k)]))
+            .filter(|(_, value)| value.modulus() > T::Real::TOLERANCE)
+            // reversed to take the first of the largest, as `max_by` returns the last
+            .rev()
+            .max_by(|(_, a), (_, b)| T::Real::partial_cmp(&a.modulus(), &b.modulus()).expect("found NaN or Inf"))?;
+        if p != k {
+            a.swap_rows(p, k);
+            x.swap_rows(p, k);
+        }
+        
+        // normalize the pivot row to make a_{kk} = 1
+        let pivot = a[(k, k)];
+        ((k + 1)..N).for_each(|j| a[(k, j)] /= pivot);
+        (0..K).for_each(|j| x[(k, j)] /= pivot);
+        a[(k, k)] = T::ONE;
+        
+        for i in (0..N).filter(|&i| i != k) {
+            let factor = a[(i, k)];
+            if factor == T::ZERO {
+                continue;
+            }
+            for j in (k + 1)..N {
+                let update = factor * a[(k, j)];
+                a[(i, j)] -= update;
+            }
+            for j in 0..K {
+                let update = factor * x[(k, j)];
+                x[(i, j)] -= update;
+            }
+            a[(i, k)] = T::ZERO;
+        }
+    }
+    Some(x)
+}
+
+/// Solve Ax = b by `gauss_jordan` reducing [A | b I] to [I | x A^{-1}] at once,
+/// returning x together with A^{-1}, or `None` if A is singular
+pub fn gauss_jordan_with_inverse<const N: usize, T: Scalar>(a: &Matrix<N, N, T>, b: &Vector<N, T>) -> Option<(Vector<N, T>, Matrix<N, N, T>)> where [(); 1 + N]: {
+    let reduced = gauss_jordan(a, &Matrix::concat(b, &Matrix::<N, N, T>::identity()))?;
+    Some(reduced.split_at_column())
+}
@@ -0,0 +1,470 @@
+use crate::{Float, Matrix, Vector, Preconditioner, NoPreconditioner, SparseMatrix, DynMatrix, GivensRotation, SolveError, check_deadline};
+use crate::eigen::arnoldi_by;
+
+/// approximate solution of Ax = b by an iterative method
+#[derive(Clone)]
+pub struct IterativeSolution<const N: usize, T = f64> {
+    pub solution: Vector<N, T>,
+    pub iteration_count: usize,
+    /// ||b - A x_k|| for k = 0, 1, ..., `iteration_count`
+    pub residual_norms: Vec<T>,
+    /// whether the `StoppingCriterion` was met within the maximum iterations
+    pub converged: bool,
+}
+
+impl<const N: usize, T: Float> std::fmt::Debug for IterativeSolution<N, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("IterativeSolution")
+            .field("solution", &self.solution)
+            .field("iteration_count", &self.iteration_count)
+            .field("residual_norms", &self.residual_norms)
+            .field("converged", &self.converged)
+            .finish()
+    }
+}
+
+impl<const N: usize, T: Float> IterativeSolution<N, T> {
+    /// the solution if converged, e.g. for `EquationSolver::new_fallible`
+    pub fn into_result(self) -> Result<Vector<N, T>, SolveError> {
+        if self.converged {
+            Ok(self.solution)
+        } else {
+            Err(SolveError::NotConverged { iteration_count: self.iteration_count })
+        }
+    }
+}
+
+/// when to stop an iterative method at x_k
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum StoppingCriterion<T = f64> {
+    /// ||b - A x_k|| <= tolerance
+    AbsoluteResidual(T),
+    /// ||b - A x_k|| <= tolerance ||b||
+    RelativeResidual(T),
+    /// ||x_k - x_{k-1}|| <= tolerance ||x_k||, which may stop far from
+    /// the solution when the iteration converges slowly
+    SolutionChange(T),
+}
+
+impl<T: Float> StoppingCriterion<T> {
+    fn is_met<const N: usize>(
+        &self,
+        b: &Vector<N, T>,
+        residual_norm: T,
+        x: &Vector<N, T>,
+        previous: Option<&Vector<N, T>>,
+    ) -> bool {
+        match *self {
+            Self::AbsoluteResidual(tolerance) => residual_norm <= tolerance,
+            Self::RelativeResidual(tolerance) => residual_norm <= tolerance * b.norm(),
+            Self::SolutionChange(tolerance) => previous.is_some_and(|previous| (x - previous).norm() <= tolerance * x.norm()),
+        }
+    }
+}
+
+/// iterative method for Ax = b given by its one step x_k -> x_{k+1},
+/// so that the initial guess, the maximum iterations, and the `StoppingCriterion`
+/// are handled by `solve` in common
+pub trait IterativeSolve<const N: usize, T: Float = f64> {
+    /// `step: x_k -> x_{k+1}` in place starting from `x0`, keeping the state of the method if any
+    fn stepper<'a>(
+        &'a self,
+        a: &'a Matrix<N, N, T>,
+        b: &'a Vector<N, T>,
+        x0: &Vector<N, T>,
+    ) -> impl FnMut(&mut Vector<N, T>) + 'a;
+    
+    /// Iterate from `x0` until `criterion` is met or `max_iterations`, recording the residual norms
+    fn solve(
+        &self,
+        a: &Matrix<N, N, T>,
+        b: &Vector<N, T>,
+        x0: &Vector<N, T>,
+        max_iterations: usize,
+        criterion: StoppingCriterion<T>,
+    ) -> IterativeSolution<N, T> {
+        let mut step = self.stepper(a, b, x0);
+        let mut x = x0.clone();
+        let mut residual_norms = vec![(b - a * &x).norm()];
+        let mut converged = criterion.is_met(b, residual_norms[0], &x, None);
+        while !converged && residual_norms.len() <= max_iterations {
+            check_deadline();
+            let previous = x.clone();
+            step(&mut x);
+            let residual_norm = (b - a * &x).norm();
+            residual_norms.push(residual_norm);
+            converged = criterion.is_met(b, residual_norm, &x, Some(&previous));
+        }
+        
+        IterativeSolution {
+            solution: x,
+            iteration_count: residual_norms.len() - 1,
+            residual_norms,
+            converged,
+        }
+    }
+}
+
+fn assert_nonzero_diagonal<const N: usize, T: Float>(a: &Matrix<N, N, T>) {
+    assert!((0..N).all(|i| a[(i, i)] != T::ZERO), "Matrix has a zero on the diagonal");
+}
+
+/// damped Richardson iteration correcting by the residual:
+/// 
+/// ```text
+/// x_{k+1} = x_k + α (b - A x_k)
+/// ```
+/// 
+/// converging iff ρ(I - αA) < 1. For a symmetric positive definite A
+/// the optimal α = 2 / (λ_min + λ_max) gives the rate (κ - 1) / (κ + 1),
+/// the baseline which `Jacobi` and `Pcg` are to improve on.
+/// `alpha: None` is the optimal one by λ_min and λ_max estimated with power iterations.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Richardson<T = f64> {
+    pub alpha: Option<T>,
+}
+
+impl<T: Float> Richardson<T> {
+    pub const OPTIMAL: Self = Self { alpha: None };
+    
+    /// 2 / (λ_min + λ_max) for a symmetric positive definite A, by the power iteration
+    /// for λ_max and then for λ_max - λ_min as the largest eigenvalue of λ_max I - A
+    pub fn optimal_alpha<const N: usize>(a: &Matrix<N, N, T>) -> T {
+        let lambda_max = power_iteration(|x| a * x);
+        let lambda_min = lambda_max - power_iteration(|x| {
+            let mut shifted = Vector::<N, T>::zeroed();
+            shifted.axpy(lambda_max, x);
+            &shifted - &(a * x)
+        });
+        T::from_f64(2.0) / (lambda_min + lambda_max)
+    }
+}
+
+/// largest eigenvalue of a symmetric positive semidefinite `apply: x -> Ax`
+/// by the Rayleigh quotients of the power iteration until they stop changing
+fn power_iteration<const N: usize, T: Float>(apply: impl Fn(&Vector<N, T>) -> Vector<N, T>) -> T {
+    const MAX_ITERATIONS: usize = 10000;
+    
+    // random, as any regular vector may be orthogonal to the extreme eigenvectors of symmetric grids
+    let mut x = Vector::<N>::random_uniform(-1.0, 1.0).cast::<T>().normalized();
+    let mut lambda = T::ZERO;
+    for _ in 0..MAX_ITERATIONS {
+        check_deadline();
+        let y = apply(&x);
+        let next = x.dot(&y);
+        x = y.normalized();
+        if (next - lambda).abs() <= T::TOLERANCE * next.abs() {
+            return next;
+        }
+        lambda = next;
+    }
+    lambda
+}
+
+impl<const N: usize, T: Float> IterativeSolve<N, T> for Richardson<T> {
+    fn stepper<'a>(
+        &'a self,
+        a: &'a Matrix<N, N, T>,
+        b: &'a Vector<N, T>,
+        _x0: &Vector<N, T>,
+    ) -> impl FnMut(&mut Vector<N, T>) + 'a {
+        let alpha = self.alpha.unwrap_or_else(|| Self::optimal_alpha(a));
+        move |x| {
+            let residual = b - a * &*x;
+            x.axpy(alpha, &residual);
+        }
+    }
+}
+
+/// Jacobi iteration updating all the components from the previous iterate:
+/// 
+/// ```text
+/// x_i^{(k+1)} = (b_i - sum_{j != i} a_{ij} x_j^{(k)}) / a_{ii}
+/// ```
+/// 
+/// converging for any x_0 iff ρ(D^{-1}(L + U)) < 1, e.g. for a strictly diagonally dominant A
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Jacobi;
+
+impl<const N: usize, T: Float> IterativeSolve<N, T> for Jacobi {
+    fn stepper<'a>(
+        &'a self,
+        a: &'a Matrix<N, N, T>,
+        b: &'a Vector<N, T>,
+        _x0: &Vector<N, T>,
+    ) -> impl FnMut(&mut Vector<N, T>) + 'a {
+        assert_nonzero_diagonal(a);
+        |x| {
+            let previous = x.clone();
+            for i in 0..N {
+                let sum = (0..N).filter(|&j| j != i).map(|j| a[(i, j)] * previous[j]).sum::<T>();
+                x[i] = (b[i] - sum) / a[(i, i)];
+            }
+        }
+    }
+}
+
+/// Gauss-Seidel iteration using the already updated components at once:
+/// 
+/// ```text
+/// x_i^{(k+1)} = (b_i - sum_{j < i} a_{ij} x_j^{(k+1)} - sum_{j > i} a_{ij} x_j^{(k)}) / a_{ii}
+/// ```
+/// 
+/// converging e.g. for a strictly diagonally dominant or symmetric positive definite A,
+/// typically twice as fast as `Jacobi` (ρ_GS = ρ_J^2 for consistently ordered A).
+/// This is `Sor` with ω = 1.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GaussSeidel;
+
+impl<const N: usize, T: Float> IterativeSolve<N, T> for GaussSeidel {
+    fn stepper<'a>(
+        &'a self,
+        a: &'a Matrix<N, N, T>,
+        b: &'a Vector<N, T>,
+        _x0: &Vector<N, T>,
+    ) -> impl FnMut(&mut Vector<N, T>) + 'a {
+        sor_stepper(a, b, T::ONE)
+    }
+}
+
+/// successive over-relaxation SOR(ω), extrapolating each Gauss-Seidel update by ω:
+/// 
+/// ```text
+/// x_i^{(k+1)} = (1 - ω) x_i^{(k)} + ω (b_i - sum_{j < i} a_{ij} x_j^{(k+1)} - sum_{j > i} a_{ij} x_j^{(k)}) / a_{ii}
+/// ```
+/// 
+/// converging only for 0 < ω < 2, and for any of them if A is symmetric positive definite.
+/// For consistently ordered A the optimal ω = 2 / (1 + sqrt(1 - ρ_J^2)).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Sor<T = f64> {
+    pub omega: T,
+}
+
+impl<const N: usize, T: Float> IterativeSolve<N, T> for Sor<T> {
+    fn stepper<'a>(
+        &'a self,
+        a: &'a Matrix<N, N, T>,
+        b: &'a Vector<N, T>,
+        _x0: &Vector<N, T>,
+    ) -> impl FnMut(&mut Vector<N, T>) + 'a {
+        sor_stepper(a, b, self.omega)
+    }
+}
+
+fn sor_stepper<'a, const N: usize, T: Float>(
+    a: &'a Matrix<N, N, T>,
+    b: &'a Vector<N, T>,
+    omega: T,
+) -> impl FnMut(&mut Vector<N, T>) + 'a {
+    assert_nonzero_diagonal(a);
+    move |x| {
+        for i in 0..N {
+            let sum = (0..N).filter(|&j| j != i).map(|j| a[(i, j)] * x[j]).sum::<T>();
+            x[i] = (T::ONE - omega) * x[i] + omega * (b[i] - sum) / a[(i, i)];
+        }
+    }
+}
+
+/// preconditioned conjugate gradient method for a symmetric positive definite A
+/// and a symmetric positive definite `preconditioner` M:
+/// 
+/// ```text
+/// r_0 = b - A x_0,  z_0 = M^{-1} r_0,  p_0 = z_0,
+/// α_k = (r_k, z_k) / (p_k, A p_k),  x_{k+1} = x_k + α_k p_k,  r_{k+1} = r_k - α_k A p_k,
+/// z_{k+1} = M^{-1} r_{k+1},  β_k = (r_{k+1}, z_{k+1}) / (r_k, z_k),  p_{k+1} = z_{k+1} + β_k p_k
+/// ```
+/// 
+/// converging in at most N iterations in exact arithmetic, and by the rate
+/// (sqrt(κ) - 1) / (sqrt(κ) + 1) with κ = κ(M^{-1} A), which the preconditioner is to reduce.
+/// `NoPreconditioner` gives the plain CG.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Pcg<P> {
+    pub preconditioner: P,
+}
+
+impl<const N: usize, T: Float, P: Preconditioner<N, T>> IterativeSolve<N, T> for Pcg<P> {
+    fn stepper<'a>(
+        &'a self,
+        a: &'a Matrix<N, N, T>,
+        b: &'a Vector<N, T>,
+        x0: &Vector<N, T>,
+    ) -> impl FnMut(&mut Vector<N, T>) + 'a {
+        pcg_stepper(a, b, x0, &self.preconditioner)
+    }
+}
+
+/// `Pcg` borrowing a possibly unsized preconditioner like `dyn Preconditioner`
+struct PcgRef<'p, P: ?Sized>(&'p P);
+
+impl<const N: usize, T: Float, P: Preconditioner<N, T> + ?Sized> IterativeSolve<N, T> for PcgRef<'_, P> {
+    fn stepper<'a>(
+        &'a self,
+        a: &'a Matrix<N, N, T>,
+        b: &'a Vector<N, T>,
+        x0: &Vector<N, T>,
+    ) -> impl FnMut(&mut Vector<N, T>) + 'a {
+        pcg_stepper(a, b, x0, self.0)
+    }
+}
+
+fn pcg_stepper<'a, const N: usize, T: Float>(
+    a: &'a Matrix<N, N, T>,
+    b: &Vector<N, T>,
+    x0: &Vector<N, T>,
+    preconditioner: &'a (impl Preconditioner<N, T> + ?Sized),
+) -> impl FnMut(&mut Vector<N, T>) + 'a {
+    let mut r = b - a * x0;
+    let mut p = preconditioner.apply(&r);
+    let mut rz = r.dot(&p);
+    move |x| {
+        let ap = a * &p;
+        let alpha = rz / p.dot(&ap);
+        x.axpy(alpha, &p);
+        r.axpy(-alpha, &ap);
+        let z = preconditioner.apply(&r);
+        let next_rz = r.dot(&z);
+        let mut next_p = z;
+        next_p.axpy(next_rz / rz, &p);
+        p = next_p;
+        rz = next_rz;
+    }
+}
+
+/// restarted GMRES(m) for a general nonsingular A, minimizing ||b - A x|| over
+/// x_0 + span{r_0, A r_0, ..., A^{m-1} r_0} by `arnoldi` from r_0 = b - A x_0:
+/// 
+/// ```text
+/// x = x_0 + Q_m y,  y = argmin ||β e_1 - H̄_m y||  (β = ||r_0||)
+/// ```
+/// 
+/// and restarting from x, so that one step x_k -> x_{k+1} here is a whole cycle of
+/// m Arnoldi steps, keeping the memory in O(N m) at the cost of possible stagnation.
+/// 
+/// Preconditioned from the right, applying M^{-1} inside the Arnoldi process:
+/// 
+/// ```text
+/// A M^{-1} Q_m = Q_{m+1} H̄_m,  x = x_0 + M^{-1} Q_m y
+/// ```
+/// 
+/// so that the minimized residual is still the true ||b - A x||.
+/// `NoPreconditioner` gives the plain GMRES(m).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Gmres<P = NoPreconditioner> {
+    pub restart: usize,
+    pub preconditioner: P,
+}
+
+impl<const N: usize, P: Preconditioner<N>> IterativeSolve<N> for Gmres<P> {
+    fn stepper<'a>(
+        &'a self,
+        a: &'a Matrix<N, N>,
+        b: &'a Vector<N>,
+        _x0: &Vector<N>,
+    ) -> impl FnMut(&mut Vector<N>) + 'a {
+        assert!(self.restart > 0, "GMRES needs restart > 0");
+        let a = SparseMatrix::from(a);
+        move |x| {
+            let r = b - &a * &*x;
+            let beta = r.norm();
+            if beta == 0.0 {
+                return;
+            }
+            let arnoldi = arnoldi_by(|q| &a * &self.preconditioner.apply(q), &r, self.restart);
+            let y = hessenberg_least_squares(&arnoldi.hessenberg, beta);
+            let mut correction = Vector::<N>::zeroed();
+            for (y_j, q_j) in y.iter().zip(&arnoldi.basis) {
+                correction.axpy(*y_j, q_j);
+            }
+            x.axpy(1.0, &self.preconditioner.apply(&correction));
+        }
+    }
+}
+
+/// argmin ||β e_1 - H̄ y|| for the (m + 1) x m upper Hessenberg H̄ of full column rank,
+/// reducing H̄ into the upper triangular by the m Givens rotations on the subdiagonal
+fn hessenberg_least_squares(h: &DynMatrix, beta: f64) -> Vec<f64> {
+    let m = h.ncols();
+    let mut r = h.clone();
+    let mut g = vec![0.0; m + 1];
+    g[0] = beta;
+    for j in 0..m {
+        let GivensRotation { c, s, .. } = GivensRotation::zeroing(j, j + 1, r[(j, j)], r[(j + 1, j)]);
+        for l in j..m {
+            let (x_j, x_k) = (r[(j, l)], r[(j + 1, l)]);
+            r[(j, l)] = c * x_j + s * x_k;
+            r[(j + 1, l)] = c * x_k - s * x_j;
+        }
+        (g[j], g[j + 1]) = (c * g[j] + s * g[j + 1], c * g[j + 1] - s * g[j]);
+    }
+    
+    let mut y = vec![0.0; m];
+    for j in (0..m).rev() {
+        let sum = ((j + 1)..m).map(|l| r[(j, l)] * y[l]).sum::<f64>();
+        y[j] = (g[j] - sum) / r[(j, j)];
+    }
+    y
+}
+
+/// `Richardson` with the optimal α from x_0 = 0 until the relative residual reaches `tolerance`
+pub fn richardson<const N: usize, T: Float>(
+    a: &Matrix<N, N, T>,
+    b: &Vector<N, T>,
+    tolerance: T,
+    max_iterations: usize,
+) -> IterativeSolution<N, T> {
+    Richardson::OPTIMAL.solve(a, b, &Vector::zeroed(), max_iterations, StoppingCriterion::RelativeResidual(tolerance))
+}
+
+/// `Jacobi` from x_0 = 0 until the relative residual reaches `tolerance`
+pub fn jacobi<const N: usize, T: Float>(
+    a: &Matrix<N, N, T>,
+    b: &Vector<N, T>,
+    tolerance: T,
+    max_iterations: usize,
+) -> IterativeSolution<N, T> {
+    Jacobi.solve(a, b, &Vector::zeroed(), max_iterations, StoppingCriterion::RelativeResidual(tolerance))
+}
+
+/// `GaussSeidel` from x_0 = 0 until the relative residual reaches `tolerance`
+pub fn gauss_seidel<const N: usize, T: Float>(
+    a: &Matrix<N, N, T>,
+    b: &Vector<N, T>,
+    tolerance: T,
+    max_iterations: usize,
+) -> IterativeSolution<N, T> {
+    GaussSeidel.solve(a, b, &Vector::zeroed(), max_iterations, StoppingCriterion::RelativeResidual(tolerance))
+}
+
+/// `Sor` from x_0 = 0 until the relative residual reaches `tolerance`
+pub fn sor<const N: usize, T: Float>(
+    a: &Matrix<N, N, T>,
+    b: &Vector<N, T>,
+    omega: T,
+    tolerance: T,
+    max_iterations: usize,
+) -> IterativeSolution<N, T> {
+    Sor { omega }.solve(a, b, &Vector::zeroed(), max_iterations, StoppingCriterion::RelativeResidual(tolerance))
+}
+
+/// `Pcg` from x_0 = 0 until the relative residual reaches `tolerance`
+pub fn pcg<const N: usize, T: Float>(
+    a: &Matrix<N, N, T>,
+    b: &Vector<N, T>,
+    preconditioner: &(impl Preconditioner<N, T> + ?Sized),
+    tolerance: T,
+    max_iterations: usize,
+) -> IterativeSolution<N, T> {
+    PcgRef(preconditioner).solve(a, b, &Vector::zeroed(), max_iterations, StoppingCriterion::RelativeResidual(tolerance))
+}
+
+/// `Gmres` from x_0 = 0 until the relative residual reaches `tolerance`,
+/// counting the restart cycles as the iterations
+pub fn gmres<const N: usize>(
+    a: &Matrix<N, N>,
+    b: &Vector<N>,
+    restart: usize,
+    tolerance: f64,
+    max_iterations: usize,
+) -> IterativeSolution<N> {
+    Gmres { restart, preconditioner: NoPreconditioner }.solve(a, b, &Vector::zeroed(), max_iterations, StoppingCriterion::RelativeResidual(tolerance))
+}
@@ -0,0 +1,177 @@
+use crate::{Float, Matrix, Vector, LowerTriangular};
+
+/// LDL^T decomposition of a symmetric (possibly indefinite) A
+/// with Bunch-Kaufman pivoting:
+/// 
+/// ```text
+/// P A P^T = L D L^T
+/// ```
+/// 
+/// where L is unit lower triangular, D is block diagonal with
+/// 1 x 1 and 2 x 2 blocks, and `(P A P^T)_{ij} = a_{pi_i pi_j}`
+#[derive(Clone)]
+pub struct Ldlt<const N: usize, T = f64> {
+    l: LowerTriangular<N, T>,
+    d: Matrix<N, N, T>,
+    permutation: [usize; N],
+}
+
+impl<const N: usize, T: Float> Ldlt<N, T> {
+    pub fn l(&self) -> &LowerTriangular<N, T> {
+        &self.l
+    }
+    
+    /// block diagonal D, where a nonzero d_{k+1,k} marks a 2 x 2 block at k
+    pub fn d(&self) -> &Matrix<N, N, T> {
+        &self.d
+    }
+    
+    /// pi of P
+    pub fn permutation(&self) -> &[usize; N] {
+        &self.permutation
+    }
+    
+    /// x = A^{-1} b by L z = P b, D w = z, L^T y = w, x = P^T y,
+    /// or `None` if D (and so A) is singular
+    pub fn solve(&self, b: &Vector<N, T>) -> Option<Vector<N, T>> {
+        let mut z = Vector::<N, T>::from_fn(|i, _| b[self.permutation[i]]);
+        for i in 0..N {
+            let update = (0..i).map(|j| self.l[(i, j)] * z[j]).sum::<T>();
+            z[i] -= update;
+        }
+        
+        let mut k = 0;
+        while k < N {
+            if k + 1 < N && self.d[(k + 1, k)] != T::ZERO {
+                let (e11, e21, e22) = (self.d[(k, k)], self.d[(k + 1, k)], self.d[(k + 1, k + 1)]);
+                let det = e11 * e22 - e21 * e21;
+                if det == T::ZERO {
+                    return None;
+                }
+                let (z1, z2) = (z[k], z[k + 1]);
+                z[k] = (e22 * z1 - e21 * z2) / det;
+                z[k + 1] = (e11 * z2 - e21 * z1) / det;
+                k += 2;
+            } else {
+                if self.d[(k, k)] == T::ZERO {
+                    return None;
+                }
+                z[k] /= self.d[(k, k)];
+                k += 1;
+            }
+        }
+        
+        for i in (0..N).rev() {
+            let update = ((i + 1)..N).map(|j| self.l[(j, i)] * z[j]).sum::<T>();
+            z[i] -= update;
+        }
+        
+        let mut x = Vector::<N, T>::zeroed();
+        (0..N).for_each(|i| x[self.permutation[i]] = z[i]);
+        Some(x)
+    }
+}
+
+impl<const N: usize, T: Float> std::fmt::Debug for Ldlt<N, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Ldlt")
+            .field("l", &self.l)
+            .field("d", &self.d)
+            .field("permutation", &self.permutation)
+            .finish()
+    }
+}
+
+/// LDL^T decomposition of a symmetric A with Bunch-Kaufman pivoting,
+/// choosing at each step, with alpha = (1 + sqrt(17)) / 8,
+/// lambda = max_{i>k} |a_{ik}| at row r and sigma = max_{j>=k, j!=r} |a_{jr}|:
+/// 
+/// ```text
+/// 1 x 1 pivot a_{kk}       if |a_{kk}| >= alpha lambda or |a_{kk}| sigma >= alpha lambda^2
+/// 1 x 1 pivot a_{rr}       else if |a_{rr}| >= alpha sigma
+/// 2 x 2 pivot at k, r      otherwise
+/// ```
+/// 
+/// Only the lower triangular part of A is read.
+pub fn ldlt<const N: usize, T: Float>(a: &Matrix<N, N, T>) -> Ldlt<N, T> {
+    let alpha = (T::ONE + T::from_f64(17.0).sqrt()) / T::from_f64(8.0);
+    
+    let mut a = Matrix::<N, N, T>::from_fn(|i, j| if i >= j { a[(i, j)] } else { a[(j, i)] });
+    let mut l = Matrix::<N, N, T>::identity();
+    let mut d = Matrix::<N, N, T>::zeroed();
+    let mut permutation = std::array::from_fn(|i| i);
+    
+    // swap the k-th and r-th rows and columns of the active submatrix,
+    // together with the already computed part of L
+    let mut swap = |a: &mut Matrix<N, N, T>, l: &mut Matrix<N, N, T>, k: usize, r: usize| {
+        if k != r {
+            a.swap_rows(k, r);
+            a.swap_columns(k, r);
+            (0..k).for_each(|j| {
+                let l_kj = l[(k, j)];
+                l[(k, j)] = l[(r, j)];
+                l[(r, j)] = l_kj;
+            });
+            permutation.swap(k, r);
+        }
+    };
+    
+    let mut k = 0;
+    while k < N {
+        let (r, lambda) = ((k + 1)..N)
+            .map(|i| (i, a[(i, k)].abs()))
+            .fold((k, T::ZERO), |max, it| if it.1 > max.1 { it } else { max });
+        
+        let is_2x2 = if lambda == T::ZERO || a[(k, k)].abs() >= alpha * lambda {
+            false
+        } else {
+            let sigma = (k..N).filter(|&j| j != r).map(|j| a[(j, r)].abs()).fold(T::ZERO, T::max);
+            if a[(k, k)].abs() * sigma >= alpha * lambda * lambda {
+                false
+            } else if a[(r, r)].abs() >= alpha * sigma {
+                swap(&mut a, &mut l, k, r);
+                false
+            } else {
+                swap(&mut a, &mut l, k + 1, r);
+                true
+            }
+        };
+        
+        if is_2x2 {
+            let (e11, e21, e22) = (a[(k, k)], a[(k + 1, k)], a[(k + 1, k + 1)]);
+            let det = e11 * e22 - e21 * e21;
+            for i in (k + 2)..N {
+                l[(i, k)] = (a[(i, k)] * e22 - a[(i, k + 1)] * e21) / det;
+                l[(i, k + 1)] = (a[(i, k + 1)] * e11 - a[(i, k)] * e21) / det;
+            }
+            for j in (k + 2)..N {
+                for i in j..N {
+                    let update = l[(i, k)] * a[(j, k)] + l[(i, k + 1)] * a[(j, k + 1)];
+                    a[(i, j)] -= update;
+                    a[(j, i)] = a[(i, j)];
+                }
+            }
+            d[(k, k)] = e11;
+            d[(k + 1, k)] = e21;
+            d[(k, k + 1)] = e21;
+            d[(k + 1, k + 1)] = e22;
+            k += 2;
+        } else {
+            let pivot = a[(k, k)];
+            if pivot != T::ZERO {
+                ((k + 1)..N).for_each(|i| l[(i, k)] = a[(i, k)] / pivot);
+            }
+            for j in (k + 1)..N {
+                for i in j..N {
+                    let update = l[(i, k)] * a[(j, k)];
+                    a[(i, j)] -= update;
+                    a[(j, i)] = a[(i, j)];
+                }
+            }
+            d[(k, k)] = pivot;
+            k += 1;
+        }
+    }
+    
+    Ldlt { l: LowerTriangular::from_lower_part(l), d, permutation }
+}
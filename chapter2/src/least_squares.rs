@@ -0,0 +1,88 @@
+use crate::{Float, Matrix, Vector, NotPositiveDefinite};
+use crate::{cholesky, forward_substitution, back_substitution, HouseholderQr};
+
+/// Least squares solution x minimizing ||b - Ax||_2 for a full column rank A (N >= M)
+/// by the normal equations and the Cholesky decomposition A^T A = L L^T:
+/// 
+/// ```text
+/// A^T A x = A^T b,  L y = A^T b,  L^T x = y
+/// ```
+/// 
+/// cheap, but squaring the condition number as κ(A^T A) = κ(A)^2.
+/// Fails if A^T A is not numerically positive definite, i.e. A is (nearly) rank deficient.
+pub fn solve_least_squares_normal<const N: usize, const M: usize, T: Float>(
+    a: &Matrix<N, M, T>,
+    b: &Vector<N, T>,
+) -> Result<Vector<M, T>, NotPositiveDefinite> {
+    let a_t = a.transpose();
+    let l = cholesky(&(&a_t * a))?;
+    let y = forward_substitution(&l, &(&a_t * b));
+    Ok(back_substitution(&l.transpose(), &y))
+}
+
+/// Least squares solution x minimizing ||b - Ax||_2 for a full column rank A (N >= M)
+/// by `HouseholderQr::solve_least_squares`, working on A itself with κ(R) = κ(A)
+/// instead of squaring it, or `None` if A is (numerically) rank deficient
+pub fn solve_least_squares_qr<const N: usize, const M: usize, T: Float>(
+    a: &Matrix<N, M, T>,
+    b: &Vector<N, T>,
+) -> Option<Vector<M, T>> {
+    HouseholderQr::new(a).solve_least_squares(b)
+}
+
+/// Tikhonov-regularized solution x minimizing ||b - Ax||_2^2 + lambda ||x||_2^2,
+/// i.e. solving the regularized normal equations
+/// 
+/// ```text
+/// (A^T A + lambda I) x = A^T b
+/// ```
+/// 
+/// by the Cholesky decomposition, trading the residual for a smaller ||x|| to suppress
+/// the noise amplified by an ill-conditioned A. Any lambda > 0 makes the system positive definite.
+pub fn solve_tikhonov<const N: usize, const M: usize, T: Float>(
+    a: &Matrix<N, M, T>,
+    b: &Vector<N, T>,
+    lambda: T,
+) -> Result<Vector<M, T>, NotPositiveDefinite> {
+    let a_t = a.transpose();
+    let mut normal = &a_t * a;
+    (0..M).for_each(|i| normal[(i, i)] += lambda);
+    let l = cholesky(&normal)?;
+    let y = forward_substitution(&l, &(&a_t * b));
+    Ok(back_substitution(&l.transpose(), &y))
+}
+
+/// Minimum 2-norm solution of an underdetermined Ax = b (N < M) for a full row rank A
+/// by the QR decomposition A^T = Q R, as x = Q z lies in the row space of A when
+/// 
+/// ```text
+/// R_1^T z_{0..N} = b,  z_{N..M} = 0
+/// ```
+/// 
+/// with R_1 the upper N x N block of R, or `None` if R_1 is singular (A is rank deficient)
+pub fn solve_minimum_norm<const N: usize, const M: usize, T: Float>(
+    a: &Matrix<N, M, T>,
+    b: &Vector<N, T>,
+) -> Option<Vector<M, T>> {
+    assert!(N <= M, "Minimum-norm problem must not be overdetermined");
+    let qr = HouseholderQr::new(&a.transpose());
+    let r = qr.r();
+    let mut z = Vector::<M, T>::zeroed();
+    for i in 0..N {
+        if r[(i, i)].abs() <= T::TOLERANCE {
+            return None;
+        }
+        let sum = (0..i).map(|j| r[(j, i)] * z[j]).sum::<T>();
+        z[i] = (b[i] - sum) / r[(i, i)];
+    }
+    Some(qr.apply_q(&z))
+}
+
+/// `solve_least_squares_qr` if A is overdetermined or square (N >= M),
+/// and `solve_minimum_norm` if underdetermined (N < M)
+pub fn solve_least_squares<const N: usize, const M: usize, T: Float>(
+    a: &Matrix<N, M, T>,
+    b: &Vector<N, T>,
+) -> Option<Vector<M, T>> {
+    if N >= M { solve_least_squares_qr(a, b) } else { solve_minimum_norm(a, b) }
+}
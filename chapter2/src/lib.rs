@@ -2,35 +2,110 @@
 #![feature(generic_const_exprs)]
 
 mod matrix;
+mod dyn_matrix;
+mod sparse;
+mod banded;
+mod symmetric;
+mod diagonal;
+mod triangular;
 mod plotter;
+mod scalar;
+mod double_double;
+mod alloc;
+mod flops;
+mod cholesky;
+mod ldlt;
+mod qr;
+mod tridiagonalization;
+mod preconditioner;
+mod polar;
+mod least_squares;
+mod gauss_jordan;
+mod iterative;
+mod eigen;
+mod experiment;
+mod distribution;
+mod random;
 
-pub use matrix::{Matrix, Vector};
-pub use plotter::Plotter;
+pub mod test_matrices;
+pub mod lu;
+
+pub use matrix::{Matrix, Vector, MatrixView, MatrixViewMut};
+pub use dyn_matrix::{DynMatrix, DynVector};
+pub use sparse::{SparseMatrix, TripletBuilder};
+pub use banded::{BandedMatrix, thomas};
+pub use symmetric::SymmetricMatrix;
+pub use diagonal::DiagonalMatrix;
+pub use triangular::{LowerTriangular, UpperTriangular};
+pub use plotter::{Plotter, CurvePlotter, ConvergencePlotter};
+pub use scalar::{Float, Scalar, Complex, Complex32, Complex64};
+pub use double_double::DoubleDouble;
+pub use alloc::CountingAllocator;
+pub use flops::count_flops;
+pub use cholesky::{cholesky, NotPositiveDefinite};
+pub use ldlt::{ldlt, Ldlt};
+pub use qr::{qr_householder, HouseholderQr, qr_classical_gram_schmidt, qr_modified_gram_schmidt, GivensRotation, GivensQr};
+pub use tridiagonalization::tridiagonalize;
+pub use preconditioner::{Preconditioner, NoPreconditioner, Ilu0, Ic0, Ssor};
+pub use polar::polar;
+pub use least_squares::{solve_least_squares, solve_least_squares_normal, solve_least_squares_qr, solve_minimum_norm, solve_tikhonov};
+pub use gauss_jordan::{gauss_jordan, gauss_jordan_with_inverse};
+pub use iterative::{IterativeSolution, IterativeSolve, StoppingCriterion, Richardson, Jacobi, GaussSeidel, Sor, Pcg, Gmres, richardson, jacobi, gauss_seidel, sor, pcg, gmres};
+pub use eigen::{shifted_inverse_iteration, power_iteration, aitken_power_iteration, AitkenPowerIteration, deflated_power_iteration, subspace_iteration, SubspaceIteration, qr_algorithm, symmetric_qr_algorithm, jacobi_eigenvalue_method};
+pub use eigen::{lanczos, Lanczos, RitzPairs, arnoldi, Arnoldi};
+pub use eigen::{sturm_count, gershgorin_interval, bisection_eigenvalues};
+pub use eigen::{spectral_radius_estimate, norm2_estimate};
+pub use eigen::{EigenvalueOrder, sort_eigenpairs, match_eigenpairs};
+pub use experiment::{Benchmark, Timing, ExperimentConfig, ExperimentRecord, ExperimentStat, ExperimentStats, ExperimentSummary, MetricSummary, ScalingStudy, TrialOutcome, check_deadline};
+pub use random::seed_rng;
+pub use distribution::{MatrixDistribution, Uniform, Gaussian, Spd, DiagonallyDominant, Orthogonal, Symmetrized, WithSpectrum, WithConditionNumber, SingularValueProfile};
 
 pub const EPSILON: f64 = 1e-10;
 
+/// Assert that two matrices of the same size are equal entry by entry
+/// up to the tolerance (`EPSILON` by default).
+/// 
+/// ```text
+/// assert_matrix_approx_eq!(a, b);
+/// assert_matrix_approx_eq!(a, b, 1e-6);
+/// ```
+#[macro_export]
+macro_rules! assert_matrix_approx_eq {
+    ($left:expr, $right:expr $(,)?) => {
+        $crate::assert_matrix_approx_eq!($left, $right, $crate::EPSILON)
+    };
+    ($left:expr, $right:expr, $tolerance:expr $(,)?) => {
+        match (&$left, &$right, $tolerance) {
+            (left, right, tolerance) => {
+                if let Some((i, j)) = left.first_approx_mismatch(right, tolerance) {
+                    panic!(
+                        "matrices differ at ({i}, {j}): {} vs {} (tolerance: {})\n left: {:?}\nright: {:?}",
+                        left[(i, j)], right[(i, j)], tolerance, left, right,
+                    );
+                }
+            }
+        }
+    };
+}
+
 /// Solve Ay = b by forward substitution:
 /// 
 /// ```text
 /// y_i = (b_i - sum_{j=0}^{i-1} a_{ij} y_j) / a_{ii}
 /// for i = 0, 1, ..., N-1
 /// ```
-pub fn forward_substitution<const N: usize>(
-    lower_triangular_matrix: &Matrix<N, N>,
-    b: &Vector<N>,
-) -> Vector<N> {
-    assert!(
-        (0..N).all(|i| lower_triangular_matrix.column(i).take(i).all(|x| x.abs() < EPSILON)),
-        "Matrix is not lower triangular"
-    );
-    
-    let mut y = Vector::<N>::zeroed();
+pub fn forward_substitution<const N: usize, T: Scalar>(
+    lower_triangular_matrix: &LowerTriangular<N, T>,
+    b: &Vector<N, T>,
+) -> Vector<N, T> {
+    let mut y = Vector::<N, T>::zeroed();
     for i in 0..N {
-        let mut sum = 0.0;
+        let mut sum = T::ZERO;
         for j in 0..i {
             sum += lower_triangular_matrix[(i, j)] * y[j];
         }
         y[i] = (b[i] - sum) / lower_triangular_matrix[(i, i)];
+        count_flops(2 * i + 2);
     }
     y
 }
@@ -41,31 +116,22 @@ pub fn forward_substitution<const N: usize>(
 /// x_i = (b_i - sum_{j=i+1}^{n} a_{ij} x_j) / a_{ii}
 /// for i = N-1, N-2, ..., 0
 /// ```
-pub fn back_substitution<const N: usize>(
-    upper_triangular_matrix: &Matrix<N, N>,
-    b: &Vector<N>,
-) -> Vector<N> {
-    assert!(
-        (0..N).all(|i| upper_triangular_matrix.column(i).skip(i + 1).all(|x| x.abs() < EPSILON)),
-        "Matrix is not upper triangular"
-    );
-    
-    let mut x = Vector::<N>::zeroed();
+pub fn back_substitution<const N: usize, T: Scalar>(
+    upper_triangular_matrix: &UpperTriangular<N, T>,
+    b: &Vector<N, T>,
+) -> Vector<N, T> {
+    let mut x = Vector::<N, T>::zeroed();
     for i in (0..N).rev() {
-        let mut sum = 0.0;
+        let mut sum = T::ZERO;
         for j in (i + 1)..N {
             sum += upper_triangular_matrix[(i, j)] * x[j];
         }
         x[i] = (b[i] - sum) / upper_triangular_matrix[(i, i)];
+        count_flops(2 * (N - 1 - i) + 2);
     }
     x
 }
 
-fn random_value() -> f64 {
-    use rand::{Rng, rng};
-    rng().random_range(-1.0..=1.0)
-}
-
 fn with_elapsed<F, R>(f: F) -> (R, std::time::Duration)
 where
     F: FnOnce() -> R,
@@ -76,70 +142,403 @@ where
     (result, elapsed)
 }
 
+/// Why a solver of `EquationSolver::new_fallible` gave up on an instance,
+/// recorded as a failed trial by `try_experiments` instead of panicking
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SolveError {
+    /// no nonzero pivot (above `Float::TOLERANCE`) was found
+    Singular,
+    NotPositiveDefinite(NotPositiveDefinite),
+    /// the iteration didn't meet its criterion within `iteration_count` steps
+    NotConverged { iteration_count: usize },
+}
+
+impl std::fmt::Display for SolveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Singular => write!(f, "Matrix is singular"),
+            Self::NotPositiveDefinite(error) => error.fmt(f),
+            Self::NotConverged { iteration_count } => write!(f, "not converged within {iteration_count} iterations"),
+        }
+    }
+}
+
+impl std::error::Error for SolveError {}
+
+impl From<NotPositiveDefinite> for SolveError {
+    fn from(error: NotPositiveDefinite) -> Self {
+        Self::NotPositiveDefinite(error)
+    }
+}
+
+/// `(A, b) -> x` solving `Ax = b`
+type Solve<const N: usize> = Box<dyn Fn(&Matrix<N, N>, &Vector<N>) -> Result<Vector<N>, SolveError> + Send + Sync>;
+
+/// `b -> x` by a factorization of A
+type FactorizedSolve<const N: usize> = Box<dyn Fn(&Vector<N>) -> Vector<N>>;
+
+/// `A -> (b -> x)`, factorizing A once to solve `Ax = b` for many b
+type Factorize<const N: usize> = Box<dyn Fn(&Matrix<N, N>) -> Result<FactorizedSolve<N>, SolveError> + Send + Sync>;
+
 pub struct EquationSolver<const N: usize> {
-    f: fn(&Matrix<N, N>, &Vector<N>) -> Vector<N>,
+    f: EquationSolverFn<N>,
+    max_refinements: usize,
+    benchmark: Benchmark,
+}
+
+enum EquationSolverFn<const N: usize> {
+    Direct(Solve<N>),
+    Factorized(Factorize<N>),
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct EquationExperimentStat<const N: usize> {
     pub solution: Vector<N>,
+    /// the median of the repetitions by `with_benchmark`, of the single shot by default
     pub elapsed: std::time::Duration,
+    /// max - min of the repetitions by `with_benchmark`, 0 for the single shot
+    pub elapsed_spread: std::time::Duration,
     pub reference_solution: Vector<N>,
     pub residual_norm: f64,
     pub relative_error: f64,
+    /// 2-norm condition number of the random instance
+    pub condition_number: f64,
+    /// peak bytes allocated during the solve,
+    /// `None` unless `CountingAllocator` is the global allocator
+    pub peak_memory_bytes: Option<usize>,
+    /// allocations during the solve, `None` unless `CountingAllocator` is the global allocator
+    pub allocation_count: Option<usize>,
+    /// flops counted during the solve (see `count_flops`), `None` without the `flop-count` feature
+    pub flop_count: Option<u64>,
+    /// correction steps of the iterative refinement (0 without `with_refinement`)
+    pub refinement_count: usize,
+}
+
+impl<const N: usize> ExperimentStat for EquationExperimentStat<N> {
+    fn metrics(&self) -> Vec<(&'static str, f64)> {
+        let mut metrics = vec![
+            ("residual_norm", self.residual_norm),
+            ("relative_error", self.relative_error),
+            ("condition_number", self.condition_number),
+            ("elapsed_secs", self.elapsed.as_secs_f64()),
+            ("elapsed_spread_secs", self.elapsed_spread.as_secs_f64()),
+            ("refinement_count", self.refinement_count as f64),
+        ];
+        if let Some(peak_memory_bytes) = self.peak_memory_bytes {
+            metrics.push(("peak_memory_bytes", peak_memory_bytes as f64));
+        }
+        if let Some(allocation_count) = self.allocation_count {
+            metrics.push(("allocation_count", allocation_count as f64));
+        }
+        if let Some(flop_count) = self.flop_count {
+            metrics.push(("flop_count", flop_count as f64));
+        }
+        metrics
+    }
 }
 
 impl<const N: usize> EquationSolver<N> {
-    /// `f: (A, b) -> x` should solve the equation `Ax = b`
+    /// `f: (A, b) -> x` should solve the equation `Ax = b`, capturing its parameters if any,
+    /// e.g. `move |a, b| gaussian_elimination(a, b, pivoting).unwrap()`
     pub fn new(
-        f: fn(&Matrix<N, N>, &Vector<N>) -> Vector<N>,
+        f: impl Fn(&Matrix<N, N>, &Vector<N>) -> Vector<N> + Send + Sync + 'static,
     ) -> Self {
-        Self { f }
+        Self::new_fallible(move |a, b| Ok(f(a, b)))
+    }
+    
+    /// `f: (A, b) -> x` reporting the instances it can't solve by `SolveError`,
+    /// e.g. `|a, b| lu_decomposition(a).map(|lu| lu.solve(b)).ok_or(SolveError::Singular)`
+    pub fn new_fallible(
+        f: impl Fn(&Matrix<N, N>, &Vector<N>) -> Result<Vector<N>, SolveError> + Send + Sync + 'static,
+    ) -> Self {
+        Self { f: EquationSolverFn::Direct(Box::new(f)), max_refinements: 0, benchmark: Benchmark::default() }
+    }
+    
+    /// `factorize: A -> (b -> x)` should factorize A once and return
+    /// the solver of `Ax = b` for each b, reused by `solve_matrix` for all the columns
+    pub fn new_factorized(
+        factorize: impl Fn(&Matrix<N, N>) -> Box<dyn Fn(&Vector<N>) -> Vector<N>> + Send + Sync + 'static,
+    ) -> Self {
+        Self::new_factorized_fallible(move |a| Ok(factorize(a)))
+    }
+    
+    /// `new_factorized` reporting the A it can't factorize by `SolveError`
+    pub fn new_factorized_fallible(
+        factorize: impl Fn(&Matrix<N, N>) -> Result<Box<dyn Fn(&Vector<N>) -> Vector<N>>, SolveError> + Send + Sync + 'static,
+    ) -> Self {
+        Self { f: EquationSolverFn::Factorized(Box::new(factorize)), max_refinements: 0, benchmark: Benchmark::default() }
+    }
+    
+    /// Follow each solve by at most `max_refinements` steps of the iterative refinement
+    /// 
+    /// ```text
+    /// r = b - A x,  A d = r,  x <- x + d
+    /// ```
+    /// 
+    /// solving for d by this solver again (reusing the factorization if `new_factorized`)
+    pub fn with_refinement(self, max_refinements: usize) -> Self {
+        Self { max_refinements, ..self }
+    }
+    
+    /// Time each solve of the experiments by `benchmark` instead of a single shot,
+    /// reporting the median in `elapsed` and the spread in `elapsed_spread`
+    pub fn with_benchmark(self, benchmark: Benchmark) -> Self {
+        Self { benchmark, ..self }
+    }
+    
+    /// Solve `Ax = b` by `lu::lu_decomposition`, factorized once for multiple right-hand sides
+    pub fn new_lu() -> Self {
+        Self::new_factorized_fallible(|a| {
+            let lu = lu::lu_decomposition(a).ok_or(SolveError::Singular)?;
+            Ok(Box::new(move |b| lu.solve(b)))
+        })
+    }
+    
+    /// Solve `Ax = b` by `lu::lu_decomposition` in `f32` and the iterative refinement in `f64`
+    /// with at most `max_refinements` steps (see `lu::solve_mixed_precision`)
+    pub fn new_mixed_precision(max_refinements: usize) -> Self {
+        Self::new_factorized_fallible(|a| {
+            let lu = lu::lu_decomposition(&a.cast::<f32>()).ok_or(SolveError::Singular)?;
+            Ok(Box::new(move |b| lu.solve(&b.cast()).cast()))
+        }).with_refinement(max_refinements)
+    }
+    
+    /// Solve `Ax = b` for a symmetric positive definite A by
+    /// the Cholesky decomposition A = L L^T and two triangular solves:
+    /// 
+    /// ```text
+    /// L y = b,  L^T x = y
+    /// ```
+    /// 
+    /// (to be experimented by `experiment_randomly_spd`)
+    pub fn new_cholesky() -> Self {
+        Self::new_factorized_fallible(|a| {
+            let l = cholesky(a)?;
+            let l_t = l.transpose();
+            Ok(Box::new(move |b| back_substitution(&l_t, &forward_substitution(&l, b))))
+        })
     }
     
     /// A reference implementation for solving the equation `Ax = b`
     /// using nalgebra's LU decomposition.
+    #[cfg(not(feature = "extended-reference"))]
     fn new_reference() -> Self {
-        Self {
-            f: |a: &Matrix<N, N>, b: &Vector<N>| -> Vector<N> {
-                let view = nalgebra::DMatrix::from_fn(N, N, |i, j| a[(i, j)])
-                    .lu()
-                    .solve(&nalgebra::DVector::from_column_slice(b.as_ref()))
-                    .unwrap();
-                Vector::<N>::try_from(view.as_slice()).unwrap()
+        Self::new(|a: &Matrix<N, N>, b: &Vector<N>| -> Vector<N> {
+            let view = nalgebra::DMatrix::from_column_slice(N, N, a.as_slice())
+                .lu()
+                .solve(&nalgebra::DVector::from_column_slice(b.as_ref()))
+                .unwrap();
+            Vector::<N>::try_from(view.as_slice()).unwrap()
+        })
+    }
+    
+    /// A reference implementation for solving the equation `Ax = b`
+    /// using LU decomposition in double-double precision,
+    /// so that the relative error of an `f64` solver is not
+    /// hidden by the rounding errors of the reference itself.
+    #[cfg(feature = "extended-reference")]
+    fn new_reference() -> Self {
+        Self::new(|a: &Matrix<N, N>, b: &Vector<N>| -> Vector<N> {
+            a.cast::<DoubleDouble>()
+                .solve(&b.cast())
+                .expect("Matrix is singular")
+                .cast()
+        })
+    }
+    
+    /// panicking by the `SolveError` if any
+    pub fn solve(&self, a: &Matrix<N, N>, b: &Vector<N>) -> Vector<N> {
+        self.try_solve(a, b).unwrap_or_else(|error| panic!("{error}"))
+    }
+    
+    pub fn try_solve(&self, a: &Matrix<N, N>, b: &Vector<N>) -> Result<Vector<N>, SolveError> {
+        self.solve_refined(a, b).map(|(solution, _)| solution)
+    }
+    
+    /// solution and the number of refinement steps taken
+    fn solve_refined(&self, a: &Matrix<N, N>, b: &Vector<N>) -> Result<(Vector<N>, usize), SolveError> {
+        match &self.f {
+            // a failed correction as zero, ending the refinement
+            EquationSolverFn::Direct(f) => Ok(lu::refine(a, b, f(a, b)?, |r| f(a, r).unwrap_or_else(|_| Vector::zeroed()), self.max_refinements)),
+            EquationSolverFn::Factorized(factorize) => {
+                let solve = factorize(a)?;
+                Ok(lu::refine(a, b, solve(b), solve, self.max_refinements))
             }
         }
     }
     
-    pub fn solve(&self, a: &Matrix<N, N>, b: &Vector<N>) -> Vector<N> {
-        (self.f)(a, b)
+    /// Solve `AX = B` column by column, reusing one factorization for all the K columns
+    /// if constructed by `new_factorized` (and solving each column from scratch otherwise),
+    /// panicking by the `SolveError` if any
+    pub fn solve_matrix<const K: usize>(&self, a: &Matrix<N, N>, b: &Matrix<N, K>) -> Matrix<N, K> {
+        self.try_solve_matrix(a, b).unwrap_or_else(|error| panic!("{error}"))
     }
-
+    
+    pub fn try_solve_matrix<const K: usize>(&self, a: &Matrix<N, N>, b: &Matrix<N, K>) -> Result<Matrix<N, K>, SolveError> {
+        let mut x = Matrix::<N, K>::zeroed();
+        match &self.f {
+            EquationSolverFn::Direct(_) => for j in 0..K {
+                x.set_column(j, &self.try_solve(a, &b.column_vector(j))?);
+            }
+            EquationSolverFn::Factorized(factorize) => {
+                let solve = factorize(a)?;
+                (0..K).for_each(|j| {
+                    let b_j = b.column_vector(j);
+                    x.set_column(j, &lu::refine(a, &b_j, solve(&b_j), &solve, self.max_refinements).0);
+                });
+            }
+        }
+        Ok(x)
+    }
+    
+    /// A^{-1} by `solve_matrix` with B = I
+    pub fn inverse(&self, a: &Matrix<N, N>) -> Matrix<N, N> {
+        self.try_inverse(a).unwrap_or_else(|error| panic!("{error}"))
+    }
+    
+    pub fn try_inverse(&self, a: &Matrix<N, N>) -> Result<Matrix<N, N>, SolveError> {
+        self.try_solve_matrix(a, &Matrix::<N, N>::identity())
+    }
+    
+    /// `experiment_randomly` for `config.trials` times
+    pub fn experiments(&self, config: &ExperimentConfig) -> Vec<EquationExperimentStat<N>> {
+        config.run(|| self.experiment_randomly())
+    }
+    
+    /// `experiments` recording the trials that panicked or timed out (see `ExperimentConfig::try_run`),
+    /// and those of a `SolveError` as failed without panicking, whose rate is `ExperimentSummary::failure_rate`
+    pub fn try_experiments(&self, config: &ExperimentConfig) -> Vec<TrialOutcome<EquationExperimentStat<N>>> {
+        config.try_run(|| self.try_experiment_randomly()).into_iter().map(TrialOutcome::flatten).collect()
+    }
+    
+    fn try_experiment_randomly(&self) -> Result<EquationExperimentStat<N>, SolveError> {
+        self.try_experiment_on(&Uniform::default().sample(), &Vector::<N>::random_uniform(-1.0, 1.0))
+    }
+    
     pub fn experiment_randomly(&self) -> EquationExperimentStat<N> {
-        let a = Matrix::<N, N>::from_fn(|_, _| random_value());
-        let b = Vector::<N>::from_fn(|_, _| random_value());    
-        
-        let (solution, elapsed) = with_elapsed(|| self.solve(&a, &b));
-        let reference_solution = Self::new_reference().solve(&a, &b);
+        self.experiment_randomly_from(&Uniform::default())
+    }
+    
+    /// `experiment_on` each of the named `solvers` on the same `trials` random instances
+    /// of `experiment_randomly`, paired so that the i-th stats of all the solvers are on the i-th instance,
+    /// to compare them without the noise of different instances
+    pub fn compare<'s>(solvers: &[(&'s str, EquationSolver<N>)], trials: usize) -> Vec<(&'s str, Vec<EquationExperimentStat<N>>)> {
+        let mut stats = solvers.iter().map(|&(name, _)| (name, Vec::with_capacity(trials))).collect::<Vec<_>>();
+        for _ in 0..trials {
+            let a = Uniform::default().sample();
+            let b = Vector::<N>::random_uniform(-1.0, 1.0);
+            for ((_, solver), (_, stats)) in solvers.iter().zip(&mut stats) {
+                stats.push(solver.experiment_on(&a, &b));
+            }
+        }
+        stats
+    }
+    
+    /// `experiment_randomly` with a symmetric positive definite A by `Matrix::random_spd`,
+    /// for solvers requiring it like `new_cholesky`
+    pub fn experiment_randomly_spd(&self) -> EquationExperimentStat<N> {
+        self.experiment_randomly_from(&Spd)
+    }
+    
+    /// with A from `distribution`, e.g. `WithConditionNumber` for the ill-conditioned cases,
+    /// and b uniformly random in [-1, 1]
+    pub fn experiment_randomly_from(&self, distribution: &impl MatrixDistribution<N>) -> EquationExperimentStat<N> {
+        let a = distribution.sample();
+        let b = Vector::<N>::random_uniform(-1.0, 1.0);
+        self.experiment_on(&a, &b)
+    }
+    
+    /// the same stats for the given instance, e.g. of `test_matrices` or loaded from a file,
+    /// panicking by the `SolveError` if any
+    pub fn experiment_on(&self, a: &Matrix<N, N>, b: &Vector<N>) -> EquationExperimentStat<N> {
+        self.try_experiment_on(a, b).unwrap_or_else(|error| panic!("{error}"))
+    }
+    
+    pub fn try_experiment_on(&self, a: &Matrix<N, N>, b: &Vector<N>) -> Result<EquationExperimentStat<N>, SolveError> {
+        let (solved, timing) = self.benchmark.run(|| self.solve_refined(a, b));
+        let (solution, refinement_count) = solved?;
+        // the allocations and the flops of one more untimed run, not to time the counting
+        let ((_, flop_count), allocations) = alloc::with_allocations(|| flops::with_flops(|| self.solve_refined(a, b)));
+        let reference_solution = Self::new_reference().solve(a, b);
         
         let residual_norm = (b - a * &solution).norm();
         let relative_error = (&solution - &reference_solution).norm() / reference_solution.norm();
+        let condition_number = a.condition_number();
         
-        EquationExperimentStat {
+        Ok(EquationExperimentStat {
             solution,
             reference_solution,
-            elapsed,
+            elapsed: timing.median,
+            elapsed_spread: timing.spread(),
             residual_norm,
             relative_error,
-        }
+            condition_number,
+            peak_memory_bytes: allocations.as_ref().map(|allocations| allocations.peak_bytes),
+            allocation_count: allocations.map(|allocations| allocations.count),
+            flop_count,
+            refinement_count,
+        })
     }
 }
 
 pub struct DominantEigenvalueSolver<const N: usize> {
-    f: fn(&Matrix<N, N>) -> DominantEigenvalueSolution<N>,
+    f: Box<dyn Fn(&Matrix<N, N>) -> DominantEigenvalueSolution<N> + Send + Sync>,
+    reference: EigenReference,
+}
+
+/// What `DominantEigenvalueSolver` compares the dominant eigenpair (λ_1, x_1) with
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EigenReference {
+    /// the largest singular value σ_1 = |λ_1| and its left singular vector by nalgebra's SVD,
+    /// agreeing with (|λ_1|, ±x_1) only for a symmetric A
+    SingularValue,
+    /// nalgebra's symmetric eigendecomposition, only for a symmetric A
+    SymmetricEigen,
+    /// the dominant one among nalgebra's complex eigenvalues (by the Schur decomposition)
+    /// of a general A, which must be real, and the null vector of A - λ_1 I by the SVD
+    Eigen,
+}
+
+impl EigenReference {
+    /// (λ_1, x_1) of A with ||x_1|| = 1
+    fn dominant_eigenpair<const N: usize>(self, a: &Matrix<N, N>) -> (f64, Vector<N>) {
+        let a = nalgebra::DMatrix::from_column_slice(N, N, a.as_slice());
+        match self {
+            Self::SingularValue => {
+                let svd = a.svd(true, false);
+                let largest_singular_value = svd
+                    .singular_values
+                    .get(0)
+                    .expect("Matrix is singular")
+                    .to_owned();
+                let its_singular_vector = Vector::<N>::try_from(
+                    svd.u
+                        .unwrap()
+                        .column(0)
+                        .as_slice()
+                ).unwrap();
+                (largest_singular_value, its_singular_vector)
+            }
+            Self::SymmetricEigen => {
+                let eigen = a.symmetric_eigen();
+                let j = eigen.eigenvalues.iamax();
+                (eigen.eigenvalues[j], Vector::<N>::try_from(eigen.eigenvectors.column(j).as_slice()).unwrap())
+            }
+            Self::Eigen => {
+                let eigenvalues = a.complex_eigenvalues();
+                let lambda = eigenvalues.iter().max_by(|x, y| x.norm().total_cmp(&y.norm())).expect("Matrix is empty");
+                assert!(lambda.im.abs() <= EPSILON * lambda.norm(), "Dominant eigenvalue is not real");
+                let svd = (a - nalgebra::DMatrix::identity(N, N) * lambda.re).svd(false, true);
+                let j = svd.singular_values.imin();
+                (lambda.re, Vector::<N>::try_from(svd.v_t.unwrap().row(j).transpose().as_slice()).unwrap())
+            }
+        }
+    }
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DominantEigenvalueSolution<const N: usize> {
     pub eigenvalue: f64,
     pub eigenvector: Vector<N>,
@@ -147,6 +546,7 @@ pub struct DominantEigenvalueSolution<const N: usize> {
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DominantEigenvalueExperimentStat<const N: usize> {
     pub solution: (f64, Vector<N>),
     pub iteration_count: usize,
@@ -155,66 +555,91 @@ pub struct DominantEigenvalueExperimentStat<const N: usize> {
     pub residual_norm: f64,
     pub eigenvalue_relative_error: f64,
     pub eigenvector_relative_error: f64,
+    /// angle between the eigenvector and the reference one,
+    /// independent of their signs (in [0, π/2])
+    pub eigenvector_angle: f64,
+}
+
+impl<const N: usize> ExperimentStat for DominantEigenvalueExperimentStat<N> {
+    fn metrics(&self) -> Vec<(&'static str, f64)> {
+        vec![
+            ("residual_norm", self.residual_norm),
+            ("eigenvalue_relative_error", self.eigenvalue_relative_error),
+            ("eigenvector_relative_error", self.eigenvector_relative_error),
+            ("eigenvector_angle", self.eigenvector_angle),
+            ("iteration_count", self.iteration_count as f64),
+            ("elapsed_secs", self.elapsed.as_secs_f64()),
+        ]
+    }
 }
 
 impl<const N: usize> DominantEigenvalueSolver<N> {
-    /// `f: A -> (λ, x)` should find the first eigenvalue λ and its eigenvector x of A
-    pub fn new(f: fn(&Matrix<N, N>) -> DominantEigenvalueSolution<N>) -> Self {
-        Self { f }
+    /// `f: A -> (λ, x)` should find the first eigenvalue λ and its eigenvector x of A (capturing
+    /// its parameters if any, e.g. the tolerance), compared with `EigenReference::SingularValue`
+    /// for the symmetric `experiment_randomly`
+    pub fn new(f: impl Fn(&Matrix<N, N>) -> DominantEigenvalueSolution<N> + Send + Sync + 'static) -> Self {
+        Self { f: Box::new(f), reference: EigenReference::SingularValue }
     }
     
-    fn new_reference() -> Self {
-        Self {
-            f: |a: &Matrix<N, N>| -> DominantEigenvalueSolution<N> {
-                let svd = nalgebra::DMatrix::from_fn(N, N, |i, j| a[(i, j)])
-                    .svd(true, true);
-                let largest_singular_value = svd
-                    .singular_values
-                    .get(0)
-                    .expect("Matrix is singular")
-                    .to_owned();
-                let its_singular_vector = Vector::<N>::try_from(
-                    svd.u
-                        .unwrap()
-                        .column(0)
-                        .as_slice()
-                ).unwrap();
-                DominantEigenvalueSolution {
-                    eigenvalue: largest_singular_value,
-                    eigenvector: its_singular_vector,
-                    iteration_count: 0,
-                }
-            }
-        }
+    /// Compare with `reference`, e.g. `EigenReference::Eigen` for `experiment_randomly_nonsymmetric`
+    pub fn with_reference(self, reference: EigenReference) -> Self {
+        Self { reference, ..self }
     }
     
     pub fn solve(&self, a: &Matrix<N, N>) -> DominantEigenvalueSolution<N> {
         (self.f)(a)
     }
     
+    /// `experiment_randomly` for `config.trials` times
+    pub fn experiments(&self, config: &ExperimentConfig) -> Vec<DominantEigenvalueExperimentStat<N>> {
+        config.run(|| self.experiment_randomly())
+    }
+    
+    /// `experiments` recording the trials that panicked or timed out (see `ExperimentConfig::try_run`)
+    pub fn try_experiments(&self, config: &ExperimentConfig) -> Vec<TrialOutcome<DominantEigenvalueExperimentStat<N>>> {
+        config.try_run(|| self.experiment_randomly())
+    }
+    
     pub fn experiment_randomly(&self) -> DominantEigenvalueExperimentStat<N> {
-        let a = {
-            let random = Matrix::<N, N>::from_fn(|_, _| random_value());
-            &random + random.transpose() // generate a symmetric matrix to ensure real eigenvalues
-        };
-        
+        self.experiment_randomly_from(&Symmetrized(Uniform::default()))
+    }
+    
+    /// with a non-symmetric A of the entries uniformly random in [0, 1), whose dominant eigenvalue
+    /// is real, simple, and about N / 2 well above the others by the Perron-Frobenius theorem,
+    /// to be compared with `EigenReference::Eigen`
+    pub fn experiment_randomly_nonsymmetric(&self) -> DominantEigenvalueExperimentStat<N> {
+        self.experiment_randomly_from(&Uniform { lo: 0.0, hi: 1.0 })
+    }
+    
+    /// with A from `distribution`, symmetric unless compared with `EigenReference::Eigen`
+    pub fn experiment_randomly_from(&self, distribution: &impl MatrixDistribution<N>) -> DominantEigenvalueExperimentStat<N> {
+        self.experiment_on(&distribution.sample())
+    }
+    
+    /// the same stats for the given A, e.g. of `test_matrices` or loaded from a file
+    pub fn experiment_on(&self, a: &Matrix<N, N>) -> DominantEigenvalueExperimentStat<N> {
         let (DominantEigenvalueSolution {
             eigenvalue,
             eigenvector,
             iteration_count,
-        }, elapsed) = with_elapsed(|| self.solve(&a));
+        }, elapsed) = with_elapsed(|| self.solve(a));
         
         let (reference_eigenvalue, reference_eigenvector) = {
-            let r = Self::new_reference().solve(&a);
+            let (reference_eigenvalue, reference_eigenvector) = self.reference.dominant_eigenpair(a);
             (
-                r.eigenvalue * eigenvalue.signum(),
-                (&r.eigenvector) * (r.eigenvector.dot(&eigenvector).signum()),
+                // only |λ_1| by the singular value
+                if self.reference == EigenReference::SingularValue { reference_eigenvalue * eigenvalue.signum() } else { reference_eigenvalue },
+                (&reference_eigenvector) * (reference_eigenvector.dot(&eigenvector).signum()),
             )
         };
         
         let residual_norm = (eigenvalue * &eigenvector - a * &eigenvector).norm();
         let eigenvalue_relative_error = (eigenvalue - reference_eigenvalue).abs() / reference_eigenvalue.abs();
         let eigenvector_relative_error = (&eigenvector - &reference_eigenvector).norm() / reference_eigenvector.norm();
+        let eigenvector_angle = {
+            let angle = eigenvector.angle_to(&reference_eigenvector);
+            angle.min(std::f64::consts::PI - angle)
+        };
         
         DominantEigenvalueExperimentStat {
             solution: (eigenvalue, eigenvector),
@@ -224,6 +649,546 @@ impl<const N: usize> DominantEigenvalueSolver<N> {
             residual_norm,
             eigenvalue_relative_error,
             eigenvector_relative_error,
+            eigenvector_angle,
+        }
+    }
+}
+
+type PartialEigenSolveFn<const N: usize> = Box<dyn Fn(&Matrix<N, N>, usize) -> Vec<DominantEigenvalueSolution<N>> + Send + Sync>;
+
+pub struct PartialEigenSolver<const N: usize> {
+    f: PartialEigenSolveFn<N>,
+    k: usize,
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PartialEigenExperimentStat<const N: usize> {
+    /// the `k` largest eigenpairs (in modulus) in descending order
+    pub solution: Vec<(f64, Vector<N>)>,
+    pub iteration_counts: Vec<usize>,
+    pub elapsed: std::time::Duration,
+    pub reference_solution: Vec<(f64, Vector<N>)>,
+    /// ||A x_j - λ_j x_j|| for each eigenpair
+    pub residual_norms: Vec<f64>,
+    pub eigenvalue_relative_errors: Vec<f64>,
+    /// angles between the eigenvectors and the reference ones,
+    /// independent of their signs (in [0, π/2])
+    pub eigenvector_angles: Vec<f64>,
+}
+
+/// the worst of the `k` eigenpairs and the total iteration count
+impl<const N: usize> ExperimentStat for PartialEigenExperimentStat<N> {
+    fn metrics(&self) -> Vec<(&'static str, f64)> {
+        let max = |values: &[f64]| values.iter().copied().fold(0.0, f64::max);
+        vec![
+            ("max_residual_norm", max(&self.residual_norms)),
+            ("max_eigenvalue_relative_error", max(&self.eigenvalue_relative_errors)),
+            ("max_eigenvector_angle", max(&self.eigenvector_angles)),
+            ("iteration_count", self.iteration_counts.iter().sum::<usize>() as f64),
+            ("elapsed_secs", self.elapsed.as_secs_f64()),
+        ]
+    }
+}
+
+impl<const N: usize> PartialEigenSolver<N> {
+    /// `f: (A, k) -> [(λ_j, x_j)]` should find the `k` largest eigenpairs (in modulus)
+    /// of a symmetric A in descending order
+    pub fn new(f: impl Fn(&Matrix<N, N>, usize) -> Vec<DominantEigenvalueSolution<N>> + Send + Sync + 'static, k: usize) -> Self {
+        assert!(k <= N, "more eigenpairs than the dimension");
+        Self { f: Box::new(f), k }
+    }
+    
+    /// by `deflated_power_iteration` with the tolerance `EPSILON`
+    pub fn new_deflation(k: usize) -> Self {
+        Self::new(|a, k| deflated_power_iteration(a, k, EPSILON).expect("Power iteration doesn't converge"), k)
+    }
+    
+    /// The `k` largest eigenpairs (in modulus) by nalgebra's symmetric eigendecomposition
+    fn reference(a: &Matrix<N, N>, k: usize) -> Vec<(f64, Vector<N>)> {
+        let eigen = nalgebra::DMatrix::from_column_slice(N, N, a.as_slice()).symmetric_eigen();
+        let mut eigenpairs = (0..N)
+            .map(|j| (eigen.eigenvalues[j], Vector::<N>::try_from(eigen.eigenvectors.column(j).as_slice()).unwrap()))
+            .collect::<Vec<_>>();
+        eigenpairs.sort_by(|(x, _), (y, _)| y.abs().total_cmp(&x.abs()));
+        eigenpairs.truncate(k);
+        eigenpairs
+    }
+    
+    pub fn solve(&self, a: &Matrix<N, N>) -> Vec<DominantEigenvalueSolution<N>> {
+        (self.f)(a, self.k)
+    }
+    
+    /// `experiment_randomly` for `config.trials` times
+    pub fn experiments(&self, config: &ExperimentConfig) -> Vec<PartialEigenExperimentStat<N>> {
+        config.run(|| self.experiment_randomly())
+    }
+    
+    /// `experiments` recording the trials that panicked or timed out (see `ExperimentConfig::try_run`)
+    pub fn try_experiments(&self, config: &ExperimentConfig) -> Vec<TrialOutcome<PartialEigenExperimentStat<N>>> {
+        config.try_run(|| self.experiment_randomly())
+    }
+    
+    /// with a random symmetric A of the eigenvalues N, N - 1, ..., 1 by `Matrix::random_with_spectrum`,
+    /// separated enough for the power iteration to converge
+    pub fn experiment_randomly(&self) -> PartialEigenExperimentStat<N> {
+        self.experiment_randomly_from(&WithSpectrum(DiagonalMatrix::from(Vector::<N>::from_fn(|i, _| (N - i) as f64))))
+    }
+    
+    /// with a symmetric A from `distribution`, as the reference is by the symmetric eigendecomposition
+    pub fn experiment_randomly_from(&self, distribution: &impl MatrixDistribution<N>) -> PartialEigenExperimentStat<N> {
+        self.experiment_on(&distribution.sample())
+    }
+    
+    /// the same stats for the given symmetric A, e.g. of `test_matrices` or loaded from a file
+    pub fn experiment_on(&self, a: &Matrix<N, N>) -> PartialEigenExperimentStat<N> {
+        let (solutions, elapsed) = with_elapsed(|| self.solve(a));
+        let reference_solution = Self::reference(a, self.k);
+        
+        let mut stat = PartialEigenExperimentStat {
+            solution: Vec::with_capacity(self.k),
+            iteration_counts: Vec::with_capacity(self.k),
+            elapsed,
+            reference_solution: Vec::with_capacity(self.k),
+            residual_norms: Vec::with_capacity(self.k),
+            eigenvalue_relative_errors: Vec::with_capacity(self.k),
+            eigenvector_angles: Vec::with_capacity(self.k),
+        };
+        for (solution, (reference_eigenvalue, reference_eigenvector)) in solutions.into_iter().zip(reference_solution) {
+            let DominantEigenvalueSolution { eigenvalue, eigenvector, iteration_count } = solution;
+            let reference_eigenvector = &reference_eigenvector * reference_eigenvector.dot(&eigenvector).signum();
+            
+            stat.residual_norms.push((eigenvalue * &eigenvector - a * &eigenvector).norm());
+            stat.eigenvalue_relative_errors.push((eigenvalue - reference_eigenvalue).abs() / reference_eigenvalue.abs());
+            stat.eigenvector_angles.push({
+                let angle = eigenvector.angle_to(&reference_eigenvector);
+                angle.min(std::f64::consts::PI - angle)
+            });
+            stat.iteration_counts.push(iteration_count);
+            stat.solution.push((eigenvalue, eigenvector));
+            stat.reference_solution.push((reference_eigenvalue, reference_eigenvector));
+        }
+        stat
+    }
+}
+
+pub struct AllEigenvaluesSolver<const N: usize> {
+    f: Box<dyn Fn(&Matrix<N, N>) -> AllEigenvaluesSolution<N> + Send + Sync>,
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AllEigenvaluesSolution<const N: usize> {
+    pub eigenvalues: Vector<N>,
+    /// the eigenvectors as the columns, corresponding to `eigenvalues`
+    pub eigenvectors: Matrix<N, N>,
+    pub iteration_count: usize,
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AllEigenvaluesExperimentStat<const N: usize> {
+    /// (λ, V) aligned with `reference_solution` by `match_eigenpairs`
+    pub solution: (Vector<N>, Matrix<N, N>),
+    pub iteration_count: usize,
+    pub elapsed: std::time::Duration,
+    pub reference_solution: (Vector<N>, Matrix<N, N>),
+    /// ||A V - V Λ||_F of all the eigenpairs at once
+    pub residual_norm: f64,
+    /// max_j |λ_j - μ_j| / |μ_j| against the reference eigenvalues μ_j
+    pub max_eigenvalues_relative_error: f64,
+    /// the largest angle between the eigenvectors and the reference ones (in [0, π/2])
+    pub max_eigenvector_angle: f64,
+    /// ||V^T V - I||_F
+    pub orthogonality_error: f64,
+}
+
+impl<const N: usize> ExperimentStat for AllEigenvaluesExperimentStat<N> {
+    fn metrics(&self) -> Vec<(&'static str, f64)> {
+        vec![
+            ("residual_norm", self.residual_norm),
+            ("max_eigenvalues_relative_error", self.max_eigenvalues_relative_error),
+            ("max_eigenvector_angle", self.max_eigenvector_angle),
+            ("orthogonality_error", self.orthogonality_error),
+            ("iteration_count", self.iteration_count as f64),
+            ("elapsed_secs", self.elapsed.as_secs_f64()),
+        ]
+    }
+}
+
+impl<const N: usize> AllEigenvaluesSolver<N> {
+    /// `f: A -> (λ, V)` should find all the eigenvalues λ_j and the eigenvectors v_j
+    /// as the columns of V of a symmetric A
+    pub fn new(f: impl Fn(&Matrix<N, N>) -> AllEigenvaluesSolution<N> + Send + Sync + 'static) -> Self {
+        Self { f: Box::new(f) }
+    }
+    
+    /// by `qr_algorithm` with the tolerance `EPSILON`
+    pub fn new_unshifted_qr() -> Self {
+        Self::new(|a| qr_algorithm(a, EPSILON).expect("QR algorithm doesn't converge"))
+    }
+    
+    /// by `symmetric_qr_algorithm` with the tolerance `f64::EPSILON`
+    pub fn new_symmetric_qr() -> Self {
+        Self::new(|a| symmetric_qr_algorithm(a, f64::EPSILON).expect("QR algorithm doesn't converge"))
+    }
+    
+    /// by `jacobi_eigenvalue_method` with the tolerance `EPSILON`, counting the sweeps
+    /// (not `f64::EPSILON`, below the rounding errors of the rotations in off(A))
+    pub fn new_jacobi() -> Self {
+        Self::new(|a| jacobi_eigenvalue_method(a, EPSILON).expect("Jacobi method doesn't converge").0)
+    }
+    
+    /// All the eigenpairs by nalgebra's symmetric eigendecomposition in descending order,
+    /// with the signs of the eigenvectors fixed to make their largest entries (in modulus) positive
+    fn reference(a: &Matrix<N, N>) -> (Vector<N>, Matrix<N, N>) {
+        let eigen = nalgebra::DMatrix::from_column_slice(N, N, a.as_slice()).symmetric_eigen();
+        let eigenvalues = Vector::<N>::try_from(eigen.eigenvalues.as_slice()).unwrap();
+        let eigenvectors = Matrix::<N, N>::from_fn(|i, j| {
+            let column = eigen.eigenvectors.column(j);
+            column[i] * column[column.iamax()].signum()
+        });
+        sort_eigenpairs(&eigenvalues, &eigenvectors, EigenvalueOrder::DescendingValue)
+    }
+    
+    pub fn solve(&self, a: &Matrix<N, N>) -> AllEigenvaluesSolution<N> {
+        (self.f)(a)
+    }
+    
+    /// `experiment_randomly` for `config.trials` times
+    pub fn experiments(&self, config: &ExperimentConfig) -> Vec<AllEigenvaluesExperimentStat<N>> {
+        config.run(|| self.experiment_randomly())
+    }
+    
+    /// `experiments` recording the trials that panicked or timed out (see `ExperimentConfig::try_run`)
+    pub fn try_experiments(&self, config: &ExperimentConfig) -> Vec<TrialOutcome<AllEigenvaluesExperimentStat<N>>> {
+        config.try_run(|| self.experiment_randomly())
+    }
+    
+    /// with a random symmetric A of the eigenvalues N, N - 1, ..., 1 by `Matrix::random_with_spectrum`,
+    /// whose ratios (N - 1) / N, ..., 1 / 2 are the rates of the unshifted QR algorithm
+    pub fn experiment_randomly(&self) -> AllEigenvaluesExperimentStat<N> {
+        self.experiment_randomly_from(&WithSpectrum(DiagonalMatrix::from(Vector::<N>::from_fn(|i, _| (N - i) as f64))))
+    }
+    
+    /// with a symmetric A from `distribution`, as the reference is by the symmetric eigendecomposition
+    pub fn experiment_randomly_from(&self, distribution: &impl MatrixDistribution<N>) -> AllEigenvaluesExperimentStat<N> {
+        self.experiment_on(&distribution.sample())
+    }
+    
+    /// the same stats for the given symmetric A, e.g. of `test_matrices` or loaded from a file
+    pub fn experiment_on(&self, a: &Matrix<N, N>) -> AllEigenvaluesExperimentStat<N> {
+        let (AllEigenvaluesSolution { eigenvalues, eigenvectors, iteration_count }, elapsed) = with_elapsed(|| self.solve(a));
+        let (reference_eigenvalues, reference_eigenvectors) = Self::reference(a);
+        let (eigenvalues, eigenvectors) = match_eigenpairs((&eigenvalues, &eigenvectors), (&reference_eigenvalues, &reference_eigenvectors));
+        
+        let residual_norm = (&(a * &eigenvectors) - &(&eigenvectors * &DiagonalMatrix::from(eigenvalues.clone()))).frobenius_norm();
+        let max_eigenvalues_relative_error = (0..N)
+            .map(|j| (eigenvalues[j] - reference_eigenvalues[j]).abs() / reference_eigenvalues[j].abs())
+            .fold(0.0, f64::max);
+        let max_eigenvector_angle = (0..N)
+            .map(|j| eigenvectors.column_vector(j).angle_to(&reference_eigenvectors.column_vector(j)))
+            .fold(0.0, f64::max);
+        let orthogonality_error = (&(&eigenvectors.transpose() * &eigenvectors) - &Matrix::<N, N>::identity()).frobenius_norm();
+        
+        AllEigenvaluesExperimentStat {
+            solution: (eigenvalues, eigenvectors),
+            iteration_count,
+            elapsed,
+            reference_solution: (reference_eigenvalues, reference_eigenvectors),
+            residual_norm,
+            max_eigenvalues_relative_error,
+            max_eigenvector_angle,
+            orthogonality_error,
+        }
+    }
+}
+
+type LeastSquaresSolveFn<const N: usize, const M: usize> = Box<dyn Fn(&Matrix<N, M>, &Vector<N>) -> Vector<M> + Send + Sync>;
+
+pub struct LeastSquaresSolver<const N: usize, const M: usize> {
+    f: LeastSquaresSolveFn<N, M>,
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LeastSquaresExperimentStat<const M: usize> {
+    pub solution: Vector<M>,
+    pub elapsed: std::time::Duration,
+    pub reference_solution: Vector<M>,
+    /// ||b - Ax||_2, not vanishing for an inconsistent system
+    pub residual_norm: f64,
+    /// ||b - Ax||_2 of the reference solution, the minimum
+    pub reference_residual_norm: f64,
+    pub relative_error: f64,
+    /// 2-norm condition number σ_max / σ_min of the random instance
+    pub condition_number: f64,
+}
+
+impl<const M: usize> ExperimentStat for LeastSquaresExperimentStat<M> {
+    fn metrics(&self) -> Vec<(&'static str, f64)> {
+        vec![
+            ("residual_norm", self.residual_norm),
+            ("reference_residual_norm", self.reference_residual_norm),
+            ("relative_error", self.relative_error),
+            ("condition_number", self.condition_number),
+            ("elapsed_secs", self.elapsed.as_secs_f64()),
+        ]
+    }
+}
+
+impl<const N: usize, const M: usize> LeastSquaresSolver<N, M> {
+    /// `f: (A, b) -> x` should find x minimizing `||b - Ax||_2` for an N x M A,
+    /// the one of the minimum `||x||_2` among them if underdetermined (N < M)
+    pub fn new(f: impl Fn(&Matrix<N, M>, &Vector<N>) -> Vector<M> + Send + Sync + 'static) -> Self {
+        Self { f: Box::new(f) }
+    }
+    
+    /// by `solve_least_squares_normal`
+    pub fn new_normal_equations() -> Self {
+        Self::new(|a, b| solve_least_squares_normal(a, b).expect("Matrix is rank deficient"))
+    }
+    
+    /// by `solve_least_squares_qr`
+    pub fn new_qr() -> Self {
+        Self::new(|a, b| solve_least_squares_qr(a, b).expect("Matrix is rank deficient"))
+    }
+    
+    /// by `Matrix::pinv` (with the tolerance `EPSILON`), also for a rank-deficient A
+    pub fn new_pinv() -> Self {
+        Self::new(|a, b| &a.pinv(EPSILON) * b)
+    }
+    
+    /// by `solve_minimum_norm`, for an underdetermined A (N < M)
+    pub fn new_minimum_norm() -> Self {
+        Self::new(|a, b| solve_minimum_norm(a, b).expect("Matrix is rank deficient"))
+    }
+    
+    /// A reference implementation using nalgebra's QR decomposition A = QR
+    /// to solve R x = Q^T b, or its SVD for the minimum-norm solution if underdetermined.
+    fn new_reference() -> Self {
+        Self::new(|a: &Matrix<N, M>, b: &Vector<N>| -> Vector<M> {
+            if N < M {
+                let x = nalgebra::DMatrix::from_column_slice(N, M, a.as_slice())
+                    .svd(true, true)
+                    .solve(&nalgebra::DVector::from_column_slice(b.as_ref()), f64::EPSILON)
+                    .expect("SVD is computed with U and V");
+                return Vector::<M>::try_from(x.as_slice()).unwrap();
+            }
+            let qr = nalgebra::DMatrix::from_column_slice(N, M, a.as_slice()).qr();
+            let qtb = qr.q().transpose() * nalgebra::DVector::from_column_slice(b.as_ref());
+            let x = qr.r().solve_upper_triangular(&qtb).expect("Matrix is rank deficient");
+            Vector::<M>::try_from(x.as_slice()).unwrap()
+        })
+    }
+    
+    pub fn solve(&self, a: &Matrix<N, M>, b: &Vector<N>) -> Vector<M> {
+        (self.f)(a, b)
+    }
+    
+    /// `experiment_randomly` for `config.trials` times
+    pub fn experiments(&self, config: &ExperimentConfig) -> Vec<LeastSquaresExperimentStat<M>> {
+        config.run(|| self.experiment_randomly())
+    }
+    
+    /// `experiments` recording the trials that panicked or timed out (see `ExperimentConfig::try_run`)
+    pub fn try_experiments(&self, config: &ExperimentConfig) -> Vec<TrialOutcome<LeastSquaresExperimentStat<M>>> {
+        config.try_run(|| self.experiment_randomly())
+    }
+    
+    pub fn experiment_randomly(&self) -> LeastSquaresExperimentStat<M> {
+        let a = Matrix::<N, M>::random_uniform(-1.0, 1.0);
+        let b = Vector::<N>::random_uniform(-1.0, 1.0);
+        self.experiment_on(&a, &b)
+    }
+    
+    /// the same stats for the given instance, e.g. the Vandermonde one of a polynomial fitting
+    pub fn experiment_on(&self, a: &Matrix<N, M>, b: &Vector<N>) -> LeastSquaresExperimentStat<M> {
+        let (solution, elapsed) = with_elapsed(|| self.solve(a, b));
+        let reference_solution = Self::new_reference().solve(a, b);
+        
+        let residual_norm = (b - a * &solution).norm();
+        let reference_residual_norm = (b - a * &reference_solution).norm();
+        let relative_error = (&solution - &reference_solution).norm() / reference_solution.norm();
+        let condition_number = {
+            let singular_values = nalgebra::DMatrix::from_column_slice(N, M, a.as_slice()).singular_values();
+            let (max, min) = (singular_values.max(), singular_values.min());
+            if min > 0.0 { max / min } else { f64::INFINITY }
+        };
+        
+        LeastSquaresExperimentStat {
+            solution,
+            elapsed,
+            reference_solution,
+            residual_norm,
+            reference_residual_norm,
+            relative_error,
+            condition_number,
+        }
+    }
+}
+
+/// `(A, b, x0, max_iterations, criterion) -> x` by `IterativeSolve::solve`
+type IterativeSolveFn<const N: usize> = Box<dyn Fn(&Matrix<N, N>, &Vector<N>, &Vector<N>, usize, StoppingCriterion) -> IterativeSolution<N> + Send + Sync>;
+
+pub struct IterativeSolver<const N: usize> {
+    f: IterativeSolveFn<N>,
+    max_iterations: usize,
+    criterion: StoppingCriterion,
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IterativeExperimentStat<const N: usize> {
+    pub solution: Vector<N>,
+    pub elapsed: std::time::Duration,
+    pub reference_solution: Vector<N>,
+    pub residual_norm: f64,
+    pub relative_error: f64,
+    /// 2-norm condition number of the random instance
+    pub condition_number: f64,
+    pub iteration_count: usize,
+    pub converged: bool,
+    /// ||b - A x_k|| for k = 0, 1, ..., `iteration_count`, to plot the convergence curve
+    pub residual_history: Vec<f64>,
+    /// peak bytes allocated during the solve,
+    /// `None` unless `CountingAllocator` is the global allocator
+    pub peak_memory_bytes: Option<usize>,
+    /// allocations during the solve, `None` unless `CountingAllocator` is the global allocator
+    pub allocation_count: Option<usize>,
+    /// flops counted during the solve (see `count_flops`), `None` without the `flop-count` feature
+    pub flop_count: Option<u64>,
+}
+
+impl<const N: usize> ExperimentStat for IterativeExperimentStat<N> {
+    fn metrics(&self) -> Vec<(&'static str, f64)> {
+        let mut metrics = vec![
+            ("residual_norm", self.residual_norm),
+            ("relative_error", self.relative_error),
+            ("condition_number", self.condition_number),
+            ("iteration_count", self.iteration_count as f64),
+            ("converged", if self.converged { 1.0 } else { 0.0 }),
+            ("elapsed_secs", self.elapsed.as_secs_f64()),
+        ];
+        if let Some(peak_memory_bytes) = self.peak_memory_bytes {
+            metrics.push(("peak_memory_bytes", peak_memory_bytes as f64));
+        }
+        if let Some(allocation_count) = self.allocation_count {
+            metrics.push(("allocation_count", allocation_count as f64));
+        }
+        if let Some(flop_count) = self.flop_count {
+            metrics.push(("flop_count", flop_count as f64));
+        }
+        metrics
+    }
+}
+
+impl<const N: usize> IterativeSolver<N> {
+    /// `method` iterating from x_0 = 0 at most 10000 times until
+    /// the relative residual reaches 1e-10, unless `with_max_iterations` or `with_criterion`
+    pub fn new(method: impl IterativeSolve<N> + Send + Sync + 'static) -> Self {
+        Self {
+            f: Box::new(move |a, b, x0, max_iterations, criterion| method.solve(a, b, x0, max_iterations, criterion)),
+            max_iterations: 10000,
+            criterion: StoppingCriterion::RelativeResidual(1e-10),
+        }
+    }
+    
+    pub fn with_max_iterations(self, max_iterations: usize) -> Self {
+        Self { max_iterations, ..self }
+    }
+    
+    pub fn with_criterion(self, criterion: StoppingCriterion) -> Self {
+        Self { criterion, ..self }
+    }
+    
+    /// `Richardson` with the optimal α estimated for each A (to be experimented by `experiment_randomly_spd`)
+    pub fn new_richardson() -> Self {
+        Self::new(Richardson::OPTIMAL)
+    }
+    
+    pub fn new_jacobi() -> Self {
+        Self::new(Jacobi)
+    }
+    
+    pub fn new_gauss_seidel() -> Self {
+        Self::new(GaussSeidel)
+    }
+    
+    pub fn new_sor(omega: f64) -> Self {
+        Self::new(Sor { omega })
+    }
+    
+    /// plain CG (to be experimented by `experiment_randomly_spd`)
+    pub fn new_cg() -> Self {
+        Self::new(Pcg { preconditioner: NoPreconditioner })
+    }
+    
+    /// restarted GMRES(`restart`), whose iterations are the restart cycles,
+    /// for a general (e.g. nonsymmetric) A
+    pub fn new_gmres(restart: usize) -> Self {
+        Self::new(Gmres { restart, preconditioner: NoPreconditioner })
+    }
+    
+    pub fn solve(&self, a: &Matrix<N, N>, b: &Vector<N>) -> IterativeSolution<N> {
+        (self.f)(a, b, &Vector::zeroed(), self.max_iterations, self.criterion)
+    }
+    
+    /// `experiment_randomly` for `config.trials` times
+    pub fn experiments(&self, config: &ExperimentConfig) -> Vec<IterativeExperimentStat<N>> {
+        config.run(|| self.experiment_randomly())
+    }
+    
+    /// `experiments` recording the trials that panicked or timed out (see `ExperimentConfig::try_run`)
+    pub fn try_experiments(&self, config: &ExperimentConfig) -> Vec<TrialOutcome<IterativeExperimentStat<N>>> {
+        config.try_run(|| self.experiment_randomly())
+    }
+    
+    /// with a strictly diagonally dominant A by `Matrix::random_diagonally_dominant`,
+    /// for which Jacobi, Gauss-Seidel, and SOR (0 < ω <= 1) converge
+    pub fn experiment_randomly(&self) -> IterativeExperimentStat<N> {
+        self.experiment_randomly_from(&DiagonallyDominant)
+    }
+    
+    /// with a symmetric positive definite A by `Matrix::random_spd`, for `new_cg`
+    pub fn experiment_randomly_spd(&self) -> IterativeExperimentStat<N> {
+        self.experiment_randomly_from(&Spd)
+    }
+    
+    /// with A from `distribution` and b uniformly random in [-1, 1]
+    pub fn experiment_randomly_from(&self, distribution: &impl MatrixDistribution<N>) -> IterativeExperimentStat<N> {
+        let a = distribution.sample();
+        let b = Vector::<N>::random_uniform(-1.0, 1.0);
+        self.experiment_on(&a, &b)
+    }
+    
+    /// the same stats for the given instance, e.g. `test_matrices::poisson_2d`
+    pub fn experiment_on(&self, a: &Matrix<N, N>, b: &Vector<N>) -> IterativeExperimentStat<N> {
+        let (((IterativeSolution {
+            solution,
+            iteration_count,
+            residual_norms,
+            converged,
+        }, elapsed), flop_count), allocations) = alloc::with_allocations(|| flops::with_flops(|| with_elapsed(|| self.solve(a, b))));
+        let reference_solution = EquationSolver::new_reference().solve(a, b);
+        
+        let residual_norm = (b - a * &solution).norm();
+        let relative_error = (&solution - &reference_solution).norm() / reference_solution.norm();
+        let condition_number = a.condition_number();
+        
+        IterativeExperimentStat {
+            solution,
+            elapsed,
+            reference_solution,
+            residual_norm,
+            relative_error,
+            condition_number,
+            iteration_count,
+            converged,
+            residual_history: residual_norms,
+            peak_memory_bytes: allocations.as_ref().map(|allocations| allocations.peak_bytes),
+            allocation_count: allocations.map(|allocations| allocations.count),
+            flop_count,
         }
     }
 }
@@ -0,0 +1,570 @@
+//! LU decompositions with the different pivoting strategies to compare
+
+use crate::{Float, Scalar, Matrix, Vector, BandedMatrix, LowerTriangular, UpperTriangular};
+use crate::{forward_substitution, back_substitution, count_flops};
+
+/// LU decomposition with partial pivoting
+/// 
+/// ```text
+/// P A = L U
+/// ```
+/// 
+/// where `(P A)_{i*} = a_{pi_i *}`, reusable for multiple right-hand sides
+#[derive(Clone)]
+pub struct Lu<const N: usize, T = f64> {
+    l: LowerTriangular<N, T>,
+    u: UpperTriangular<N, T>,
+    pi: [usize; N],
+}
+
+/// LU decomposition with partial pivoting, choosing the entry
+/// of the largest absolute value in the k-th column as the k-th pivot,
+/// or `None` if A is singular.
+pub fn lu_decomposition<const N: usize, T: Scalar>(a: &Matrix<N, N, T>) -> Option<Lu<N, T>> {
+    row_pivoted_lu(a, &[T::Real::ONE; N])
+}
+
+/// LU decomposition with scaled partial pivoting, choosing the entry of the largest
+/// |a_{ik}| / s_i in the k-th column as the k-th pivot with the scale of each original row
+/// 
+/// ```text
+/// s_i = max_j |a_{ij}|
+/// ```
+/// 
+/// so that the choice is invariant under scaling the rows of A,
+/// or `None` if A is singular (including a zero row).
+pub fn scaled_partial_pivot_lu<const N: usize, T: Scalar>(a: &Matrix<N, N, T>) -> Option<Lu<N, T>> {
+    let scales: [T::Real; N] = std::array::from_fn(|i| a.row(i).map(|a_ij| a_ij.modulus()).fold(T::Real::ZERO, T::Real::max));
+    if scales.iter().any(|&s_i| s_i <= T::Real::TOLERANCE) {
+        return None;
+    }
+    row_pivoted_lu(a, &scales)
+}
+
+/// partial pivoting by the largest |u_{ik}| / `scales[pi_i]` of the original row pi_i
+fn row_pivoted_lu<const N: usize, T: Scalar>(a: &Matrix<N, N, T>, scales: &[T::Real; N]) -> Option<Lu<N, T>> {
+    // initialize `pi` as an identity permutation
+    let mut pi: [usize; N] = std::array::from_fn(|i| i);
+    // initialize `l` as an identity matrix
+    let mut l = Matrix::<N, N, T>::identity();
+    // initialize `u` as `a` itself
+    let mut u = a.clone();
+    
+    /*
+     * NOTE:
+     * 
+     * Our textbook illustrates this step as
+     * iterating k from 1 to **N - 1** by 1-based index,
+     * which is equivalent to iterating k from 0 to **N - 2** by 0-based index.
+     * 
+     * It's wrong. It should be iterating k from 0 to **N - 1** by 0-based index,
+     * i.e., 1 to **N** by 1-based index.
+     */
+    for k in 0..N {
+        let (i, _pivot) = (k..N)
+            .map(|i| (i, u[(i, k)]))
+            .filter(|(_, value)| value.modulus() > T::Real::TOLERANCE)
+            // reversed to take the first of the largest, as `max_by` returns the last
+            .rev()
+            .max_by(|&(i, a), &(j, b)| {
+                T::Real::partial_cmp(&(a.modulus() / scales[pi[i]]), &(b.modulus() / scales[pi[j]])).expect("found NaN or Inf")
+            })?;
+        
+        if i != k {
+            u.swap_rows(i, k);
+            l.swap_rows(i, k);
+            pi.swap(i, k);
+        }
+        
+        for i in (k + 1)..N {
+            let factor = u[(i, k)] / u[(k, k)];
+            for j in k..N {
+                let update = factor * u[(k, j)];
+                u[(i, j)] -= update;
+            }
+            l[(i, k)] = factor;
+            count_flops(1 + 2 * (N - k));
+        }
+        l[(k, k)] = T::ONE;
+        l.column_mut(k).take(k).for_each(|it| *it = T::ZERO);
+    }
+    
+    Some(Lu {
+        l: LowerTriangular::from_lower_part(l),
+        u: UpperTriangular::from_upper_part(u),
+        pi,
+    })
+}
+
+impl<const N: usize, T: Scalar> Lu<N, T> {
+    pub fn l(&self) -> &LowerTriangular<N, T> {
+        &self.l
+    }
+    pub fn u(&self) -> &UpperTriangular<N, T> {
+        &self.u
+    }
+    
+    /// pi of P
+    pub fn permutation(&self) -> &[usize; N] {
+        &self.pi
+    }
+    
+    /// Solve Ax = b by L y = P b, U x = y
+    pub fn solve(&self, b: &Vector<N, T>) -> Vector<N, T> {
+        // solve Ly = Pb by forward substitution
+        let y = forward_substitution(&self.l, &Vector::<N, T>::from_fn(|i, _| b[self.pi[i]]));
+        // solve Ux = y by back substitution
+        back_substitution(&self.u, &y)
+    }
+    
+    /// Solve A^T x = b by U^T w = b, L^T v = w, x = P^T v
+    pub fn solve_transposed(&self, b: &Vector<N, T>) -> Vector<N, T> {
+        let w = forward_substitution(&self.u.transpose(), b);
+        let v = back_substitution(&self.l.transpose(), &w);
+        let mut x = Vector::<N, T>::zeroed();
+        (0..N).for_each(|i| x[self.pi[i]] = v[i]);
+        x
+    }
+    
+    /// Solve AX = B column by column
+    pub fn solve_matrix<const K: usize>(&self, b: &Matrix<N, K, T>) -> Matrix<N, K, T> {
+        let mut x = Matrix::<N, K, T>::zeroed();
+        (0..K).for_each(|j| x.set_column(j, &self.solve(&b.column_vector(j))));
+        x
+    }
+    
+    /// det(A) = sgn(pi) prod_i u_{ii}
+    pub fn det(&self) -> T {
+        // sgn(pi) = (-1)^(N - number of cycles)
+        let mut visited = [false; N];
+        let mut sign = T::ONE;
+        for start in 0..N {
+            let mut i = start;
+            let mut length = 0;
+            while !visited[i] {
+                visited[i] = true;
+                i = self.pi[i];
+                length += 1;
+            }
+            if length % 2 == 0 && length > 0 {
+                sign = -sign;
+            }
+        }
+        (0..N).map(|i| self.u[(i, i)]).fold(sign, |det, u_ii| det * u_ii)
+    }
+    
+    /// A^{-1} by solving AX = I
+    pub fn inverse(&self) -> Matrix<N, N, T> {
+        self.solve_matrix(&Matrix::<N, N, T>::identity())
+    }
+}
+
+impl<const N: usize, T: Scalar> std::fmt::Debug for Lu<N, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Lu")
+            .field("l", &self.l)
+            .field("u", &self.u)
+            .field("pi", &self.pi)
+            .finish()
+    }
+}
+
+/// LU decomposition with complete pivoting
+/// 
+/// ```text
+/// P A Q = L U
+/// ```
+/// 
+/// where `(P A Q)_{ij} = a_{pi_i sigma_j}`
+#[derive(Clone)]
+pub struct FullPivotLu<const N: usize, T = f64> {
+    l: LowerTriangular<N, T>,
+    u: UpperTriangular<N, T>,
+    row_permutation: [usize; N],
+    column_permutation: [usize; N],
+}
+
+/// LU decomposition with complete pivoting, choosing the entry
+/// of the largest absolute value in the whole remaining submatrix
+/// as the k-th pivot, or `None` if A is singular.
+pub fn full_pivot_lu<const N: usize, T: Scalar>(a: &Matrix<N, N, T>) -> Option<FullPivotLu<N, T>> {
+    let mut row_permutation: [usize; N] = std::array::from_fn(|i| i);
+    let mut column_permutation: [usize; N] = std::array::from_fn(|j| j);
+    let mut l = Matrix::<N, N, T>::identity();
+    let mut u = a.clone();
+    for k in 0..N {
+        let (p, q, _pivot) = (k..N)
+            .flat_map(|i| (k..N).map(move |j| (i, j)))
+            .map(|(i, j)| (i, j, u[(i, j)]))
+            .filter(|(_, _, value)| value.modulus() > T::Real::TOLERANCE)
+            // reversed to take the first of the largest, as `max_by` returns the last
+            .rev()
+            .max_by(|(_, _, a), (_, _, b)| T::Real::partial_cmp(&a.modulus(), &b.modulus()).expect("found NaN or Inf"))?;
+        
+        if p != k {
+            u.swap_rows(p, k);
+            l.swap_rows(p, k);
+            row_permutation.swap(p, k);
+        }
+        if q != k {
+            u.swap_columns(q, k);
+            column_permutation.swap(q, k);
+        }
+        
+        for i in (k + 1)..N {
+            let factor = u[(i, k)] / u[(k, k)];
+            for j in k..N {
+                let update = factor * u[(k, j)];
+                u[(i, j)] -= update;
+            }
+            l[(i, k)] = factor;
+            count_flops(1 + 2 * (N - k));
+        }
+        l[(k, k)] = T::ONE;
+        l.column_mut(k).take(k).for_each(|it| *it = T::ZERO);
+    }
+    Some(FullPivotLu {
+        l: LowerTriangular::from_lower_part(l),
+        u: UpperTriangular::from_upper_part(u),
+        row_permutation,
+        column_permutation,
+    })
+}
+
+impl<const N: usize, T: Scalar> FullPivotLu<N, T> {
+    pub fn l(&self) -> &LowerTriangular<N, T> {
+        &self.l
+    }
+    pub fn u(&self) -> &UpperTriangular<N, T> {
+        &self.u
+    }
+    
+    /// pi of P
+    pub fn row_permutation(&self) -> &[usize; N] {
+        &self.row_permutation
+    }
+    /// sigma of Q
+    pub fn column_permutation(&self) -> &[usize; N] {
+        &self.column_permutation
+    }
+    
+    /// Solve Ax = b by L y = P b, U z = y, x = Q z
+    pub fn solve(&self, b: &Vector<N, T>) -> Vector<N, T> {
+        let y = forward_substitution(&self.l, &Vector::<N, T>::from_fn(|i, _| b[self.row_permutation[i]]));
+        let z = back_substitution(&self.u, &y);
+        let mut x = Vector::<N, T>::zeroed();
+        (0..N).for_each(|j| x[self.column_permutation[j]] = z[j]);
+        x
+    }
+}
+
+impl<const N: usize, T: Scalar> std::fmt::Debug for FullPivotLu<N, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FullPivotLu")
+            .field("l", &self.l)
+            .field("u", &self.u)
+            .field("row_permutation", &self.row_permutation)
+            .field("column_permutation", &self.column_permutation)
+            .finish()
+    }
+}
+
+/// The LU decomposition without pivoting broke down
+/// because the `step`-th pivot u_{kk} vanished (up to `TOLERANCE`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NoPivotBreakdown {
+    pub step: usize,
+}
+
+impl std::fmt::Display for NoPivotBreakdown {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "LU decomposition without pivoting broke down at step {}", self.step)
+    }
+}
+
+impl std::error::Error for NoPivotBreakdown {}
+
+/// LU decomposition without pivoting A = LU, which exists
+/// e.g. for diagonally dominant or positive definite A
+/// but is unstable in general (to contrast with partial pivoting)
+#[derive(Clone)]
+pub struct NoPivotLu<const N: usize, T = f64> {
+    l: LowerTriangular<N, T>,
+    u: UpperTriangular<N, T>,
+}
+
+/// LU decomposition without any row exchange,
+/// failing if some pivot vanishes on the way
+pub fn no_pivot_lu<const N: usize, T: Scalar>(a: &Matrix<N, N, T>) -> Result<NoPivotLu<N, T>, NoPivotBreakdown> {
+    let mut l = Matrix::<N, N, T>::identity();
+    let mut u = a.clone();
+    for k in 0..N {
+        if u[(k, k)].modulus() <= T::Real::TOLERANCE {
+            return Err(NoPivotBreakdown { step: k });
+        }
+        for i in (k + 1)..N {
+            let factor = u[(i, k)] / u[(k, k)];
+            for j in k..N {
+                let update = factor * u[(k, j)];
+                u[(i, j)] -= update;
+            }
+            l[(i, k)] = factor;
+            count_flops(1 + 2 * (N - k));
+        }
+    }
+    Ok(NoPivotLu {
+        l: LowerTriangular::from_lower_part(l),
+        u: UpperTriangular::from_upper_part(u),
+    })
+}
+
+impl<const N: usize, T: Scalar> NoPivotLu<N, T> {
+    pub fn l(&self) -> &LowerTriangular<N, T> {
+        &self.l
+    }
+    pub fn u(&self) -> &UpperTriangular<N, T> {
+        &self.u
+    }
+    
+    /// Solve Ax = b by L y = b, U x = y
+    pub fn solve(&self, b: &Vector<N, T>) -> Vector<N, T> {
+        back_substitution(&self.u, &forward_substitution(&self.l, b))
+    }
+}
+
+impl<const N: usize, T: Scalar> std::fmt::Debug for NoPivotLu<N, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NoPivotLu")
+            .field("l", &self.l)
+            .field("u", &self.u)
+            .finish()
+    }
+}
+
+/// Pivoting strategy of the Gaussian elimination
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Pivoting {
+    /// by `no_pivot_lu`
+    None,
+    /// by `lu_decomposition`
+    Partial,
+    /// by `scaled_partial_pivot_lu`
+    ScaledPartial,
+    /// by `full_pivot_lu`
+    Complete,
+}
+
+impl Pivoting {
+    pub const ALL: [Self; 4] = [Self::None, Self::Partial, Self::ScaledPartial, Self::Complete];
+}
+
+/// Solve Ax = b by the Gaussian elimination with the `pivoting` strategy,
+/// or `None` if it breaks down (a zero pivot for `Pivoting::None`, a singular A otherwise)
+pub fn gaussian_elimination<const N: usize, T: Scalar>(a: &Matrix<N, N, T>, b: &Vector<N, T>, pivoting: Pivoting) -> Option<Vector<N, T>> {
+    match pivoting {
+        Pivoting::None => no_pivot_lu(a).ok().map(|lu| lu.solve(b)),
+        Pivoting::Partial => lu_decomposition(a).map(|lu| lu.solve(b)),
+        Pivoting::ScaledPartial => scaled_partial_pivot_lu(a).map(|lu| lu.solve(b)),
+        Pivoting::Complete => full_pivot_lu(a).map(|lu| lu.solve(b)),
+    }
+}
+
+/// Which factor of the compact LU schemes has the unit diagonal
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CompactScheme {
+    /// unit lower triangular L
+    Doolittle,
+    /// unit upper triangular U
+    Crout,
+}
+
+/// LU decomposition without pivoting A = LU by a compact scheme, computing
+/// each entry of L and U directly as an inner product instead of
+/// updating the remaining submatrix. Doolittle computes the k-th row of U
+/// and then the k-th column of L:
+/// 
+/// ```text
+/// u_{kj} = a_{kj} - sum_{m<k} l_{km} u_{mj}  (j >= k)
+/// l_{ik} = (a_{ik} - sum_{m<k} l_{im} u_{mk}) / u_{kk}  (i > k)
+/// ```
+/// 
+/// and Crout is Doolittle of A^T transposed: A^T = L' U' gives A = U'^T L'^T.
+pub fn compact_lu<const N: usize, T: Scalar>(a: &Matrix<N, N, T>, scheme: CompactScheme) -> Result<NoPivotLu<N, T>, NoPivotBreakdown> {
+    match scheme {
+        CompactScheme::Doolittle => doolittle(a),
+        CompactScheme::Crout => doolittle(&a.transpose()).map(|lu| NoPivotLu {
+            l: lu.u.transpose(),
+            u: lu.l.transpose(),
+        }),
+    }
+}
+
+fn doolittle<const N: usize, T: Scalar>(a: &Matrix<N, N, T>) -> Result<NoPivotLu<N, T>, NoPivotBreakdown> {
+    let mut l = Matrix::<N, N, T>::identity();
+    let mut u = Matrix::<N, N, T>::zeroed();
+    for k in 0..N {
+        for j in k..N {
+            u[(k, j)] = a[(k, j)] - (0..k).map(|m| l[(k, m)] * u[(m, j)]).sum::<T>();
+        }
+        count_flops((2 * k + 1) * (N - k));
+        if u[(k, k)].modulus() <= T::Real::TOLERANCE {
+            return Err(NoPivotBreakdown { step: k });
+        }
+        for i in (k + 1)..N {
+            l[(i, k)] = (a[(i, k)] - (0..k).map(|m| l[(i, m)] * u[(m, k)]).sum::<T>()) / u[(k, k)];
+            count_flops(2 * k + 2);
+        }
+    }
+    Ok(NoPivotLu {
+        l: LowerTriangular::from_lower_part(l),
+        u: UpperTriangular::from_upper_part(u),
+    })
+}
+
+/// Solve Ax = b by `lu_decomposition` followed by at most `max_refinements`
+/// steps of iterative refinement reusing the factorization (see `refine`),
+/// returning x and the number of the correction steps taken, or `None` if A is singular
+pub fn solve_with_refinement<const N: usize, T: Scalar>(
+    a: &Matrix<N, N, T>,
+    b: &Vector<N, T>,
+    max_refinements: usize,
+) -> Option<(Vector<N, T>, usize)> {
+    let lu = lu_decomposition(a)?;
+    Some(refine(a, b, lu.solve(b), |r| lu.solve(r), max_refinements))
+}
+
+/// Solve Ax = b in mixed precision: `lu_decomposition` of A in `f32`, about twice as fast
+/// on the half-sized entries, then at most `max_refinements` steps of the refinement in `f64`
+/// with each correction solved by the `f32` factors (see `refine`),
+/// recovering the `f64` accuracy for a moderately conditioned A (κ(A) << 1 / ε_f32).
+/// Returns x and the number of the correction steps taken, or `None` if A is singular in `f32`.
+pub fn solve_mixed_precision<const N: usize>(
+    a: &Matrix<N, N>,
+    b: &Vector<N>,
+    max_refinements: usize,
+) -> Option<(Vector<N>, usize)> {
+    let lu = lu_decomposition(&a.cast::<f32>())?;
+    let solve = |r: &Vector<N>| lu.solve(&r.cast()).cast();
+    Some(refine(a, b, solve(b), solve, max_refinements))
+}
+
+/// iterative refinement of an approximate solution x of Ax = b by `solve` approximating A^{-1}:
+/// 
+/// ```text
+/// r = b - A x,  A d = r,  x <- x + d
+/// ```
+/// 
+/// until ||d|| <= TOLERANCE ||x|| or `max_refinements` steps,
+/// returning the refined x and the number of the steps taken
+pub(crate) fn refine<const N: usize, T: Scalar>(
+    a: &Matrix<N, N, T>,
+    b: &Vector<N, T>,
+    mut x: Vector<N, T>,
+    solve: impl Fn(&Vector<N, T>) -> Vector<N, T>,
+    max_refinements: usize,
+) -> (Vector<N, T>, usize) {
+    for count in 0..max_refinements {
+        let d = solve(&(b - a * &x));
+        x = &x + &d;
+        if d.norm() <= T::Real::TOLERANCE * x.norm() {
+            return (x, count + 1);
+        }
+    }
+    (x, max_refinements)
+}
+
+/// LU decomposition with partial pivoting of a banded A with `lower` = p and `upper` = q,
+/// where the row interchanges grow the upper bandwidth of U to p + q
+/// while each column of L keeps at most p multipliers,
+/// costing O(N p (p + q)) instead of O(N^3)
+#[derive(Clone, Debug, PartialEq)]
+pub struct BandLu<const N: usize, T = f64> {
+    /// the multipliers below the diagonal and U on and above it
+    factors: BandedMatrix<N, T>,
+    /// row k was interchanged with row `pivots[k]` at the k-th step
+    pivots: [usize; N],
+}
+
+/// LU decomposition with partial pivoting restricted to the band,
+/// or `None` if A is singular
+pub fn band_lu<const N: usize, T: Scalar>(a: &BandedMatrix<N, T>) -> Option<BandLu<N, T>> {
+    let (p, q) = (a.lower(), a.upper());
+    let mut factors = BandedMatrix::<N, T>::from_fn(p, p + q, |i, j| a.get(i, j));
+    let mut pivots: [usize; N] = std::array::from_fn(|k| k);
+    
+    for k in 0..N {
+        let last_row = (k + p).min(N - 1);
+        let last_column = (k + p + q).min(N - 1);
+        
+        let (i, _pivot) = (k..=last_row)
+            .map(|i| (i, factors[(i, k)]))
+            .filter(|(_, value)| value.modulus() > T::Real::TOLERANCE)
+            // reversed to take the first of the largest, as `max_by` returns the last
+            .rev()
+            .max_by(|(_, a), (_, b)| T::Real::partial_cmp(&a.modulus(), &b.modulus()).expect("found NaN or Inf"))?;
+        
+        pivots[k] = i;
+        if i != k {
+            for j in k..=last_column {
+                let (a_ij, a_kj) = (factors[(i, j)], factors[(k, j)]);
+                factors[(i, j)] = a_kj;
+                factors[(k, j)] = a_ij;
+            }
+        }
+        
+        for i in (k + 1)..=last_row {
+            let factor = factors[(i, k)] / factors[(k, k)];
+            for j in (k + 1)..=last_column {
+                let update = factor * factors[(k, j)];
+                factors[(i, j)] -= update;
+            }
+            factors[(i, k)] = factor;
+        }
+    }
+    
+    Some(BandLu { factors, pivots })
+}
+
+impl<const N: usize, T: Scalar> BandLu<N, T> {
+    /// U, banded with `upper` = p + q
+    pub fn u(&self) -> BandedMatrix<N, T> {
+        BandedMatrix::<N, T>::from_fn(0, self.factors.upper(), |i, j| self.factors[(i, j)])
+    }
+    
+    /// Solve Ax = b by applying the interchanges and eliminations to b in order,
+    /// then back substitution with U, both within the band
+    pub fn solve(&self, b: &Vector<N, T>) -> Vector<N, T> {
+        let (p, upper) = (self.factors.lower(), self.factors.upper());
+        let mut x = b.clone();
+        for k in 0..N {
+            let i = self.pivots[k];
+            if i != k {
+                let (x_i, x_k) = (x[i], x[k]);
+                x[i] = x_k;
+                x[k] = x_i;
+            }
+            for i in (k + 1)..=(k + p).min(N - 1) {
+                let update = self.factors[(i, k)] * x[k];
+                x[i] -= update;
+            }
+        }
+        for i in (0..N).rev() {
+            let update = ((i + 1)..=(i + upper).min(N - 1)).map(|j| self.factors[(i, j)] * x[j]).sum::<T>();
+            x[i] = (x[i] - update) / self.factors[(i, i)];
+        }
+        x
+    }
+}
+
+/// growth factor of the elimination of A resulting in U:
+/// 
+/// ```text
+/// rho = max_{ij} |u_{ij}| / max_{ij} |a_{ij}|
+/// ```
+/// 
+/// bounded by 2^{N-1} with partial pivoting (attained by `test_matrices::wilkinson_growth`)
+/// and growing much slower with complete pivoting
+pub fn growth_factor<const N: usize, T: Scalar>(a: &Matrix<N, N, T>, u: &UpperTriangular<N, T>) -> T::Real {
+    let max_modulus = |entries: &[T]| entries.iter().map(|x| x.modulus()).fold(T::Real::ZERO, T::Real::max);
+    max_modulus(u.as_slice()) / max_modulus(a.as_slice())
+}
@@ -1,90 +1,138 @@
-/// N-rows and M-columns matrix
+use crate::{Float, Scalar, DiagonalMatrix, DynMatrix, count_flops};
+
+/// N-rows and M-columns matrix of `T` (`f64` by default)
 #[derive(Clone)]
-pub struct Matrix<const N: usize, const M: usize> {
+pub struct Matrix<const N: usize, const M: usize, T = f64> {
+    /// column-major in a single contiguous `Vec` (`a_{ij}` at `j * N + i`),
     /// using `Vec` instead of array to avoid stack overflow for large matrices
-    columns: Vec<Vec<f64>>,
+    entries: Vec<T>,
 }
 
-pub type Vector<const N: usize> = Matrix<N, 1>;
+pub type Vector<const N: usize, T = f64> = Matrix<N, 1, T>;
 
-impl<const N: usize, const M: usize> From<[[f64; M]; N]> for Matrix<N, M> {
-    fn from(array_of_rows: [[f64; M]; N]) -> Self {
+impl<const N: usize, const M: usize, T: Scalar> From<[[T; M]; N]> for Matrix<N, M, T> {
+    fn from(array_of_rows: [[T; M]; N]) -> Self {
         Self::from_fn(|i, j| array_of_rows[i][j])
     }
 }
-impl<const N: usize, const M: usize> From<&[[f64; M]; N]> for Matrix<N, M> {
-    fn from(array_of_rows: &[[f64; M]; N]) -> Self {
+impl<const N: usize, const M: usize, T: Scalar> From<&[[T; M]; N]> for Matrix<N, M, T> {
+    fn from(array_of_rows: &[[T; M]; N]) -> Self {
         Self::from_fn(|i, j| array_of_rows[i][j])
     }
 }
-impl<const N: usize, const M: usize> From<[&[f64; M]; N]> for Matrix<N, M> {
-    fn from(array_of_rows: [&[f64; M]; N]) -> Self {
+impl<const N: usize, const M: usize, T: Scalar> From<[&[T; M]; N]> for Matrix<N, M, T> {
+    fn from(array_of_rows: [&[T; M]; N]) -> Self {
         Self::from_fn(|i, j| array_of_rows[i][j])
     }
 }
-impl<const N: usize, const M: usize> TryFrom<&[&[f64]]> for Matrix<N, M> {
+impl<const N: usize, const M: usize, T: Scalar> TryFrom<&[&[T]]> for Matrix<N, M, T> {
     type Error = &'static str;
-    fn try_from(slice_of_rows: &[&[f64]]) -> Result<Self, Self::Error> {
+    fn try_from(slice_of_rows: &[&[T]]) -> Result<Self, Self::Error> {
         (slice_of_rows.len() == N && slice_of_rows.iter().all(|row| row.len() == M))
             .then(|| Self::from_fn(|i, j| slice_of_rows[i][j]))
             .ok_or("slice of rows dimensions do not match matrix size")
     }
 }
 
-impl<const N: usize> From<[f64; N]> for Vector<N> {
-    fn from(array: [f64; N]) -> Self {
-        Self { columns: vec![array.to_vec()] }
+impl<const N: usize, T: Scalar> From<[T; N]> for Vector<N, T> {
+    fn from(array: [T; N]) -> Self {
+        Self { entries: array.to_vec() }
     }
 }
-impl<const N: usize> From<&[f64; N]> for Vector<N> {
-    fn from(array: &[f64; N]) -> Self {
-        Self { columns: vec![array.to_vec()] }
+impl<const N: usize, T: Scalar> From<&[T; N]> for Vector<N, T> {
+    fn from(array: &[T; N]) -> Self {
+        Self { entries: array.to_vec() }
     }
 }
-impl<const N: usize> TryFrom<&[f64]> for Vector<N> {
+impl<const N: usize, T: Scalar> TryFrom<&[T]> for Vector<N, T> {
     type Error = &'static str;
-    fn try_from(slice: &[f64]) -> Result<Self, Self::Error> {
+    fn try_from(slice: &[T]) -> Result<Self, Self::Error> {
         (slice.len() == N)
-            .then(|| Self { columns: vec![slice.to_vec()] })
+            .then(|| Self { entries: slice.to_vec() })
             .ok_or("slice length does not match vector size")
     }
 }
-impl<const N: usize> AsRef<[f64]> for Vector<N> {
-    fn as_ref(&self) -> &[f64] {
-        &self.columns[0]
+/// Panics if the iterator doesn't yield exactly N items.
+/// Use `Vector::try_from(&[T])` for a checked conversion.
+impl<const N: usize, T: Scalar> FromIterator<T> for Vector<N, T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let entries = iter.into_iter().collect::<Vec<_>>();
+        assert!(entries.len() == N, "iterator yielded {} items for vector of size {N}", entries.len());
+        Self { entries }
+    }
+}
+impl<const N: usize, T: Scalar> AsRef<[T]> for Vector<N, T> {
+    fn as_ref(&self) -> &[T] {
+        &self.entries
     }
 }
 
-impl<const N: usize, const M: usize> Matrix<N, M> {
-    pub fn from_fn(mut f: impl FnMut(usize, usize) -> f64) -> Self {
-        Self { columns: (0..M).map(|j| (0..N).map(|i| f(i, j)).collect()).collect() }
+impl<const N: usize, const M: usize, T: Scalar> Matrix<N, M, T> {
+    pub fn from_fn(mut f: impl FnMut(usize, usize) -> T) -> Self {
+        Self { entries: (0..M).flat_map(|j| (0..N).map(move |i| (i, j))).map(|(i, j)| f(i, j)).collect() }
+    }
+    
+    pub fn from_columns(columns: [Vector<N, T>; M]) -> Self {
+        Self { entries: columns.into_iter().flat_map(|column| column.entries).collect() }
+    }
+    
+    pub fn from_rows(rows: [Vector<M, T>; N]) -> Self {
+        Self::from_fn(|i, j| rows[i][j])
     }
     
-    pub fn filled_with(value: f64) -> Self {
-        Self { columns: vec![vec![value; N]; M] }
+    pub fn filled_with(value: T) -> Self {
+        Self { entries: vec![value; N * M] }
     }
     
     pub fn zeroed() -> Self {
-        Self::filled_with(0.0)
+        Self::filled_with(T::ZERO)
+    }
+    
+    /// convert each entry into another precision, e.g. `f64` -> `f32`
+    pub fn cast<U: Float>(&self) -> Matrix<N, M, U> where T: Float {
+        Matrix::<N, M, U> { entries: self.entries.iter().map(|x| U::from_f64(x.to_f64())).collect() }
     }
     
-    pub fn transpose(&self) -> Matrix<M, N> {
-        Matrix::<M, N>::from_fn(|i, j| self[(j, i)])
+    pub fn transpose(&self) -> Matrix<M, N, T> {
+        Matrix::<M, N, T>::from_fn(|i, j| self[(j, i)])
     }
     
-    pub fn into_split_first_column(self) -> (Vector<N>, Matrix<N, {M - 1}>) {
-        let mut columns = self.columns;
-        let first_column = columns.remove(0);
-        (Vector::<N> { columns: vec![first_column] }, Matrix::<N, {M - 1}> { columns })
+    /// conjugate transpose A^*, i.e. `transpose` for real matrices
+    pub fn adjoint(&self) -> Matrix<M, N, T> {
+        Matrix::<M, N, T>::from_fn(|i, j| self[(j, i)].conj())
     }
-    pub fn into_split_last_column(self) -> (Matrix<N, {M - 1}>, Vector<N>) {
-        let mut columns = self.columns;
-        let last_column = columns.pop().expect("Matrix must have at least one column to split");
-        (Matrix::<N, {M - 1}> { columns }, Vector::<N> { columns: vec![last_column] })
+    
+    pub fn into_split_first_column(self) -> (Vector<N, T>, Matrix<N, {M - 1}, T>) {
+        let mut entries = self.entries;
+        let rest = entries.split_off(N);
+        (Vector::<N, T> { entries }, Matrix::<N, {M - 1}, T> { entries: rest })
+    }
+    pub fn into_split_last_column(self) -> (Matrix<N, {M - 1}, T>, Vector<N, T>) {
+        assert!(M > 0, "Matrix must have at least one column to split");
+        let mut entries = self.entries;
+        let last_column = entries.split_off(N * (M - 1));
+        (Matrix::<N, {M - 1}, T> { entries }, Vector::<N, T> { entries: last_column })
     }
     
-    pub fn concat<const L: usize>(a: &Matrix<N, M>, b: &Matrix<N, L>) -> Matrix<N, {M + L}> {
-        Matrix::<N, {M + L}>::from_fn(|i, j| {
+    /// Split into the first K columns and the remaining L columns.
+    /// 
+    /// Unlike `into_split_first_column` / `into_split_last_column`,
+    /// the sizes are not expressed as `{M - 1}` expressions but as
+    /// independent parameters checked to satisfy K + L = M, so that
+    /// they can be inferred from the caller's types like:
+    /// 
+    /// ```text
+    /// let (a, b): (Matrix<N, N>, Vector<N>) = ab.split_at_column();
+    /// ```
+    pub fn split_at_column<const K: usize, const L: usize>(self) -> (Matrix<N, K, T>, Matrix<N, L, T>) {
+        assert!(K + L == M, "split sizes {K} + {L} do not match matrix columns {M}");
+        let mut left = self.entries;
+        let right = left.split_off(N * K);
+        (Matrix::<N, K, T> { entries: left }, Matrix::<N, L, T> { entries: right })
+    }
+    
+    pub fn concat<const L: usize>(a: &Matrix<N, M, T>, b: &Matrix<N, L, T>) -> Matrix<N, {M + L}, T> {
+        Matrix::<N, {M + L}, T>::from_fn(|i, j| {
             if j < M {
                 a[(i, j)]
             } else {
@@ -93,31 +141,449 @@ impl<const N: usize, const M: usize> Matrix<N, M> {
         })
     }
     
+    /// all entries in column-major order
+    pub fn as_slice(&self) -> &[T] {
+        &self.entries
+    }
+    
+    /// bytes occupied by the matrix including its heap allocation
+    pub fn memory_bytes(&self) -> usize {
+        std::mem::size_of::<Self>() + self.entries.capacity() * std::mem::size_of::<T>()
+    }
+    
+    fn column_slice(&self, j: usize) -> &[T] {
+        &self.entries[(j * N)..((j + 1) * N)]
+    }
+    fn column_slice_mut(&mut self, j: usize) -> &mut [T] {
+        &mut self.entries[(j * N)..((j + 1) * N)]
+    }
+    
+    /// i-th row as an owned (column) vector
+    pub fn row_vector(&self, i: usize) -> Vector<M, T> {
+        Vector::<M, T> { entries: self.row(i).collect() }
+    }
+    /// j-th column as an owned vector
+    pub fn column_vector(&self, j: usize) -> Vector<N, T> {
+        Vector::<N, T> { entries: self.column_slice(j).to_vec() }
+    }
+    
+    pub fn set_row(&mut self, i: usize, row: &Vector<M, T>) {
+        self.row_mut(i).zip(row.iter()).for_each(|(it, x)| *it = *x);
+    }
+    pub fn set_column(&mut self, j: usize, column: &Vector<N, T>) {
+        self.column_slice_mut(j).copy_from_slice(column.as_ref());
+    }
+    
+    /// vertical counterpart of `concat`
+    pub fn vconcat<const P: usize>(a: &Matrix<N, M, T>, b: &Matrix<P, M, T>) -> Matrix<{N + P}, M, T> {
+        Matrix::<{N + P}, M, T>::from_fn(|i, j| {
+            if i < N {
+                a[(i, j)]
+            } else {
+                b[(i - N, j)]
+            }
+        })
+    }
+    
     pub fn swap_rows(&mut self, i: usize, k: usize) {
         (0..M).for_each(|j| {
-            self.columns[j].swap(i, k);
+            self.entries.swap(j * N + i, j * N + k);
+        });
+    }
+    pub fn swap_columns(&mut self, j: usize, k: usize) {
+        (0..N).for_each(|i| {
+            self.entries.swap(j * N + i, k * N + i);
+        });
+    }
+    
+    /// multiply the i-th row by c
+    pub fn scale_row(&mut self, i: usize, c: T) {
+        self.row_mut(i).for_each(|it| *it *= c);
+        count_flops(M);
+    }
+    
+    /// add c times the `source`-th row to the `destination`-th row
+    pub fn add_scaled_row(&mut self, source: usize, destination: usize, c: T) {
+        (0..M).for_each(|j| {
+            let column = self.column_slice_mut(j);
+            let update = c * column[source];
+            column[destination] += update;
         });
+        count_flops(2 * M);
+    }
+    
+    /// apply `f` to each entry, counting no flops as `f` is arbitrary
+    pub fn map(&self, f: impl Fn(T) -> T) -> Self {
+        Self { entries: self.entries.iter().map(|x| f(*x)).collect() }
+    }
+    pub fn map_inplace(&mut self, f: impl Fn(T) -> T) {
+        self.entries.iter_mut().for_each(|x| *x = f(*x));
+    }
+    
+    /// apply `f` to each pair of entries at the same position
+    pub fn zip_map(&self, other: &Self, f: impl Fn(T, T) -> T) -> Self {
+        Self { entries: std::iter::zip(&self.entries, &other.entries).map(|(x, y)| f(*x, *y)).collect() }
+    }
+    fn zip_map_inplace(&mut self, other: &Self, f: impl Fn(T, T) -> T) {
+        std::iter::zip(&mut self.entries, &other.entries)
+            .for_each(|(x, y)| *x = f(*x, *y));
+    }
+    
+    /// one flop for each entry, of the entrywise arithmetic operators
+    fn count_entrywise_flops(&self) {
+        count_flops(N * M);
+    }
+    
+    /// element-wise (Hadamard) product
+    pub fn component_mul(&self, rhs: &Self) -> Self {
+        self.count_entrywise_flops();
+        self.zip_map(rhs, |x, y| x * y)
+    }
+    pub fn component_mul_assign(&mut self, rhs: &Self) {
+        self.count_entrywise_flops();
+        self.zip_map_inplace(rhs, |x, y| x * y);
+    }
+    
+    /// element-wise division
+    pub fn component_div(&self, rhs: &Self) -> Self {
+        self.count_entrywise_flops();
+        self.zip_map(rhs, |x, y| x / y)
+    }
+    pub fn component_div_assign(&mut self, rhs: &Self) {
+        self.count_entrywise_flops();
+        self.zip_map_inplace(rhs, |x, y| x / y);
+    }
+    
+    /// in-place matrix-vector product without allocation:
+    /// 
+    /// ```text
+    /// y <- alpha A x + beta y
+    /// ```
+    /// 
+    /// (`y` is not read when `beta` is 0)
+    pub fn gemv(&self, alpha: T, x: &Vector<M, T>, beta: T, y: &mut Vector<N, T>) {
+        if beta == T::ZERO {
+            y.entries.fill(T::ZERO);
+        } else if beta != T::ONE {
+            *y *= beta;
+        }
+        for (column, x_j) in std::iter::zip((0..M).map(|j| self.column_slice(j)), x.iter()) {
+            let c = alpha * *x_j;
+            std::iter::zip(&mut y.entries, column).for_each(|(y_i, a_ij)| *y_i += c * *a_ij);
+        }
+        count_flops(2 * N * M);
+    }
+    
+    /// whether every entry differs from the corresponding one of `other`
+    /// by at most `tolerance` in absolute value
+    pub fn approx_eq(&self, other: &Self, tolerance: T::Real) -> bool {
+        self.first_approx_mismatch(other, tolerance).is_none()
+    }
+    
+    /// position of the first entry (in column-major order) differing from
+    /// the corresponding one of `other` by more than `tolerance`
+    #[doc(hidden)]
+    pub fn first_approx_mismatch(&self, other: &Self, tolerance: T::Real) -> Option<(usize, usize)> {
+        self.entries()
+            .find(|&(i, j, x)| {
+                let difference = (x - other[(i, j)]).modulus();
+                difference.is_nan() || difference > tolerance
+            })
+            .map(|(i, j, _)| (i, j))
+    }
+    
+    /// ```text
+    /// ||A||_F = sqrt(sum_{i,j} |a_{ij}|^2)
+    /// ```
+    pub fn frobenius_norm(&self) -> T::Real {
+        self.entries.iter().map(|x| x.modulus().powi(2)).sum::<T::Real>().sqrt()
+    }
+    
+    /// maximum absolute column sum:
+    /// 
+    /// ```text
+    /// ||A||_1 = max_j sum_i |a_{ij}|
+    /// ```
+    pub fn norm_1(&self) -> T::Real {
+        (0..M)
+            .map(|j| self.column_slice(j).iter().map(|x| x.modulus()).sum::<T::Real>())
+            .fold(T::Real::ZERO, T::Real::max)
+    }
+    
+    /// maximum absolute row sum:
+    /// 
+    /// ```text
+    /// ||A||_inf = max_i sum_j |a_{ij}|
+    /// ```
+    pub fn norm_inf(&self) -> T::Real {
+        (0..N)
+            .map(|i| self.row(i).map(|x| x.modulus()).sum::<T::Real>())
+            .fold(T::Real::ZERO, T::Real::max)
+    }
+    
+    /// The number of pivots whose absolute value exceeds `tolerance`
+    /// in Gaussian elimination with partial pivoting
+    pub fn rank(&self, tolerance: T::Real) -> usize {
+        let mut a = self.clone();
+        let mut rank = 0;
+        for j in 0..M {
+            if rank == N {
+                break;
+            }
+            
+            let (i, pivot) = (rank..N)
+                .map(|i| (i, a[(i, j)]))
+                .max_by(|(_, a), (_, b)| T::Real::partial_cmp(&a.modulus(), &b.modulus()).expect("found NaN or Inf"))
+                .unwrap();
+            if pivot.modulus() <= tolerance {
+                continue;
+            }
+            
+            if i != rank {
+                a.swap_rows(i, rank);
+            }
+            for i in (rank + 1)..N {
+                let factor = a[(i, j)] / a[(rank, j)];
+                for k in j..M {
+                    let update = factor * a[(rank, k)];
+                    a[(i, k)] -= update;
+                }
+            }
+            rank += 1;
+        }
+        rank
     }
 }
 
-impl<const N: usize> Matrix<N, N> {
+impl<const N: usize, T: Scalar> Matrix<N, N, T> {
     pub fn identity() -> Self {
-        Self::from_fn(|i, j| if i == j { 1.0 } else { 0.0 })
+        Self::from_fn(|i, j| if i == j { T::ONE } else { T::ZERO })
+    }
+    
+    pub fn from_diagonal(diagonal: &Vector<N, T>) -> Self {
+        Self::from_fn(|i, j| if i == j { diagonal[i] } else { T::ZERO })
+    }
+    
+    pub fn diagonal(&self) -> Vector<N, T> {
+        Vector::<N, T>::from_fn(|i, _| self[(i, i)])
+    }
+    
+    /// |a_{ij} - a_{ji}| <= tolerance for all i, j
+    pub fn is_symmetric(&self, tolerance: T::Real) -> bool {
+        (0..N).all(|i| (0..i).all(|j| (self[(i, j)] - self[(j, i)]).modulus() <= tolerance))
+    }
+    
+    /// |a_{ij} - conj(a_{ji})| <= tolerance for all i, j,
+    /// i.e. `is_symmetric` for real matrices
+    pub fn is_hermitian(&self, tolerance: T::Real) -> bool {
+        (0..N).all(|i| (0..=i).all(|j| (self[(i, j)] - self[(j, i)].conj()).modulus() <= tolerance))
+    }
+    
+    /// strictly diagonally dominant by rows:
+    /// 
+    /// ```text
+    /// |a_{ii}| > sum_{j != i} |a_{ij}|  for all i
+    /// ```
+    pub fn is_diagonally_dominant(&self) -> bool {
+        (0..N).all(|i| {
+            let off_diagonal_sum = self.row(i).enumerate().filter(|(j, _)| *j != i).map(|(_, x)| x.modulus()).sum::<T::Real>();
+            self[(i, i)].modulus() > off_diagonal_sum
+        })
+    }
+    
+    /// symmetric (up to `tolerance`) and every pivot of
+    /// the Cholesky decomposition exceeds `tolerance`
+    pub fn is_positive_definite(&self, tolerance: T::Real) -> bool where T: Float {
+        if !self.is_symmetric(tolerance) {
+            return false;
+        }
+        
+        crate::cholesky::cholesky_with_tolerance(self, tolerance).is_ok()
+    }
+    
+    pub fn trace(&self) -> T {
+        (0..N).map(|i| self[(i, i)]).sum()
+    }
+    
+    /// Solve Ax = b with the LU decomposition with partial pivoting PA = LU.
+    /// 
+    /// Returns `None` if A is singular.
+    pub fn solve(&self, b: &Vector<N, T>) -> Option<Vector<N, T>> {
+        crate::lu::lu_decomposition(self).map(|lu| lu.solve(b))
+    }
+    
+    /// Compute A^{-1} by solving AX = I column by column
+    /// with the LU decomposition with partial pivoting PA = LU.
+    /// 
+    /// Returns `None` if A is singular.
+    pub fn inverse(&self) -> Option<Self> {
+        crate::lu::lu_decomposition(self).map(|lu| lu.inverse())
+    }
+    
+    /// 1-norm condition number estimate
+    /// 
+    /// ```text
+    /// κ_1(A) = ||A||_1 ||A^{-1}||_1
+    /// ```
+    /// 
+    /// where ||A^{-1}||_1 is estimated by Hager's method using only
+    /// a few solves with the LU factors instead of forming A^{-1}.
+    /// Returns `T::INFINITY` if A is singular.
+    pub fn condition_number_1_estimate(&self) -> T where T: Float {
+        const MAX_ITERATIONS: usize = 5;
+        
+        let Some(lu) = crate::lu::lu_decomposition(self) else {
+            return T::INFINITY;
+        };
+        
+        let mut x = Vector::<N, T>::filled_with(T::ONE / T::from_f64(N as f64));
+        let mut estimate = T::ZERO;
+        for _ in 0..MAX_ITERATIONS {
+            let y = lu.solve(&x);
+            estimate = y.iter().map(|it| it.abs()).sum::<T>();
+            
+            let xi = Vector::<N, T>::from_fn(|i, _| if y[i] >= T::ZERO { T::ONE } else { -T::ONE });
+            let z = lu.solve_transposed(&xi);
+            
+            let j = z.argmax_abs();
+            if z[j].abs() <= z.dot(&x) {
+                break;
+            }
+            x = Vector::<N, T>::from_fn(|i, _| if i == j { T::ONE } else { T::ZERO });
+        }
+        
+        self.norm_1() * estimate
     }
 }
 
-impl<const N: usize> Vector<N> {
-    pub fn iter(&self) -> std::slice::Iter<'_, f64> {
+impl<const N: usize> Matrix<N, N> {
+    /// 2-norm condition number
+    /// 
+    /// ```text
+    /// κ_2(A) = σ_max(A) / σ_min(A)
+    /// ```
+    /// 
+    /// using nalgebra's singular values. Returns `f64::INFINITY` if A is singular.
+    pub fn condition_number(&self) -> f64 {
+        let singular_values = nalgebra::DMatrix::from_column_slice(N, N, self.as_slice()).singular_values();
+        let (max, min) = (singular_values.max(), singular_values.min());
+        if min > 0.0 { max / min } else { f64::INFINITY }
+    }
+}
+
+impl<const N: usize, const M: usize> Matrix<N, M> {
+    /// Moore-Penrose pseudo-inverse by nalgebra's SVD A = U S V^T
+    /// 
+    /// ```text
+    /// A^+ = V S^+ U^T,  (S^+)_{ii} = 1 / σ_i  if σ_i > tolerance σ_max,  0 otherwise
+    /// ```
+    /// 
+    /// truncating the relatively small singular values, so that A^+ b is the minimum-norm
+    /// least squares solution even for a rank-deficient A.
+    pub fn pinv(&self, tolerance: f64) -> Matrix<M, N> {
+        let svd = nalgebra::DMatrix::from_column_slice(N, M, self.as_slice()).svd(true, true);
+        let (u, v_t) = (svd.u.expect("SVD is computed with U"), svd.v_t.expect("SVD is computed with V"));
+        let threshold = tolerance * svd.singular_values.max();
+        let mut pinv = Matrix::<M, N>::zeroed();
+        for (k, &sigma) in svd.singular_values.iter().enumerate().filter(|&(_, &sigma)| sigma > threshold) {
+            for j in 0..N {
+                for i in 0..M {
+                    pinv[(i, j)] += v_t[(k, i)] * u[(j, k)] / sigma;
+                }
+            }
+        }
+        pinv
+    }
+}
+
+impl<const N: usize, T: Scalar> Vector<N, T> {
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
         self.as_ref().iter()
     }
     
-    pub fn norm(&self) -> f64 {
-        self.as_ref().iter().map(|x| x * x).sum::<f64>().sqrt()
+    /// in-place update without allocation:
+    /// 
+    /// ```text
+    /// y <- alpha x + y
+    /// ```
+    pub fn axpy(&mut self, alpha: T, x: &Self) {
+        std::iter::zip(&mut self.entries, x.iter()).for_each(|(y_i, x_i)| *y_i += alpha * *x_i);
+        count_flops(2 * N);
+    }
+    
+    /// N evenly spaced points from `a` to `b` (both inclusive)
+    pub fn linspace(a: T, b: T) -> Self where T: Float {
+        let step = if N > 1 { (b - a) / T::from_f64((N - 1) as f64) } else { T::ZERO };
+        Self::from_fn(|i, _| a + step * T::from_f64(i as f64))
+    }
+    
+    pub fn sum(&self) -> T {
+        self.iter().copied().sum()
+    }
+    
+    pub fn mean(&self) -> T {
+        self.sum() / T::from_real(T::Real::from_f64(N as f64))
+    }
+    
+    pub fn min(&self) -> T where T: Float {
+        self.iter().copied().min_by(|a, b| T::partial_cmp(a, b).expect("found NaN")).expect("Vector is empty")
+    }
+    
+    pub fn max(&self) -> T where T: Float {
+        self.iter().copied().max_by(|a, b| T::partial_cmp(a, b).expect("found NaN")).expect("Vector is empty")
+    }
+    
+    /// index of the entry with the largest absolute value
+    pub fn argmax_abs(&self) -> usize {
+        let (i, _max_abs) = self
+            .iter()
+            .enumerate()
+            .max_by(|(_, p), (_, q)| T::Real::partial_cmp(&p.modulus(), &q.modulus()).expect("found NaN"))
+            .expect("Vector is empty");
+        i
+    }
+    
+    /// ```text
+    /// ||x|| = sqrt((x, x)) = sqrt(sum_i |x_i|^2)
+    /// ```
+    pub fn norm(&self) -> T::Real {
+        self.as_ref().iter().map(|x| x.modulus().powi(2)).sum::<T::Real>().sqrt()
+    }
+    
+    /// ```text
+    /// ||x||_p = (sum_i |x_i|^p)^{1/p}
+    /// ```
+    /// 
+    /// (for p = ∞, `norm_inf` of `Matrix` already gives max_i |x_i| for vectors)
+    pub fn norm_p(&self, p: T::Real) -> T::Real {
+        if p.is_infinite() {
+            self.norm_inf()
+        } else {
+            self.iter().map(|x| x.modulus().powf(p)).sum::<T::Real>().powf(T::Real::ONE / p)
+        }
+    }
+    
+    /// angle θ ∈ [0, π] between the two vectors:
+    /// 
+    /// ```text
+    /// cos θ = (x, y) / (||x|| ||y||)
+    /// ```
+    pub fn angle_to(&self, other: &Self) -> T where T: Float {
+        (self.dot(other) / (self.norm() * other.norm())).clamp(-T::ONE, T::ONE).acos()
+    }
+    
+    /// orthogonal projection onto the line spanned by `other`:
+    /// 
+    /// ```text
+    /// (y, x) / (y, y) y
+    /// ```
+    pub fn project_onto(&self, other: &Self) -> Self {
+        other * (other.dot(self) / other.dot(other))
     }
     
     pub fn normalize(&mut self) {
         let norm = self.norm();
-        *self /= norm;
+        *self /= T::from_real(norm);
     }
     pub fn normalized(&self) -> Self {
         let mut result = self.clone();
@@ -125,191 +591,760 @@ impl<const N: usize> Vector<N> {
         result
     }
     
-    pub fn dot(&self, rhs: &Self) -> f64 {
-        let result_matrix: Matrix<1, 1> = self.transpose() * rhs;
+    /// inner product conjugating `self`:
+    /// 
+    /// ```text
+    /// (x, y) = x^* y = sum_i conj(x_i) y_i
+    /// ```
+    pub fn dot(&self, rhs: &Self) -> T {
+        let result_matrix: Matrix<1, 1, T> = self.adjoint() * rhs;
         result_matrix[(0, 0)]
     }
 }
 
+/// random matrix constructors
+impl<const N: usize, const M: usize> Matrix<N, M> {
+    /// entries independently drawn from the uniform distribution on [lo, hi]
+    pub fn random_uniform(lo: f64, hi: f64) -> Self {
+        use rand::Rng;
+        crate::random::with_rng(|rng| Self::from_fn(|_, _| rng.random_range(lo..=hi)))
+    }
+    
+    /// entries independently drawn from the normal distribution N(mu, sigma^2)
+    /// by the Box-Muller transform
+    pub fn random_normal(mu: f64, sigma: f64) -> Self {
+        use rand::Rng;
+        crate::random::with_rng(|rng| Self::from_fn(|_, _| {
+            let (u1, u2): (f64, f64) = (1.0 - rng.random::<f64>(), rng.random());
+            mu + sigma * (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
+        }))
+    }
+}
+impl<const N: usize> Matrix<N, N> {
+    /// Haar-distributed random orthogonal matrix, obtained as Q of
+    /// the QR decomposition (by modified Gram-Schmidt) of a Gaussian matrix
+    pub fn random_orthogonal() -> Self {
+        crate::qr_modified_gram_schmidt(&Self::random_normal(0.0, 1.0)).0
+    }
+    
+    /// random symmetric positive definite matrix
+    /// 
+    /// ```text
+    /// B^T B + N I
+    /// ```
+    /// 
+    /// where the entries of B are uniform on [-1, 1]
+    pub fn random_spd() -> Self {
+        let b = Self::random_uniform(-1.0, 1.0);
+        b.transpose() * &b + Self::identity() * (N as f64)
+    }
+    
+    /// random strictly diagonally dominant matrix with the off-diagonal entries
+    /// uniform on [-1, 1] and
+    /// 
+    /// ```text
+    /// a_{ii} = 2 sum_{j != i} |a_{ij}|
+    /// ```
+    /// 
+    /// for which the Jacobi and Gauss-Seidel iterations converge (the Jacobi one by the rate <= 1/2)
+    pub fn random_diagonally_dominant() -> Self {
+        let mut a = Self::random_uniform(-1.0, 1.0);
+        for i in 0..N {
+            a[(i, i)] = 2.0 * (0..N).filter(|&j| j != i).map(|j| a[(i, j)].abs()).sum::<f64>();
+        }
+        a
+    }
+    
+    /// random symmetric matrix with the prescribed eigenvalues
+    /// 
+    /// ```text
+    /// Q D Q^T
+    /// ```
+    /// 
+    /// where Q is `random_orthogonal`
+    pub fn random_with_spectrum(eigenvalues: &DiagonalMatrix<N>) -> Self {
+        let q = Self::random_orthogonal();
+        &(&q * eigenvalues) * &q.transpose()
+    }
+    
+    /// random matrix with the prescribed singular values
+    /// 
+    /// ```text
+    /// U S V^T
+    /// ```
+    /// 
+    /// where U, V are independently `random_orthogonal`
+    pub fn random_with_singular_values(singular_values: &DiagonalMatrix<N>) -> Self {
+        let (u, v) = (Self::random_orthogonal(), Self::random_orthogonal());
+        &(&u * singular_values) * &v.transpose()
+    }
+    
+    /// `random_with_singular_values` from 1 down to 1 / `condition_number` graded by `profile`,
+    /// so that κ_2 = `condition_number` exactly
+    pub fn random_with_condition_number(condition_number: f64, profile: crate::SingularValueProfile) -> Self {
+        Self::random_with_singular_values(&profile.singular_values(condition_number))
+    }
+}
+
 const _: () = {
-    pub struct Column<'a>(std::slice::Iter<'a, f64>);
-    impl<'a> Iterator for Column<'a> {
-        type Item = f64;
+    pub struct Column<'a, T>(std::slice::Iter<'a, T>);
+    impl<'a, T: Scalar> Iterator for Column<'a, T> {
+        type Item = T;
+        fn next(&mut self) -> Option<Self::Item> {
+            self.0.next().copied()
+        }
+    }
+    
+    pub struct ColumnMut<'a, T>(std::slice::IterMut<'a, T>);
+    impl<'a, T: Scalar> Iterator for ColumnMut<'a, T> {
+        type Item = &'a mut T;
+        fn next(&mut self) -> Option<Self::Item> {
+            self.0.next()
+        }
+    }
+    
+    /// every N-th entry of the column-major storage from `a_{i0}`
+    pub struct Row<'a, T>(std::iter::StepBy<std::slice::Iter<'a, T>>);
+    impl<'a, T: Scalar> Iterator for Row<'a, T> {
+        type Item = T;
         fn next(&mut self) -> Option<Self::Item> {
             self.0.next().copied()
         }
     }
     
-    pub struct ColumnMut<'a>(std::slice::IterMut<'a, f64>);
-    impl<'a> Iterator for ColumnMut<'a> {
-        type Item = &'a mut f64;
+    pub struct RowMut<'a, T>(std::iter::StepBy<std::slice::IterMut<'a, T>>);
+    impl<'a, T: Scalar> Iterator for RowMut<'a, T> {
+        type Item = &'a mut T;
         fn next(&mut self) -> Option<Self::Item> {
             self.0.next()
         }
     }
     
-    impl<const N: usize, const M: usize> Matrix<N, M> {
-        pub fn column(&self, j: usize) -> Column<'_> {
-            Column(self.columns[j].iter())
+    pub struct Rows<'a, T> {
+        entries: &'a [T],
+        stride: usize,
+        i: std::ops::Range<usize>,
+    }
+    impl<'a, T: Scalar> Iterator for Rows<'a, T> {
+        type Item = Row<'a, T>;
+        fn next(&mut self) -> Option<Self::Item> {
+            self.i.next().map(|i| Row(self.entries[i..].iter().step_by(self.stride)))
+        }
+    }
+    
+    impl<const N: usize, const M: usize, T: Scalar> Matrix<N, M, T> {
+        pub fn column(&self, j: usize) -> Column<'_, T> {
+            Column(self.column_slice(j).iter())
         }
         
-        pub fn column_mut(&mut self, j: usize) -> ColumnMut<'_> {
-            ColumnMut(self.columns[j].iter_mut())
+        pub fn column_mut(&mut self, j: usize) -> ColumnMut<'_, T> {
+            ColumnMut(self.column_slice_mut(j).iter_mut())
+        }
+        
+        pub fn row(&self, i: usize) -> Row<'_, T> {
+            assert!(i < N, "row index out of bounds");
+            Row(self.entries[i..].iter().step_by(N))
+        }
+        
+        pub fn row_mut(&mut self, i: usize) -> RowMut<'_, T> {
+            assert!(i < N, "row index out of bounds");
+            RowMut(self.entries[i..].iter_mut().step_by(N))
+        }
+        
+        /// iterate over rows from top to bottom
+        pub fn rows(&self) -> Rows<'_, T> {
+            Rows { entries: &self.entries, stride: N, i: 0..N }
+        }
+        
+        /// iterate over all `(i, j, a_{ij})` in column-major order
+        pub fn entries(&self) -> impl Iterator<Item = (usize, usize, T)> + '_ {
+            self.entries.iter().enumerate().map(|(k, x)| (k % N, k / N, *x))
+        }
+        
+        /// iterate over all `(i, j, &mut a_{ij})` in column-major order
+        pub fn entries_mut(&mut self) -> impl Iterator<Item = (usize, usize, &mut T)> + '_ {
+            self.entries.iter_mut().enumerate().map(|(k, x)| (k % N, k / N, x))
         }
     }
 };
 
-impl<const N: usize, const M: usize> std::fmt::Debug for Matrix<N, M> {
+const _: () = {
+    /// mutable view of an R-rows and C-columns sub-block
+    pub struct BlockMut<'a, const R: usize, const C: usize, T> {
+        /// whole columns of the block
+        entries: &'a mut [T],
+        stride: usize,
+        row_offset: usize,
+    }
+    impl<const R: usize, const C: usize, T: Scalar> std::ops::Index<(usize, usize)> for BlockMut<'_, R, C, T> {
+        type Output = T;
+        fn index(&self, (i, j): (usize, usize)) -> &Self::Output {
+            assert!(i < R && j < C, "block index out of bounds");
+            &self.entries[j * self.stride + self.row_offset + i]
+        }
+    }
+    impl<const R: usize, const C: usize, T: Scalar> std::ops::IndexMut<(usize, usize)> for BlockMut<'_, R, C, T> {
+        fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut Self::Output {
+            assert!(i < R && j < C, "block index out of bounds");
+            &mut self.entries[j * self.stride + self.row_offset + i]
+        }
+    }
+    impl<const R: usize, const C: usize, T: Scalar> BlockMut<'_, R, C, T> {
+        pub fn to_matrix(&self) -> Matrix<R, C, T> {
+            Matrix::<R, C, T>::from_fn(|i, j| self[(i, j)])
+        }
+        
+        pub fn copy_from(&mut self, source: &Matrix<R, C, T>) {
+            (0..R).for_each(|i| (0..C).for_each(|j| self[(i, j)] = source[(i, j)]));
+        }
+    }
+    
+    impl<const N: usize, const M: usize, T: Scalar> Matrix<N, M, T> {
+        /// copy of the R-rows and C-columns sub-block
+        /// whose top-left entry is `self[(row_offset, column_offset)]`
+        pub fn block<const R: usize, const C: usize>(&self, row_offset: usize, column_offset: usize) -> Matrix<R, C, T> {
+            assert!(row_offset + R <= N && column_offset + C <= M, "block exceeds matrix bounds");
+            Matrix::<R, C, T>::from_fn(|i, j| self[(row_offset + i, column_offset + j)])
+        }
+        
+        /// mutable view of the R-rows and C-columns sub-block
+        /// whose top-left entry is `self[(row_offset, column_offset)]`
+        pub fn block_mut<const R: usize, const C: usize>(&mut self, row_offset: usize, column_offset: usize) -> BlockMut<'_, R, C, T> {
+            assert!(row_offset + R <= N && column_offset + C <= M, "block exceeds matrix bounds");
+            BlockMut {
+                entries: &mut self.entries[(column_offset * N)..((column_offset + C) * N)],
+                stride: N,
+                row_offset,
+            }
+        }
+    }
+};
+
+/// Lightweight view of a sub-block selected by ranges at runtime,
+/// obtained by `Matrix::view((rows, columns))`
+#[derive(Clone, Copy)]
+pub struct MatrixView<'a, T = f64> {
+    /// the top-left entry of the view, `a_{ij}` of the view at `i + j * stride` from it
+    ptr: *const T,
+    stride: usize,
+    nrows: usize,
+    ncols: usize,
+    _borrow: std::marker::PhantomData<&'a [T]>,
+}
+
+/// Mutable counterpart of `MatrixView`,
+/// obtained by `Matrix::view_mut((rows, columns))` or `Matrix::split_at_mut`
+pub struct MatrixViewMut<'a, T = f64> {
+    ptr: *mut T,
+    stride: usize,
+    nrows: usize,
+    ncols: usize,
+    _borrow: std::marker::PhantomData<&'a mut [T]>,
+}
+
+// SAFETY: the views are a shared and a mutable borrow of the entries they point to
+unsafe impl<T: Sync> Send for MatrixView<'_, T> {}
+unsafe impl<T: Sync> Sync for MatrixView<'_, T> {}
+unsafe impl<T: Send> Send for MatrixViewMut<'_, T> {}
+unsafe impl<T: Sync> Sync for MatrixViewMut<'_, T> {}
+
+fn resolve_range(range: impl std::ops::RangeBounds<usize>, len: usize) -> std::ops::Range<usize> {
+    use std::ops::Bound;
+    
+    let start = match range.start_bound() {
+        Bound::Included(&start) => start,
+        Bound::Excluded(&start) => start + 1,
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&end) => end + 1,
+        Bound::Excluded(&end) => end,
+        Bound::Unbounded => len,
+    };
+    assert!(start <= end && end <= len, "range {start}..{end} out of bounds for length {len}");
+    start..end
+}
+
+impl<const N: usize, const M: usize, T: Scalar> Matrix<N, M, T> {
+    /// view of the sub-block selected by row and column ranges like `m.view((2..5, 0..3))`
+    pub fn view(
+        &self,
+        (rows, columns): (impl std::ops::RangeBounds<usize>, impl std::ops::RangeBounds<usize>),
+    ) -> MatrixView<'_, T> {
+        let (rows, columns) = (resolve_range(rows, N), resolve_range(columns, M));
+        MatrixView {
+            // wrapping for the empty views at the end
+            ptr: self.entries.as_ptr().wrapping_add(columns.start * N + rows.start),
+            stride: N,
+            nrows: rows.len(),
+            ncols: columns.len(),
+            _borrow: std::marker::PhantomData,
+        }
+    }
+    
+    /// mutable view of the sub-block selected by row and column ranges like `m.view_mut((2..5, 0..3))`
+    pub fn view_mut(
+        &mut self,
+        (rows, columns): (impl std::ops::RangeBounds<usize>, impl std::ops::RangeBounds<usize>),
+    ) -> MatrixViewMut<'_, T> {
+        let (rows, columns) = (resolve_range(rows, N), resolve_range(columns, M));
+        let [_, _, _, view] = self.split_at_mut((rows.start, columns.start));
+        MatrixViewMut { nrows: rows.len(), ncols: columns.len(), ..view }
+    }
+    
+    /// disjoint mutable views `[A11, A12, A21, A22]` of the blocks split before the i-th row and the j-th column
+    /// 
+    /// ```text
+    /// [A11 A12]
+    /// [A21 A22]
+    /// ```
+    /// 
+    /// to update a block by the others in place, e.g. the Schur-complement update of blocked LU:
+    /// 
+    /// ```text
+    /// let [_, a12, a21, mut a22] = a.split_at_mut((k, k));
+    /// a22.gemm(-1.0, a21.as_view(), a12.as_view(), 1.0);
+    /// ```
+    pub fn split_at_mut(&mut self, (i, j): (usize, usize)) -> [MatrixViewMut<'_, T>; 4] {
+        assert!(i <= N && j <= M, "split ({i}, {j}) out of bounds for {N} x {M} matrix");
+        // all the views from the one pointer, each accessing only its own entries
+        let ptr = self.entries.as_mut_ptr();
+        let block = |row_offset: usize, column_offset: usize, nrows: usize, ncols: usize| MatrixViewMut {
+            ptr: ptr.wrapping_add(column_offset * N + row_offset),
+            stride: N,
+            nrows,
+            ncols,
+            _borrow: std::marker::PhantomData,
+        };
+        [block(0, 0, i, j), block(0, j, i, M - j), block(i, 0, N - i, j), block(i, j, N - i, M - j)]
+    }
+}
+
+impl<'a, T: Scalar> MatrixView<'a, T> {
+    pub fn nrows(&self) -> usize {
+        self.nrows
+    }
+    pub fn ncols(&self) -> usize {
+        self.ncols
+    }
+    
+    /// j-th column of the view as a slice
+    pub fn column(&self, j: usize) -> &'a [T] {
+        assert!(j < self.ncols, "view column index out of bounds");
+        // SAFETY: the column is within the borrowed matrix (checked on construction)
+        unsafe { std::slice::from_raw_parts(self.ptr.wrapping_add(j * self.stride), self.nrows) }
+    }
+    
+    /// copy into a matrix of the statically known size
+    pub fn to_matrix<const R: usize, const C: usize>(&self) -> Matrix<R, C, T> {
+        assert!(R == self.nrows() && C == self.ncols(), "view size does not match matrix size");
+        Matrix::<R, C, T> { entries: (0..C).flat_map(|j| self.column(j)).copied().collect() }
+    }
+    
+    /// copy into a matrix of the runtime size
+    pub fn to_dyn_matrix(&self) -> DynMatrix<T> {
+        DynMatrix::from_fn(self.nrows(), self.ncols(), |i, j| self[(i, j)])
+    }
+    
+    /// product with a vector whose length is `ncols()`
+    pub fn mul_slice(&self, x: &[T]) -> Vec<T> {
+        assert!(x.len() == self.ncols(), "vector length does not match view columns");
+        count_flops(2 * self.nrows() * self.ncols());
+        let mut y = vec![T::ZERO; self.nrows()];
+        for (j, x_j) in x.iter().enumerate() {
+            std::iter::zip(&mut y, self.column(j)).for_each(|(y_i, a_ij)| *y_i += *x_j * *a_ij);
+        }
+        y
+    }
+    
+    fn count_entrywise_flops(&self) {
+        count_flops(self.nrows() * self.ncols());
+    }
+}
+
+impl<T: Scalar> MatrixViewMut<'_, T> {
+    pub fn nrows(&self) -> usize {
+        self.nrows
+    }
+    pub fn ncols(&self) -> usize {
+        self.ncols
+    }
+    
+    pub fn as_view(&self) -> MatrixView<'_, T> {
+        MatrixView {
+            ptr: self.ptr,
+            stride: self.stride,
+            nrows: self.nrows,
+            ncols: self.ncols,
+            _borrow: std::marker::PhantomData,
+        }
+    }
+    
+    pub fn column_mut(&mut self, j: usize) -> &mut [T] {
+        assert!(j < self.ncols, "view column index out of bounds");
+        // SAFETY: the column is within the mutably borrowed entries of this view
+        unsafe { std::slice::from_raw_parts_mut(self.ptr.wrapping_add(j * self.stride), self.nrows) }
+    }
+    
+    pub fn copy_from(&mut self, source: MatrixView<'_, T>) {
+        assert!(source.nrows() == self.nrows() && source.ncols() == self.ncols(), "view sizes do not match");
+        (0..self.ncols()).for_each(|j| self.column_mut(j).copy_from_slice(source.column(j)));
+    }
+    
+    /// in-place matrix-matrix product without allocation:
+    /// 
+    /// ```text
+    /// C <- alpha A B + beta C
+    /// ```
+    /// 
+    /// e.g. the Schur-complement update A22 <- A22 - A21 A12 of blocked LU
+    /// by the blocks of `Matrix::split_at_mut`
+    pub fn gemm(&mut self, alpha: T, a: MatrixView<'_, T>, b: MatrixView<'_, T>, beta: T) {
+        assert!(
+            a.nrows() == self.nrows() && b.ncols() == self.ncols() && a.ncols() == b.nrows(),
+            "view sizes do not match for multiplication"
+        );
+        count_flops(2 * self.nrows() * self.ncols() * a.ncols());
+        for j in 0..self.ncols() {
+            let c_j = self.column_mut(j);
+            if beta == T::ZERO {
+                c_j.fill(T::ZERO);
+            } else if beta != T::ONE {
+                c_j.iter_mut().for_each(|c_ij| *c_ij *= beta);
+            }
+            for (k, b_kj) in b.column(j).iter().enumerate() {
+                let factor = alpha * *b_kj;
+                std::iter::zip(&mut *c_j, a.column(k)).for_each(|(c_ij, a_ik)| *c_ij += factor * *a_ik);
+            }
+        }
+    }
+}
+
+impl<T: Scalar> std::ops::Index<(usize, usize)> for MatrixView<'_, T> {
+    type Output = T;
+    fn index(&self, (i, j): (usize, usize)) -> &Self::Output {
+        assert!(i < self.nrows() && j < self.ncols(), "view index out of bounds");
+        &self.column(j)[i]
+    }
+}
+impl<T: Scalar> std::ops::Index<(usize, usize)> for MatrixViewMut<'_, T> {
+    type Output = T;
+    fn index(&self, (i, j): (usize, usize)) -> &Self::Output {
+        assert!(i < self.nrows() && j < self.ncols(), "view index out of bounds");
+        &self.as_view().column(j)[i]
+    }
+}
+impl<T: Scalar> std::ops::IndexMut<(usize, usize)> for MatrixViewMut<'_, T> {
+    fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut Self::Output {
+        assert!(i < self.nrows() && j < self.ncols(), "view index out of bounds");
+        &mut self.column_mut(j)[i]
+    }
+}
+
+impl<T: Scalar> std::ops::Add for MatrixView<'_, T> {
+    type Output = DynMatrix<T>;
+    fn add(self, rhs: Self) -> Self::Output {
+        assert!(rhs.nrows() == self.nrows() && rhs.ncols() == self.ncols(), "view sizes do not match");
+        self.count_entrywise_flops();
+        DynMatrix::from_fn(self.nrows(), self.ncols(), |i, j| self[(i, j)] + rhs[(i, j)])
+    }
+}
+impl<T: Scalar> std::ops::Sub for MatrixView<'_, T> {
+    type Output = DynMatrix<T>;
+    fn sub(self, rhs: Self) -> Self::Output {
+        assert!(rhs.nrows() == self.nrows() && rhs.ncols() == self.ncols(), "view sizes do not match");
+        self.count_entrywise_flops();
+        DynMatrix::from_fn(self.nrows(), self.ncols(), |i, j| self[(i, j)] - rhs[(i, j)])
+    }
+}
+impl<T: Scalar> std::ops::Mul<T> for MatrixView<'_, T> {
+    type Output = DynMatrix<T>;
+    fn mul(self, rhs: T) -> Self::Output {
+        self.count_entrywise_flops();
+        DynMatrix::from_fn(self.nrows(), self.ncols(), |i, j| self[(i, j)] * rhs)
+    }
+}
+impl<T: Scalar> std::ops::Mul for MatrixView<'_, T> {
+    type Output = DynMatrix<T>;
+    fn mul(self, rhs: Self) -> Self::Output {
+        assert!(self.ncols() == rhs.nrows(), "view sizes do not match for multiplication");
+        count_flops(2 * self.nrows() * self.ncols() * rhs.ncols());
+        let mut product = DynMatrix::zeroed(self.nrows(), rhs.ncols());
+        for j in 0..rhs.ncols() {
+            let c_j = product.column_mut(j);
+            for (k, b_kj) in rhs.column(j).iter().enumerate() {
+                std::iter::zip(&mut *c_j, self.column(k)).for_each(|(c_ij, a_ik)| *c_ij += *a_ik * *b_kj);
+            }
+        }
+        product
+    }
+}
+
+impl<T: Scalar> std::ops::AddAssign<MatrixView<'_, T>> for MatrixViewMut<'_, T> {
+    fn add_assign(&mut self, rhs: MatrixView<'_, T>) {
+        assert!(rhs.nrows() == self.nrows() && rhs.ncols() == self.ncols(), "view sizes do not match");
+        (0..self.ncols()).for_each(|j| {
+            std::iter::zip(self.column_mut(j), rhs.column(j)).for_each(|(x, y)| *x += *y);
+        });
+    }
+}
+impl<T: Scalar> std::ops::SubAssign<MatrixView<'_, T>> for MatrixViewMut<'_, T> {
+    fn sub_assign(&mut self, rhs: MatrixView<'_, T>) {
+        assert!(rhs.nrows() == self.nrows() && rhs.ncols() == self.ncols(), "view sizes do not match");
+        (0..self.ncols()).for_each(|j| {
+            std::iter::zip(self.column_mut(j), rhs.column(j)).for_each(|(x, y)| *x -= *y);
+        });
+    }
+}
+impl<T: Scalar> std::ops::MulAssign<T> for MatrixViewMut<'_, T> {
+    fn mul_assign(&mut self, rhs: T) {
+        (0..self.ncols()).for_each(|j| self.column_mut(j).iter_mut().for_each(|x| *x *= rhs));
+    }
+}
+
+impl<const N: usize, const M: usize, T: Scalar> std::fmt::Debug for Matrix<N, M, T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         if M == 1 {
-            self.columns[0].fmt(f)
+            self.entries.fmt(f)
+        } else {
+            // row-major, unlike the internal storage
+            f.debug_list().entries(self.rows().map(|row| row.collect::<Vec<_>>())).finish()
+        }
+    }
+}
+
+/// Row by row with aligned columns.
+/// The precision defaults to 6 and can be specified as `{:.3}`.
+impl<const N: usize, const M: usize, T: Scalar> std::fmt::Display for Matrix<N, M, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let precision = f.precision().unwrap_or(6);
+        let formatted = self
+            .rows()
+            .map(|row| row.map(|x| format!("{x:.precision$}")).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        let width = formatted.iter().flatten().map(|s| s.chars().count()).max().unwrap_or(0);
+        for (i, row) in formatted.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "[")?;
+            for (j, x) in row.iter().enumerate() {
+                if j > 0 {
+                    write!(f, " ")?;
+                }
+                write!(f, "{x:>width$}")?;
+            }
+            write!(f, "]")?;
+        }
+        Ok(())
+    }
+}
+
+/// Serialized row-major as a sequence of rows, or as a flat sequence for `Vector`
+#[cfg(feature = "serde")]
+impl<const N: usize, const M: usize, T: Scalar + serde::Serialize> serde::Serialize for Matrix<N, M, T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeSeq;
+        
+        if M == 1 {
+            self.entries.serialize(serializer)
+        } else {
+            let mut seq = serializer.serialize_seq(Some(N))?;
+            for row in self.rows() {
+                seq.serialize_element(&row.collect::<Vec<_>>())?;
+            }
+            seq.end()
+        }
+    }
+}
+#[cfg(feature = "serde")]
+impl<'de, const N: usize, const M: usize, T: Scalar + serde::Deserialize<'de>> serde::Deserialize<'de> for Matrix<N, M, T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+        
+        if M == 1 {
+            let entries = Vec::<T>::deserialize(deserializer)?;
+            (entries.len() == N)
+                .then_some(Self { entries })
+                .ok_or_else(|| D::Error::custom("sequence length does not match vector size"))
         } else {
-            self.columns.fmt(f)
+            let rows = Vec::<Vec<T>>::deserialize(deserializer)?;
+            Self::try_from(&*rows.iter().map(Vec::as_slice).collect::<Vec<_>>()).map_err(D::Error::custom)
         }
     }
 }
 
-impl<const N: usize, const M: usize> std::ops::Index<(usize, usize)> for Matrix<N, M> {
-    type Output = f64;
+impl<const N: usize, const M: usize, T: Scalar> std::ops::Index<(usize, usize)> for Matrix<N, M, T> {
+    type Output = T;
     fn index(&self, (i, j): (usize, usize)) -> &Self::Output {
-        &self.columns[j][i]
+        assert!(i < N && j < M, "index out of bounds");
+        &self.entries[j * N + i]
     }
 }
-impl<const N: usize, const M: usize> std::ops::IndexMut<(usize, usize)> for Matrix<N, M> {
+impl<const N: usize, const M: usize, T: Scalar> std::ops::IndexMut<(usize, usize)> for Matrix<N, M, T> {
     fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut Self::Output {
-        &mut self.columns[j][i]
+        assert!(i < N && j < M, "index out of bounds");
+        &mut self.entries[j * N + i]
     }
 }
 
-impl<const N: usize> std::ops::Index<usize> for Vector<N> {
-    type Output = f64;
+impl<const N: usize, T: Scalar> std::ops::Index<usize> for Vector<N, T> {
+    type Output = T;
     fn index(&self, i: usize) -> &Self::Output {
-        &self.columns[0][i]
+        &self.entries[i]
     }
 }
-impl<const N: usize> std::ops::IndexMut<usize> for Vector<N> {
+impl<const N: usize, T: Scalar> std::ops::IndexMut<usize> for Vector<N, T> {
     fn index_mut(&mut self, i: usize) -> &mut Self::Output {
-        &mut self.columns[0][i]
+        &mut self.entries[i]
     }
 }
 
-impl<const N: usize, const M: usize> std::ops::Add for &Matrix<N, M> {
-    type Output = Matrix<N, M>;
+impl<const N: usize, const M: usize, T: Scalar> std::ops::Add for &Matrix<N, M, T> {
+    type Output = Matrix<N, M, T>;
     fn add(self, rhs: Self) -> Self::Output {
-        Matrix::from_fn(|i, j| self[(i, j)] + rhs[(i, j)])
+        self.count_entrywise_flops();
+        self.zip_map(rhs, |x, y| x + y)
     }
 }
-impl<const N: usize, const M: usize> std::ops::Add<Matrix<N, M>> for Matrix<N, M> {
+impl<const N: usize, const M: usize, T: Scalar> std::ops::Add<Matrix<N, M, T>> for Matrix<N, M, T> {
     type Output = Self;
     fn add(self, rhs: Self) -> Self::Output { &self + &rhs }
 }
-impl<const N: usize, const M: usize> std::ops::Add<&Matrix<N, M>> for Matrix<N, M> {
+impl<const N: usize, const M: usize, T: Scalar> std::ops::Add<&Matrix<N, M, T>> for Matrix<N, M, T> {
     type Output = Self;
-    fn add(self, rhs: &Matrix<N, M>) -> Self::Output { &self + rhs }
+    fn add(self, rhs: &Matrix<N, M, T>) -> Self::Output { &self + rhs }
 }
-impl<const N: usize, const M: usize> std::ops::Add<Matrix<N, M>> for &Matrix<N, M> {
-    type Output = Matrix<N, M>;
-    fn add(self, rhs: Matrix<N, M>) -> Self::Output { self + &rhs }
+impl<const N: usize, const M: usize, T: Scalar> std::ops::Add<Matrix<N, M, T>> for &Matrix<N, M, T> {
+    type Output = Matrix<N, M, T>;
+    fn add(self, rhs: Matrix<N, M, T>) -> Self::Output { self + &rhs }
 }
 
-impl<const N: usize, const M: usize> std::ops::AddAssign<&Matrix<N, M>> for Matrix<N, M> {
-    fn add_assign(&mut self, rhs: &Matrix<N, M>) {
-        (0..N).for_each(|i| (0..M).for_each(|j| self[(i, j)] += rhs[(i, j)]));
+impl<const N: usize, const M: usize, T: Scalar> std::ops::AddAssign<&Matrix<N, M, T>> for Matrix<N, M, T> {
+    fn add_assign(&mut self, rhs: &Matrix<N, M, T>) {
+        self.zip_map_inplace(rhs, |x, y| x + y);
+        self.count_entrywise_flops();
     }
 }
-impl<const N: usize, const M: usize> std::ops::AddAssign<Matrix<N, M>> for Matrix<N, M> {
-    fn add_assign(&mut self, rhs: Matrix<N, M>) {
+impl<const N: usize, const M: usize, T: Scalar> std::ops::AddAssign<Matrix<N, M, T>> for Matrix<N, M, T> {
+    fn add_assign(&mut self, rhs: Matrix<N, M, T>) {
         *self += &rhs;
     }
 }
 
-impl<const N: usize, const M: usize> std::ops::Sub for &Matrix<N, M> {
-    type Output = Matrix<N, M>;
+impl<const N: usize, const M: usize, T: Scalar> std::ops::Neg for &Matrix<N, M, T> {
+    type Output = Matrix<N, M, T>;
+    fn neg(self) -> Self::Output {
+        self.map(|x| -x)
+    }
+}
+impl<const N: usize, const M: usize, T: Scalar> std::ops::Neg for Matrix<N, M, T> {
+    type Output = Self;
+    fn neg(mut self) -> Self::Output {
+        self.map_inplace(|x| -x);
+        self
+    }
+}
+
+impl<const N: usize, const M: usize, T: Scalar> std::ops::Sub for &Matrix<N, M, T> {
+    type Output = Matrix<N, M, T>;
     fn sub(self, rhs: Self) -> Self::Output {
-        Matrix::from_fn(|i, j| self[(i, j)] - rhs[(i, j)])
+        self.count_entrywise_flops();
+        self.zip_map(rhs, |x, y| x - y)
     }
 }
-impl<const N: usize, const M: usize> std::ops::Sub<Matrix<N, M>> for Matrix<N, M> {
+impl<const N: usize, const M: usize, T: Scalar> std::ops::Sub<Matrix<N, M, T>> for Matrix<N, M, T> {
     type Output = Self;
     fn sub(self, rhs: Self) -> Self::Output { &self - &rhs }
 }
-impl<const N: usize, const M: usize> std::ops::Sub<&Matrix<N, M>> for Matrix<N, M> {
+impl<const N: usize, const M: usize, T: Scalar> std::ops::Sub<&Matrix<N, M, T>> for Matrix<N, M, T> {
     type Output = Self;
-    fn sub(self, rhs: &Matrix<N, M>) -> Self::Output { &self - rhs }
+    fn sub(self, rhs: &Matrix<N, M, T>) -> Self::Output { &self - rhs }
 }
-impl<const N: usize, const M: usize> std::ops::Sub<Matrix<N, M>> for &Matrix<N, M> {
-    type Output = Matrix<N, M>;
-    fn sub(self, rhs: Matrix<N, M>) -> Self::Output { self - &rhs }
+impl<const N: usize, const M: usize, T: Scalar> std::ops::Sub<Matrix<N, M, T>> for &Matrix<N, M, T> {
+    type Output = Matrix<N, M, T>;
+    fn sub(self, rhs: Matrix<N, M, T>) -> Self::Output { self - &rhs }
 }
 
-impl<const N: usize, const M: usize> std::ops::SubAssign<&Matrix<N, M>> for Matrix<N, M> {
-    fn sub_assign(&mut self, rhs: &Matrix<N, M>) {
-        (0..N).for_each(|i| (0..M).for_each(|j| self[(i, j)] -= rhs[(i, j)]));
+impl<const N: usize, const M: usize, T: Scalar> std::ops::SubAssign<&Matrix<N, M, T>> for Matrix<N, M, T> {
+    fn sub_assign(&mut self, rhs: &Matrix<N, M, T>) {
+        self.zip_map_inplace(rhs, |x, y| x - y);
+        self.count_entrywise_flops();
     }
 }
-impl<const N: usize, const M: usize> std::ops::SubAssign<Matrix<N, M>> for Matrix<N, M> {
-    fn sub_assign(&mut self, rhs: Matrix<N, M>) {
+impl<const N: usize, const M: usize, T: Scalar> std::ops::SubAssign<Matrix<N, M, T>> for Matrix<N, M, T> {
+    fn sub_assign(&mut self, rhs: Matrix<N, M, T>) {
         *self -= &rhs;
     }
 }
 
-impl<const N: usize, const M: usize, const L: usize> std::ops::Mul<&Matrix<M, L>> for &Matrix<N, M> {
-    type Output = Matrix<N, L>;
-    fn mul(self, rhs: &Matrix<M, L>) -> Self::Output {
-        Matrix::<N, L>::from_fn(|i, j| (0..M).map(|k| self[(i, k)] * rhs[(k, j)]).sum())
+impl<const N: usize, const M: usize, const L: usize, T: Scalar> std::ops::Mul<&Matrix<M, L, T>> for &Matrix<N, M, T> {
+    type Output = Matrix<N, L, T>;
+    fn mul(self, rhs: &Matrix<M, L, T>) -> Self::Output {
+        count_flops(2 * N * M * L);
+        Matrix::<N, L, T>::from_fn(|i, j| (0..M).map(|k| self[(i, k)] * rhs[(k, j)]).sum())
     }
 }
-impl<const N: usize, const M: usize, const L: usize> std::ops::Mul<Matrix<M, L>> for Matrix<N, M> {
-    type Output = Matrix<N, L>;
-    fn mul(self, rhs: Matrix<M, L>) -> Self::Output { &self * &rhs }
+impl<const N: usize, const M: usize, const L: usize, T: Scalar> std::ops::Mul<Matrix<M, L, T>> for Matrix<N, M, T> {
+    type Output = Matrix<N, L, T>;
+    fn mul(self, rhs: Matrix<M, L, T>) -> Self::Output { &self * &rhs }
 }
-impl<const N: usize, const M: usize, const L: usize> std::ops::Mul<&Matrix<M, L>> for Matrix<N, M> {
-    type Output = Matrix<N, L>;
-    fn mul(self, rhs: &Matrix<M, L>) -> Self::Output { &self * rhs }
+impl<const N: usize, const M: usize, const L: usize, T: Scalar> std::ops::Mul<&Matrix<M, L, T>> for Matrix<N, M, T> {
+    type Output = Matrix<N, L, T>;
+    fn mul(self, rhs: &Matrix<M, L, T>) -> Self::Output { &self * rhs }
 }
-impl<const N: usize, const M: usize, const L: usize> std::ops::Mul<Matrix<M, L>> for &Matrix<N, M> {
-    type Output = Matrix<N, L>;
-    fn mul(self, rhs: Matrix<M, L>) -> Self::Output { self * &rhs }
+impl<const N: usize, const M: usize, const L: usize, T: Scalar> std::ops::Mul<Matrix<M, L, T>> for &Matrix<N, M, T> {
+    type Output = Matrix<N, L, T>;
+    fn mul(self, rhs: Matrix<M, L, T>) -> Self::Output { self * &rhs }
 }
 
-impl<const N: usize, const M: usize> std::ops::Mul<f64> for &Matrix<N, M> {
-    type Output = Matrix<N, M>;
-    fn mul(self, rhs: f64) -> Self::Output {
-        Matrix::from_fn(|i, j| self[(i, j)] * rhs)
+impl<const N: usize, const M: usize, T: Scalar> std::ops::Mul<T> for &Matrix<N, M, T> {
+    type Output = Matrix<N, M, T>;
+    fn mul(self, rhs: T) -> Self::Output {
+        self.count_entrywise_flops();
+        self.map(|x| x * rhs)
     }
 }
-impl<const N: usize, const M: usize> std::ops::Mul<f64> for Matrix<N, M> {
-    type Output = Matrix<N, M>;
-    fn mul(self, rhs: f64) -> Self::Output { &self * rhs }
-}
-impl<const N: usize, const M: usize> std::ops::Mul<&Matrix<N, M>> for f64 {
-    type Output = Matrix<N, M>;
-    fn mul(self, rhs: &Matrix<N, M>) -> Self::Output { rhs * self }
+impl<const N: usize, const M: usize, T: Scalar> std::ops::Mul<T> for Matrix<N, M, T> {
+    type Output = Matrix<N, M, T>;
+    fn mul(self, rhs: T) -> Self::Output { &self * rhs }
 }
-impl<const N: usize, const M: usize> std::ops::Mul<Matrix<N, M>> for f64 {
-    type Output = Matrix<N, M>;
-    fn mul(self, rhs: Matrix<N, M>) -> Self::Output { &rhs * self }
+macro_rules! impl_scalar_mul_matrix {
+    ($($t:ty),*) => {$(
+        impl<const N: usize, const M: usize> std::ops::Mul<&Matrix<N, M, $t>> for $t {
+            type Output = Matrix<N, M, $t>;
+            fn mul(self, rhs: &Matrix<N, M, $t>) -> Self::Output { rhs * self }
+        }
+        impl<const N: usize, const M: usize> std::ops::Mul<Matrix<N, M, $t>> for $t {
+            type Output = Matrix<N, M, $t>;
+            fn mul(self, rhs: Matrix<N, M, $t>) -> Self::Output { &rhs * self }
+        }
+    )*};
 }
+impl_scalar_mul_matrix! { f32, f64, crate::Complex32, crate::Complex64 }
 
-impl<const N: usize, const M: usize> std::ops::MulAssign<f64> for Matrix<N, M> {
-    fn mul_assign(&mut self, rhs: f64) {
-        (0..N).for_each(|i| (0..M).for_each(|j| self[(i, j)] *= rhs));
+impl<const N: usize, const M: usize, T: Scalar> std::ops::MulAssign<T> for Matrix<N, M, T> {
+    fn mul_assign(&mut self, rhs: T) {
+        self.map_inplace(|x| x * rhs);
+        self.count_entrywise_flops();
     }
 }
 
-impl<const N: usize, const M: usize> std::ops::Div<f64> for &Matrix<N, M> {
-    type Output = Matrix<N, M>;
-    fn div(self, rhs: f64) -> Self::Output {
-        Matrix::from_fn(|i, j| self[(i, j)] / rhs)
+impl<const N: usize, const M: usize, T: Scalar> std::ops::Div<T> for &Matrix<N, M, T> {
+    type Output = Matrix<N, M, T>;
+    fn div(self, rhs: T) -> Self::Output {
+        self.count_entrywise_flops();
+        self.map(|x| x / rhs)
     }
 }
-impl<const N: usize, const M: usize> std::ops::Div<f64> for Matrix<N, M> {
-    type Output = Matrix<N, M>;
-    fn div(self, rhs: f64) -> Self::Output { &self / rhs }
+impl<const N: usize, const M: usize, T: Scalar> std::ops::Div<T> for Matrix<N, M, T> {
+    type Output = Matrix<N, M, T>;
+    fn div(self, rhs: T) -> Self::Output { &self / rhs }
 }
 
-impl<const N: usize, const M: usize> std::ops::DivAssign<f64> for Matrix<N, M> {
-    fn div_assign(&mut self, rhs: f64) {
-        (0..N).for_each(|i| (0..M).for_each(|j| self[(i, j)] /= rhs));
+impl<const N: usize, const M: usize, T: Scalar> std::ops::DivAssign<T> for Matrix<N, M, T> {
+    fn div_assign(&mut self, rhs: T) {
+        self.map_inplace(|x| x / rhs);
+        self.count_entrywise_flops();
     }
 }
@@ -1,12 +1,14 @@
 use plotters::drawing::IntoDrawingArea;
-use plotters::style::{Color, IntoFont, BLUE, RED, WHITE};
+use plotters::style::{Color, IntoFont, Palette, Palette99, BLACK, BLUE, RED, WHITE};
 use plotters::coord::ranged1d::{AsRangedCoord, ValueFormatter};
-use plotters::series::{PointSeries, DashedLineSeries};
-use plotters::prelude::{SVGBackend, Circle, IntoLogRange, BindKeyPoints};
+use plotters::series::{PointSeries, DashedLineSeries, LineSeries};
+use plotters::prelude::{SVGBackend, Circle, PathElement, IntoLogRange, BindKeyPoints};
 
+/// `data` of the trials, e.g. the `ExperimentConfig::trials` stats, on the logarithmic y axis
+/// with their average
 pub struct Plotter {
     pub y_desc: &'static str,
-    pub data: [f64; 100],
+    pub data: Vec<f64>,
 }
 
 impl Plotter {
@@ -14,12 +16,14 @@ impl Plotter {
         let root = SVGBackend::new(&path, (800, 600)).into_drawing_area();
         root.fill(&WHITE)?;
         
+        let trials = self.data.len() as i32;
+        let margin = (trials / 20).max(1);
         let mut chart = plotters::chart::ChartBuilder::on(&root)
             .margin(20)
             .x_label_area_size(60)
             .y_label_area_size(100)
             .build_cartesian_2d(
-                (-5..105).with_key_points(vec![0, 20, 40, 60, 80, 100]),
+                (-margin..trials + margin).with_key_points((0..=5).map(|k| k * trials / 5).collect()),
                 self.derive_y_coord(),
             )?;
         
@@ -33,13 +37,13 @@ impl Plotter {
         
         let average = self.data.iter().copied().sum::<f64>() / (self.data.len() as f64);
         chart.draw_series(DashedLineSeries::new(
-            (-5..105).map(|i| (i, average)),
+            (-margin..trials + margin).map(|i| (i, average)),
             12,
             8,
-            RED.stroke_width(3).into(),
+            RED.stroke_width(3),
         ))?;
         chart.draw_series(PointSeries::<_, _, Circle<(i32, f64), i32>, _>::new(
-            (0..100).map(|i| (i, self.data[i as usize])),
+            (0..trials).map(|i| (i, self.data[i as usize])),
             4,
             BLUE.filled(),
         ))?;
@@ -50,33 +54,7 @@ impl Plotter {
     }
     
     fn derive_y_coord(&self) -> impl AsRangedCoord<Value = f64, CoordDescType: ValueFormatter<f64>> {
-        let min = self.data.iter().copied().min_by(|a, b| a.partial_cmp(b).unwrap()).unwrap();
-        let max = self.data.iter().copied().max_by(|a, b| a.partial_cmp(b).unwrap()).unwrap();
-        
-        assert!(min > 0.0, "expected positive values for plotting in chapter 2");
-        
-        let (min_coef, min_exp) = {
-            let (log10, log10_floor) = (min.log10(), min.log10().floor());
-            (10f64.powf(log10 - log10_floor), log10_floor as i32)
-        };
-        let (max_coef, max_exp) = {
-            let (log10, log10_floor) = (max.log10(), max.log10().floor());
-            (10f64.powf(log10 - log10_floor), log10_floor as i32)
-        };
-        
-        let key_points = if min_exp != max_exp {
-            (min_exp - 1..=max_exp + 1)
-                .map(|e| 10f64.powi(e))
-                .collect::<Vec<_>>()
-        } else {
-            (min_coef.floor() as i32..=max_coef.ceil() as i32)
-                .map(|c| (c as f64) * 10f64.powi(max_exp))
-                .collect::<Vec<_>>()
-        };
-        
-        (*key_points.first().unwrap()..*key_points.last().unwrap())
-            .log_scale()
-            .with_key_points(key_points)
+        derive_log_coord(&self.data)
     }
     
     fn format_y_label(value: &f64) -> String {
@@ -109,3 +87,141 @@ impl Plotter {
         }
     }
 }
+
+/// curve through `points` on the logarithmic y axis and the logarithmic (`log_x`) or linear x axis,
+/// e.g. the log-log L-curve of a regularization parameter sweep
+pub struct CurvePlotter {
+    pub x_desc: &'static str,
+    pub y_desc: &'static str,
+    pub points: Vec<(f64, f64)>,
+    pub log_x: bool,
+}
+
+impl CurvePlotter {
+    pub fn plot_into(self, path: impl AsRef<std::path::Path>) -> Result<(), Box<dyn std::error::Error>> {
+        let xs = self.points.iter().map(|&(x, _)| x).collect::<Vec<_>>();
+        if self.log_x {
+            self.draw_into(path, derive_log_coord(&xs), &Plotter::format_y_label)
+        } else {
+            let min = xs.iter().copied().fold(f64::INFINITY, f64::min);
+            let max = xs.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+            self.draw_into(path, min..max, &|x: &f64| format!("{x:.2}"))
+        }
+    }
+    
+    fn draw_into<X>(
+        self,
+        path: impl AsRef<std::path::Path>,
+        x_coord: X,
+        x_label_formatter: &dyn Fn(&f64) -> String,
+    ) -> Result<(), Box<dyn std::error::Error>>
+    where
+        X: AsRangedCoord<Value = f64, CoordDescType: ValueFormatter<f64>>,
+    {
+        let root = SVGBackend::new(&path, (800, 600)).into_drawing_area();
+        root.fill(&WHITE)?;
+        
+        let ys = self.points.iter().map(|&(_, y)| y).collect::<Vec<_>>();
+        let mut chart = plotters::chart::ChartBuilder::on(&root)
+            .margin(20)
+            .x_label_area_size(60)
+            .y_label_area_size(100)
+            .build_cartesian_2d(x_coord, derive_log_coord(&ys))?;
+        
+        chart.configure_mesh()
+            .x_label_formatter(x_label_formatter)
+            .y_label_formatter(&Plotter::format_y_label)
+            .label_style(("sans-serif", 24).into_font())
+            .axis_desc_style(("sans-serif", 24).into_font())
+            .x_desc(self.x_desc)
+            .y_desc(self.y_desc)
+            .draw()?;
+        
+        chart.draw_series(LineSeries::new(self.points.iter().copied(), RED.stroke_width(2)))?;
+        chart.draw_series(PointSeries::<_, _, Circle<(f64, f64), i32>, _>::new(
+            self.points.iter().copied(),
+            4,
+            BLUE.filled(),
+        ))?;
+        
+        root.present()?;
+        
+        Ok(())
+    }
+}
+
+/// convergence curves of the `histories` (e.g. residual norms) against the iteration,
+/// drawn together for comparing the methods on the same instance
+pub struct ConvergencePlotter {
+    pub y_desc: &'static str,
+    pub histories: Vec<(&'static str, Vec<f64>)>,
+}
+
+impl ConvergencePlotter {
+    pub fn plot_into(self, path: impl AsRef<std::path::Path>) -> Result<(), Box<dyn std::error::Error>> {
+        let root = SVGBackend::new(&path, (800, 600)).into_drawing_area();
+        root.fill(&WHITE)?;
+        
+        let iterations = self.histories.iter().map(|(_, history)| history.len()).max().unwrap_or(1).max(2) - 1;
+        let values = self.histories.iter().flat_map(|(_, history)| history.iter().copied()).collect::<Vec<_>>();
+        let mut chart = plotters::chart::ChartBuilder::on(&root)
+            .margin(20)
+            .x_label_area_size(60)
+            .y_label_area_size(100)
+            .build_cartesian_2d(0..iterations, derive_log_coord(&values))?;
+        
+        chart.configure_mesh()
+            .y_label_formatter(&Plotter::format_y_label)
+            .label_style(("sans-serif", 24).into_font())
+            .axis_desc_style(("sans-serif", 24).into_font())
+            .x_desc("iterations")
+            .y_desc(self.y_desc)
+            .draw()?;
+        
+        for (index, (name, history)) in self.histories.iter().enumerate() {
+            let color = Palette99::pick(index);
+            chart.draw_series(LineSeries::new(history.iter().copied().enumerate(), color.stroke_width(2)))?
+                .label(*name)
+                .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], color.stroke_width(2)));
+        }
+        chart.configure_series_labels()
+            .background_style(WHITE)
+            .border_style(BLACK)
+            .label_font(("sans-serif", 20).into_font())
+            .draw()?;
+        
+        root.present()?;
+        
+        Ok(())
+    }
+}
+
+fn derive_log_coord(data: &[f64]) -> impl AsRangedCoord<Value = f64, CoordDescType: ValueFormatter<f64>> {
+    let min = data.iter().copied().min_by(|a, b| a.partial_cmp(b).unwrap()).unwrap();
+    let max = data.iter().copied().max_by(|a, b| a.partial_cmp(b).unwrap()).unwrap();
+    
+    assert!(min > 0.0, "expected positive values for plotting in chapter 2");
+    
+    let (min_coef, min_exp) = {
+        let (log10, log10_floor) = (min.log10(), min.log10().floor());
+        (10f64.powf(log10 - log10_floor), log10_floor as i32)
+    };
+    let (max_coef, max_exp) = {
+        let (log10, log10_floor) = (max.log10(), max.log10().floor());
+        (10f64.powf(log10 - log10_floor), log10_floor as i32)
+    };
+    
+    let key_points = if min_exp != max_exp {
+        (min_exp - 1..=max_exp + 1)
+            .map(|e| 10f64.powi(e))
+            .collect::<Vec<_>>()
+    } else {
+        (min_coef.floor() as i32..=max_coef.ceil() as i32)
+            .map(|c| (c as f64) * 10f64.powi(max_exp))
+            .collect::<Vec<_>>()
+    };
+    
+    (*key_points.first().unwrap()..*key_points.last().unwrap())
+        .log_scale()
+        .with_key_points(key_points)
+}
@@ -0,0 +1,34 @@
+use crate::{Float, Matrix};
+
+/// Polar decomposition A = U H of a nonsingular A into an orthogonal U
+/// and a symmetric positive definite H, returning `(U, H)`,
+/// by the scaled Newton iteration
+/// 
+/// ```text
+/// X_0 = A,  X_{k+1} = (gamma_k X_k + X_k^{-T} / gamma_k) / 2,
+/// gamma_k = (||X_k^{-1}||_F / ||X_k||_F)^{1/2}
+/// ```
+/// 
+/// converging quadratically to U, and then H = (U^T A + A^T U) / 2.
+/// Returns `None` if A is singular.
+pub fn polar<const N: usize, T: Float>(a: &Matrix<N, N, T>) -> Option<(Matrix<N, N, T>, Matrix<N, N, T>)> {
+    const MAX_ITERATIONS: usize = 100;
+    
+    let two = T::ONE + T::ONE;
+    let mut x = a.clone();
+    for _ in 0..MAX_ITERATIONS {
+        let x_inverse = x.inverse()?;
+        let gamma = (x_inverse.frobenius_norm() / x.frobenius_norm()).sqrt();
+        let next = Matrix::<N, N, T>::from_fn(|i, j| (gamma * x[(i, j)] + x_inverse[(j, i)] / gamma) / two);
+        
+        let change = (&next - &x).frobenius_norm();
+        x = next;
+        if change <= T::TOLERANCE * x.frobenius_norm() {
+            break;
+        }
+    }
+    
+    let h = x.transpose() * a;
+    let h = Matrix::<N, N, T>::from_fn(|i, j| (h[(i, j)] + h[(j, i)]) / two);
+    Some((x, h))
+}
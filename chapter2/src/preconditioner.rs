@@ -0,0 +1,225 @@
+use crate::{Float, Scalar, Vector, SparseMatrix, BandedMatrix, DiagonalMatrix, NotPositiveDefinite};
+
+/// M approximating A, easy to invert, for preconditioned iterative methods
+/// solving M^{-1} A x = M^{-1} b instead of A x = b
+pub trait Preconditioner<const N: usize, T = f64> {
+    /// z = M^{-1} r
+    fn apply(&self, r: &Vector<N, T>) -> Vector<N, T>;
+}
+
+/// M = I, i.e. no preconditioning, as the baseline of the preconditioned methods
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NoPreconditioner;
+
+impl<const N: usize, T: Scalar> Preconditioner<N, T> for NoPreconditioner {
+    fn apply(&self, r: &Vector<N, T>) -> Vector<N, T> {
+        r.clone()
+    }
+}
+
+/// Jacobi preconditioner M = D, the diagonal part of A
+/// (by `DiagonalMatrix::diagonal_part_of`)
+impl<const N: usize, T: Scalar> Preconditioner<N, T> for DiagonalMatrix<N, T> {
+    fn apply(&self, r: &Vector<N, T>) -> Vector<N, T> {
+        self.solve(r).expect("Matrix is singular")
+    }
+}
+
+/// incomplete LU decomposition ILU(0) M = LU, where L (unit lower triangular)
+/// and U (upper triangular) are restricted to the sparsity pattern of A,
+/// i.e. the fill-ins of the Gaussian elimination are dropped
+#[derive(Clone, Debug, PartialEq)]
+pub struct Ilu0<const N: usize, T = f64> {
+    /// L below the diagonal and U on and above it, in the pattern of A
+    factors: SparseMatrix<N, N, T>,
+}
+
+impl<const N: usize, T: Scalar> Ilu0<N, T> {
+    /// IKJ-variant Gaussian elimination updating only the stored entries:
+    /// 
+    /// ```text
+    /// for i = 1, ..., N-1, for stored k < i:
+    ///     a_{ik} <- a_{ik} / a_{kk},
+    ///     a_{ij} <- a_{ij} - a_{ik} a_{kj}  for stored j > k
+    /// ```
+    /// 
+    /// or `None` if some pivot a_{kk} is not stored or vanishes
+    pub fn new(a: &SparseMatrix<N, N, T>) -> Option<Self> {
+        let mut factors = a.clone();
+        for i in 0..N {
+            for p in factors.row_positions(i) {
+                let k = factors.column_index(p);
+                if k >= i {
+                    break;
+                }
+                let pivot = factors.values()[factors.position(k, k)?];
+                if pivot.modulus() <= T::Real::TOLERANCE {
+                    return None;
+                }
+                let l_ik = factors.values()[p] / pivot;
+                factors.values_mut()[p] = l_ik;
+                for q in (p + 1)..factors.row_positions(i).end {
+                    let j = factors.column_index(q);
+                    if let Some(kj) = factors.position(k, j) {
+                        let update = l_ik * factors.values()[kj];
+                        factors.values_mut()[q] -= update;
+                    }
+                }
+            }
+            let ii = factors.position(i, i)?;
+            if factors.values()[ii].modulus() <= T::Real::TOLERANCE {
+                return None;
+            }
+        }
+        Some(Self { factors })
+    }
+    
+    /// ILU(0) of a banded A, which is its exact LU decomposition without pivoting
+    /// since the elimination makes no fill-in outside the band
+    pub fn from_banded(a: &BandedMatrix<N, T>) -> Option<Self> {
+        Self::new(&SparseMatrix::from(a))
+    }
+    
+    /// L with its unit diagonal, in the pattern of the lower triangular part of A
+    pub fn l(&self) -> SparseMatrix<N, N, T> {
+        SparseMatrix::from_triplets(
+            self.factors.triplets().filter(|&(i, j, _)| j < i).chain((0..N).map(|i| (i, i, T::ONE)))
+        )
+    }
+    
+    /// U in the pattern of the upper triangular part of A
+    pub fn u(&self) -> SparseMatrix<N, N, T> {
+        SparseMatrix::from_triplets(self.factors.triplets().filter(|&(i, j, _)| j >= i))
+    }
+}
+
+/// z = U^{-1} L^{-1} r by forward and back substitutions over the stored entries
+impl<const N: usize, T: Scalar> Preconditioner<N, T> for Ilu0<N, T> {
+    fn apply(&self, r: &Vector<N, T>) -> Vector<N, T> {
+        let mut z = r.clone();
+        for i in 0..N {
+            let update = self.factors.row(i).take_while(|&(j, _)| j < i).map(|(j, l_ij)| l_ij * z[j]).sum::<T>();
+            z[i] -= update;
+        }
+        for i in (0..N).rev() {
+            let update = self.factors.row(i).filter(|&(j, _)| j > i).map(|(j, u_ij)| u_ij * z[j]).sum::<T>();
+            z[i] = (z[i] - update) / self.factors.get(i, i);
+        }
+        z
+    }
+}
+
+/// incomplete Cholesky decomposition IC(0) M = L L^T of a symmetric positive definite A,
+/// where L is restricted to the sparsity pattern of the lower triangular part of A
+#[derive(Clone, Debug, PartialEq)]
+pub struct Ic0<const N: usize, T = f64> {
+    l: SparseMatrix<N, N, T>,
+}
+
+impl<const N: usize, T: Float> Ic0<N, T> {
+    /// Cholesky decomposition over the stored entries only:
+    /// 
+    /// ```text
+    /// l_{ik} = (a_{ik} - sum_{m<k} l_{im} l_{km}) / l_{kk}  for stored k < i
+    /// l_{ii} = sqrt(a_{ii} - sum_{m<i} l_{im}^2)
+    /// ```
+    /// 
+    /// failing if some l_{ii}^2 is not positive, which may happen
+    /// even for an SPD A (while never for an M-matrix A).
+    /// Only the lower triangular part of A is read.
+    pub fn new(a: &SparseMatrix<N, N, T>) -> Result<Self, NotPositiveDefinite> {
+        let mut l = SparseMatrix::<N, N, T>::from_triplets(a.triplets().filter(|&(i, j, _)| j <= i));
+        for i in 0..N {
+            for p in l.row_positions(i) {
+                let k = l.column_index(p);
+                let sum = l.row_positions(k)
+                    .map(|q| (l.column_index(q), l.values()[q]))
+                    .take_while(|&(m, _)| m < k)
+                    .filter_map(|(m, l_km)| l.position(i, m).map(|im| l.values()[im] * l_km))
+                    .sum::<T>();
+                if k < i {
+                    let l_kk = l.get(k, k);
+                    l.values_mut()[p] = (l.values()[p] - sum) / l_kk;
+                } else {
+                    let pivot = l.values()[p] - sum;
+                    if pivot.is_nan() || pivot <= T::ZERO {
+                        return Err(NotPositiveDefinite { column: i });
+                    }
+                    l.values_mut()[p] = pivot.sqrt();
+                }
+            }
+            if l.position(i, i).is_none() {
+                return Err(NotPositiveDefinite { column: i });
+            }
+        }
+        Ok(Self { l })
+    }
+    
+    /// L in the pattern of the lower triangular part of A
+    pub fn l(&self) -> &SparseMatrix<N, N, T> {
+        &self.l
+    }
+}
+
+/// z = L^{-T} L^{-1} r by forward and back substitutions over the stored entries
+impl<const N: usize, T: Float> Preconditioner<N, T> for Ic0<N, T> {
+    fn apply(&self, r: &Vector<N, T>) -> Vector<N, T> {
+        let mut z = r.clone();
+        for i in 0..N {
+            let update = self.l.row(i).take_while(|&(j, _)| j < i).map(|(j, l_ij)| l_ij * z[j]).sum::<T>();
+            z[i] = (z[i] - update) / self.l.get(i, i);
+        }
+        // L^T z = y column by column, since the rows of L are the columns of L^T
+        for i in (0..N).rev() {
+            z[i] /= self.l.get(i, i);
+            let z_i = z[i];
+            self.l.row(i).take_while(|&(j, _)| j < i).for_each(|(j, l_ij)| z[j] -= l_ij * z_i);
+        }
+        z
+    }
+}
+
+/// symmetric SOR preconditioner of A = L + D + U (L, U strictly lower / upper triangular)
+/// 
+/// ```text
+/// M = ω / (2 - ω) (D / ω + L) (D / ω)^{-1} (D / ω + U)
+/// ```
+/// 
+/// symmetric positive definite for a symmetric positive definite A and 0 < ω < 2
+/// (ω = 1 gives the symmetric Gauss-Seidel one)
+#[derive(Clone, Debug, PartialEq)]
+pub struct Ssor<const N: usize, T = f64> {
+    a: SparseMatrix<N, N, T>,
+    omega: T,
+}
+
+impl<const N: usize, T: Scalar> Ssor<N, T> {
+    /// or `None` if some a_{ii} vanishes
+    pub fn new(a: &SparseMatrix<N, N, T>, omega: T) -> Option<Self> {
+        if (0..N).any(|i| a.get(i, i).modulus() <= T::Real::TOLERANCE) {
+            return None;
+        }
+        Some(Self { a: a.clone(), omega })
+    }
+}
+
+/// z = (2 - ω) / ω (D / ω + U)^{-1} (D / ω) (D / ω + L)^{-1} r
+/// by forward and back substitutions over the stored entries
+impl<const N: usize, T: Scalar> Preconditioner<N, T> for Ssor<N, T> {
+    fn apply(&self, r: &Vector<N, T>) -> Vector<N, T> {
+        let d = |i: usize| self.a.get(i, i) / self.omega;
+        let mut z = r.clone();
+        for i in 0..N {
+            let update = self.a.row(i).take_while(|&(j, _)| j < i).map(|(j, l_ij)| l_ij * z[j]).sum::<T>();
+            z[i] = (z[i] - update) / d(i);
+        }
+        (0..N).for_each(|i| z[i] *= d(i));
+        for i in (0..N).rev() {
+            let update = self.a.row(i).filter(|&(j, _)| j > i).map(|(j, u_ij)| u_ij * z[j]).sum::<T>();
+            z[i] = (z[i] - update) / d(i);
+        }
+        let scale = (T::ONE + T::ONE - self.omega) / self.omega;
+        (0..N).for_each(|i| z[i] *= scale);
+        z
+    }
+}
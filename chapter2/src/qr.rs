@@ -0,0 +1,319 @@
+use crate::{Float, Matrix, Vector};
+
+/// `(v, tau, beta)` of the Householder reflection H = I - tau v v^T mapping x into beta e_0:
+/// 
+/// ```text
+/// beta = -sign(x_0) ||x||,  tau = (beta - x_0) / beta,  v = x / (x_0 - beta)
+/// ```
+/// 
+/// where v_0 = 1, or tau = 0 (H = I) if x is already a multiple of e_0
+pub(crate) fn householder_reflector<T: Float>(x: &[T]) -> (Vec<T>, T, T) {
+    let x0 = x[0];
+    let tail_norm2 = x[1..].iter().map(|&x_i| x_i * x_i).sum::<T>();
+    if tail_norm2 == T::ZERO {
+        let mut v = vec![T::ZERO; x.len()];
+        v[0] = T::ONE;
+        return (v, T::ZERO, x0);
+    }
+    
+    let norm = (x0 * x0 + tail_norm2).sqrt();
+    let beta = if x0 >= T::ZERO { -norm } else { norm };
+    let scale = x0 - beta;
+    let v = std::iter::once(T::ONE).chain(x[1..].iter().map(|&x_i| x_i / scale)).collect();
+    (v, (beta - x0) / beta, beta)
+}
+
+/// QR decomposition A = QR of an N x M matrix A by Householder reflections
+/// H_k = I - tau_k v_k v_k^T, kept as the reflectors without forming Q:
+/// 
+/// ```text
+/// Q = H_0 H_1 ... H_{p-1},  p = min(N, M)
+/// ```
+/// 
+/// in the compact form where R is the upper triangular part of `qr`,
+/// and v_k is 1 at k and the part of `qr` below the diagonal at the k-th column.
+#[derive(Clone)]
+pub struct HouseholderQr<const N: usize, const M: usize, T = f64> {
+    qr: Matrix<N, M, T>,
+    taus: Vec<T>,
+}
+
+impl<const N: usize, const M: usize, T: Float> HouseholderQr<N, M, T> {
+    /// H_k maps the k-th column of the remaining part into beta e_k
+    /// (see `householder_reflector`)
+    pub fn new(a: &Matrix<N, M, T>) -> Self {
+        let mut qr = a.clone();
+        let mut taus = Vec::with_capacity(N.min(M));
+        for k in 0..N.min(M) {
+            let (v, tau, beta) = householder_reflector(&qr.column(k).skip(k).collect::<Vec<_>>());
+            if tau == T::ZERO {
+                taus.push(T::ZERO);
+                continue;
+            }
+            
+            qr[(k, k)] = beta;
+            ((k + 1)..N).for_each(|i| qr[(i, k)] = v[i - k]);
+            
+            for j in (k + 1)..M {
+                let w = (k..N).map(|i| v[i - k] * qr[(i, j)]).sum::<T>();
+                for i in k..N {
+                    let update = tau * w * v[i - k];
+                    qr[(i, j)] -= update;
+                }
+            }
+            taus.push(tau);
+        }
+        Self { qr, taus }
+    }
+    
+    /// B <- H_k B
+    fn reflect<const K: usize>(&self, k: usize, b: &mut Matrix<N, K, T>) {
+        let tau = self.taus[k];
+        if tau == T::ZERO {
+            return;
+        }
+        for j in 0..K {
+            let w = b[(k, j)] + ((k + 1)..N).map(|i| self.qr[(i, k)] * b[(i, j)]).sum::<T>();
+            b[(k, j)] -= tau * w;
+            for i in (k + 1)..N {
+                let update = tau * w * self.qr[(i, k)];
+                b[(i, j)] -= update;
+            }
+        }
+    }
+    
+    /// Q^T B, applying the reflectors without forming Q
+    pub fn apply_qt<const K: usize>(&self, b: &Matrix<N, K, T>) -> Matrix<N, K, T> {
+        let mut b = b.clone();
+        (0..self.taus.len()).for_each(|k| self.reflect(k, &mut b));
+        b
+    }
+    
+    /// Q B, applying the reflectors without forming Q
+    pub fn apply_q<const K: usize>(&self, b: &Matrix<N, K, T>) -> Matrix<N, K, T> {
+        let mut b = b.clone();
+        (0..self.taus.len()).rev().for_each(|k| self.reflect(k, &mut b));
+        b
+    }
+    
+    /// Q formed explicitly (orthogonal N x N)
+    pub fn q(&self) -> Matrix<N, N, T> {
+        self.apply_q(&Matrix::<N, N, T>::identity())
+    }
+    
+    /// R (upper triangular N x M)
+    pub fn r(&self) -> Matrix<N, M, T> {
+        Matrix::<N, M, T>::from_fn(|i, j| if i <= j { self.qr[(i, j)] } else { T::ZERO })
+    }
+    
+    /// least squares solution x minimizing ||b - Ax||_2 for N >= M by
+    /// 
+    /// ```text
+    /// ||b - Ax||_2 = ||Q^T b - R x||_2,  R_1 x = (Q^T b)_{0..M}
+    /// ```
+    /// 
+    /// with R_1 the upper M x M block of R, or `None` if R_1 is singular (A is rank deficient)
+    pub fn solve_least_squares(&self, b: &Vector<N, T>) -> Option<Vector<M, T>> {
+        assert!(N >= M, "Least squares problem must not be underdetermined");
+        let qtb = self.apply_qt(b);
+        let mut x = Vector::<M, T>::zeroed();
+        for i in (0..M).rev() {
+            let r_ii = self.qr[(i, i)];
+            if r_ii.abs() <= T::TOLERANCE {
+                return None;
+            }
+            let sum = ((i + 1)..M).map(|j| self.qr[(i, j)] * x[j]).sum::<T>();
+            x[i] = (qtb[i] - sum) / r_ii;
+        }
+        Some(x)
+    }
+}
+
+impl<const N: usize, const M: usize, T: Float> std::fmt::Debug for HouseholderQr<N, M, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HouseholderQr")
+            .field("qr", &self.qr)
+            .field("taus", &self.taus)
+            .finish()
+    }
+}
+
+/// QR decomposition A = QR by Householder reflections with Q formed explicitly,
+/// returning `(Q, R)`. Use `HouseholderQr` to keep Q as the reflectors.
+pub fn qr_householder<const N: usize, const M: usize, T: Float>(a: &Matrix<N, M, T>) -> (Matrix<N, N, T>, Matrix<N, M, T>) {
+    let qr = HouseholderQr::new(a);
+    (qr.q(), qr.r())
+}
+
+/// Thin QR decomposition A = QR (Q: N x M with orthonormal columns, R: M x M upper triangular)
+/// of an A with linearly independent columns by classical Gram-Schmidt,
+/// projecting the original j-th column onto the previous q_k's:
+/// 
+/// ```text
+/// r_{kj} = q_k^T a_j  (k < j),  q_j = (a_j - sum_{k<j} r_{kj} q_k) / r_{jj}
+/// ```
+pub fn qr_classical_gram_schmidt<const N: usize, const M: usize, T: Float>(a: &Matrix<N, M, T>) -> (Matrix<N, M, T>, Matrix<M, M, T>) {
+    let mut q = a.clone();
+    let mut r = Matrix::<M, M, T>::zeroed();
+    for j in 0..M {
+        for k in 0..j {
+            r[(k, j)] = (0..N).map(|i| q[(i, k)] * a[(i, j)]).sum::<T>();
+        }
+        for k in 0..j {
+            for i in 0..N {
+                let update = r[(k, j)] * q[(i, k)];
+                q[(i, j)] -= update;
+            }
+        }
+        r[(j, j)] = q.column(j).map(|x| x * x).sum::<T>().sqrt();
+        let norm = r[(j, j)];
+        q.column_mut(j).for_each(|x| *x /= norm);
+    }
+    (q, r)
+}
+
+/// `qr_classical_gram_schmidt` but by modified Gram-Schmidt,
+/// projecting the partially orthogonalized j-th column instead:
+/// 
+/// ```text
+/// r_{kj} = q_k^T v_j,  v_j <- v_j - r_{kj} q_k  for k = 0, 1, ..., j-1
+/// ```
+/// 
+/// which loses orthogonality in proportion to cond(A) instead of cond(A)^2
+pub fn qr_modified_gram_schmidt<const N: usize, const M: usize, T: Float>(a: &Matrix<N, M, T>) -> (Matrix<N, M, T>, Matrix<M, M, T>) {
+    let mut q = a.clone();
+    let mut r = Matrix::<M, M, T>::zeroed();
+    for j in 0..M {
+        for k in 0..j {
+            r[(k, j)] = (0..N).map(|i| q[(i, k)] * q[(i, j)]).sum::<T>();
+            for i in 0..N {
+                let update = r[(k, j)] * q[(i, k)];
+                q[(i, j)] -= update;
+            }
+        }
+        r[(j, j)] = q.column(j).map(|x| x * x).sum::<T>().sqrt();
+        let norm = r[(j, j)];
+        q.column_mut(j).for_each(|x| *x /= norm);
+    }
+    (q, r)
+}
+
+/// Givens rotation in the (i, k)-plane, acting on the i-th and k-th entries as
+/// 
+/// ```text
+/// [ c  s] [x_i]
+/// [-s  c] [x_k]
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GivensRotation<T = f64> {
+    pub i: usize,
+    pub k: usize,
+    pub c: T,
+    pub s: T,
+}
+
+impl<T: Float> GivensRotation<T> {
+    /// the rotation mapping (x_i, x_k) = (a, b) into (r, 0):
+    /// 
+    /// ```text
+    /// r = sqrt(a^2 + b^2),  c = a / r,  s = b / r
+    /// ```
+    pub fn zeroing(i: usize, k: usize, a: T, b: T) -> Self {
+        if b == T::ZERO {
+            return Self { i, k, c: T::ONE, s: T::ZERO };
+        }
+        // scaled to avoid overflow in a^2 + b^2
+        let scale = a.abs().max(b.abs());
+        let r = scale * ((a / scale).powi(2) + (b / scale).powi(2)).sqrt();
+        Self { i, k, c: a / r, s: b / r }
+    }
+    
+    pub fn transpose(&self) -> Self {
+        Self { s: -self.s, ..*self }
+    }
+    
+    /// A <- G A on the i-th and k-th rows, touching only the given `columns`
+    /// (e.g. the nonzero ones of a Hessenberg or banded A) in O(len(columns))
+    pub fn apply_left<const N: usize, const M: usize>(&self, a: &mut Matrix<N, M, T>, columns: std::ops::Range<usize>) {
+        for j in columns {
+            let (x_i, x_k) = (a[(self.i, j)], a[(self.k, j)]);
+            a[(self.i, j)] = self.c * x_i + self.s * x_k;
+            a[(self.k, j)] = self.c * x_k - self.s * x_i;
+        }
+    }
+    
+    /// A <- A G^T on the i-th and k-th columns, touching only the given `rows`
+    pub fn apply_right<const N: usize, const M: usize>(&self, a: &mut Matrix<N, M, T>, rows: std::ops::Range<usize>) {
+        for r in rows {
+            let (x_i, x_k) = (a[(r, self.i)], a[(r, self.k)]);
+            a[(r, self.i)] = self.c * x_i + self.s * x_k;
+            a[(r, self.k)] = self.c * x_k - self.s * x_i;
+        }
+    }
+}
+
+/// QR decomposition A = QR by Givens rotations, zeroing the
+/// subdiagonal entries of each column from the bottom with rotations
+/// of adjacent rows and skipping the entries which are already zero,
+/// so that only N - 1 rotations are needed for a Hessenberg A
+/// (and O(N lower) ones for a banded A):
+/// 
+/// ```text
+/// G_m ... G_1 A = R,  Q = G_1^T ... G_m^T
+/// ```
+#[derive(Clone)]
+pub struct GivensQr<const N: usize, const M: usize, T = f64> {
+    r: Matrix<N, M, T>,
+    rotations: Vec<GivensRotation<T>>,
+}
+
+impl<const N: usize, const M: usize, T: Float> GivensQr<N, M, T> {
+    pub fn new(a: &Matrix<N, M, T>) -> Self {
+        let mut r = a.clone();
+        let mut rotations = vec![];
+        for j in 0..N.min(M) {
+            for i in ((j + 1)..N).rev() {
+                if r[(i, j)] == T::ZERO {
+                    continue;
+                }
+                let rotation = GivensRotation::zeroing(i - 1, i, r[(i - 1, j)], r[(i, j)]);
+                rotation.apply_left(&mut r, j..M);
+                r[(i, j)] = T::ZERO;
+                rotations.push(rotation);
+            }
+        }
+        Self { r, rotations }
+    }
+    
+    /// G_1, ..., G_m in the order of application
+    pub fn rotations(&self) -> &[GivensRotation<T>] {
+        &self.rotations
+    }
+    
+    pub fn r(&self) -> &Matrix<N, M, T> {
+        &self.r
+    }
+    
+    /// Q^T B, applying the rotations without forming Q
+    pub fn apply_qt<const K: usize>(&self, b: &Matrix<N, K, T>) -> Matrix<N, K, T> {
+        let mut b = b.clone();
+        self.rotations.iter().for_each(|g| g.apply_left(&mut b, 0..K));
+        b
+    }
+    
+    /// Q formed explicitly (orthogonal N x N)
+    pub fn q(&self) -> Matrix<N, N, T> {
+        let mut q = Matrix::<N, N, T>::identity();
+        self.rotations.iter().rev().for_each(|g| g.transpose().apply_left(&mut q, 0..N));
+        q
+    }
+}
+
+impl<const N: usize, const M: usize, T: Float> std::fmt::Debug for GivensQr<N, M, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GivensQr")
+            .field("r", &self.r)
+            .field("rotations", &self.rotations)
+            .finish()
+    }
+}
@@ -0,0 +1,19 @@
+//! The random number generator behind the random matrix constructors, seedable per thread
+//! to reproduce the random experiments
+
+use rand::{SeedableRng, rngs::StdRng};
+use std::cell::RefCell;
+
+thread_local! {
+    static RNG: RefCell<StdRng> = RefCell::new(StdRng::from_os_rng());
+}
+
+/// restart the random stream of this thread from `seed`,
+/// e.g. by `ExperimentConfig::with_seed` to reproduce the same instances
+pub fn seed_rng(seed: u64) {
+    RNG.with_borrow_mut(|rng| *rng = StdRng::seed_from_u64(seed));
+}
+
+pub(crate) fn with_rng<R>(f: impl FnOnce(&mut StdRng) -> R) -> R {
+    RNG.with_borrow_mut(f)
+}
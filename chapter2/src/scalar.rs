@@ -0,0 +1,116 @@
+/// Scalar type of `Matrix` entries: the field operations plus
+/// conjugation and modulus, implemented for real `f32` / `f64` and
+/// complex `Complex<f32>` / `Complex<f64>`
+pub trait Scalar:
+    Copy
+    + PartialEq
+    + std::fmt::Debug
+    + std::fmt::Display
+    + std::iter::Sum
+    + std::ops::Add<Output = Self>
+    + std::ops::Sub<Output = Self>
+    + std::ops::Mul<Output = Self>
+    + std::ops::Div<Output = Self>
+    + std::ops::Neg<Output = Self>
+    + std::ops::AddAssign
+    + std::ops::SubAssign
+    + std::ops::MulAssign
+    + std::ops::DivAssign
+    + 'static
+{
+    /// type of the modulus, i.e. `Self` itself for real scalars
+    type Real: Float;
+    
+    const ZERO: Self;
+    const ONE: Self;
+    
+    fn from_real(x: Self::Real) -> Self;
+    
+    /// complex conjugate, i.e. identity for real scalars
+    fn conj(self) -> Self;
+    
+    /// |x|, i.e. absolute value for real scalars
+    fn modulus(self) -> Self::Real;
+}
+
+/// Real floating-point scalar type of `Matrix` entries,
+/// implemented for `f32` and `f64` so that the same solvers
+/// can be run in both precisions
+pub trait Float: Scalar<Real = Self> + PartialOrd {
+    const INFINITY: Self;
+    /// difference between 1.0 and the next representable value
+    const MACHINE_EPSILON: Self;
+    /// threshold below which a value is regarded as zero,
+    /// i.e. `crate::EPSILON` for `f64`
+    const TOLERANCE: Self;
+    
+    fn from_f64(x: f64) -> Self;
+    fn to_f64(self) -> f64;
+    
+    fn abs(self) -> Self;
+    fn sqrt(self) -> Self;
+    fn powi(self, n: i32) -> Self;
+    fn powf(self, p: Self) -> Self;
+    fn acos(self) -> Self;
+    fn max(self, other: Self) -> Self;
+    fn min(self, other: Self) -> Self;
+    fn clamp(self, min: Self, max: Self) -> Self;
+    fn signum(self) -> Self;
+    fn is_nan(self) -> bool;
+    fn is_infinite(self) -> bool;
+}
+
+macro_rules! impl_float {
+    ($($t:ident: $tolerance:expr),*) => {$(
+        impl Scalar for $t {
+            type Real = Self;
+            
+            const ZERO: Self = 0.0;
+            const ONE: Self = 1.0;
+            
+            fn from_real(x: Self) -> Self { x }
+            fn conj(self) -> Self { self }
+            fn modulus(self) -> Self { $t::abs(self) }
+        }
+        
+        impl Float for $t {
+            const INFINITY: Self = $t::INFINITY;
+            const MACHINE_EPSILON: Self = $t::EPSILON;
+            const TOLERANCE: Self = $tolerance;
+            
+            fn from_f64(x: f64) -> Self { x as $t }
+            fn to_f64(self) -> f64 { self as f64 }
+            
+            fn abs(self) -> Self { $t::abs(self) }
+            fn sqrt(self) -> Self { $t::sqrt(self) }
+            fn powi(self, n: i32) -> Self { $t::powi(self, n) }
+            fn powf(self, p: Self) -> Self { $t::powf(self, p) }
+            fn acos(self) -> Self { $t::acos(self) }
+            fn max(self, other: Self) -> Self { $t::max(self, other) }
+            fn min(self, other: Self) -> Self { $t::min(self, other) }
+            fn clamp(self, min: Self, max: Self) -> Self { $t::clamp(self, min, max) }
+            fn signum(self) -> Self { $t::signum(self) }
+            fn is_nan(self) -> bool { $t::is_nan(self) }
+            fn is_infinite(self) -> bool { $t::is_infinite(self) }
+        }
+    )*};
+}
+impl_float! { f32: 1e-5, f64: crate::EPSILON }
+
+pub use num_complex::{Complex, Complex32, Complex64};
+
+macro_rules! impl_complex {
+    ($($t:ident)*) => {$(
+        impl Scalar for Complex<$t> {
+            type Real = $t;
+            
+            const ZERO: Self = Complex::new(0.0, 0.0);
+            const ONE: Self = Complex::new(1.0, 0.0);
+            
+            fn from_real(x: $t) -> Self { Complex::new(x, 0.0) }
+            fn conj(self) -> Self { Complex::conj(&self) }
+            fn modulus(self) -> $t { self.norm() }
+        }
+    )*};
+}
+impl_complex! { f32 f64 }
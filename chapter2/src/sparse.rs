@@ -0,0 +1,201 @@
+use crate::{Scalar, Matrix, Vector, BandedMatrix};
+
+/// N-rows and M-columns sparse matrix of `T` (`f64` by default)
+/// in the CSR (compressed sparse row) format:
+/// 
+/// ```text
+/// the nonzeros of the i-th row are values[row_offsets[i]..row_offsets[i + 1]]
+/// at columns column_indices[row_offsets[i]..row_offsets[i + 1]] (ascending)
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct SparseMatrix<const N: usize, const M: usize, T = f64> {
+    row_offsets: Vec<usize>,
+    column_indices: Vec<usize>,
+    values: Vec<T>,
+}
+
+impl<const N: usize, const M: usize, T: Scalar> SparseMatrix<N, M, T> {
+    /// Build from `(i, j, a_{ij})` triplets in any order.
+    /// Duplicated positions are summed up.
+    pub fn from_triplets(triplets: impl IntoIterator<Item = (usize, usize, T)>) -> Self {
+        let mut triplets = triplets.into_iter().collect::<Vec<_>>();
+        assert!(triplets.iter().all(|&(i, j, _)| i < N && j < M), "triplet index out of bounds");
+        triplets.sort_by_key(|&(i, j, _)| (i, j));
+        
+        let mut row_offsets = vec![0; N + 1];
+        let mut column_indices = Vec::with_capacity(triplets.len());
+        let mut values = Vec::<T>::with_capacity(triplets.len());
+        let mut last = None;
+        for (i, j, value) in triplets {
+            if last == Some((i, j)) {
+                *values.last_mut().unwrap() += value;
+            } else {
+                row_offsets[i + 1] += 1;
+                column_indices.push(j);
+                values.push(value);
+                last = Some((i, j));
+            }
+        }
+        (0..N).for_each(|i| row_offsets[i + 1] += row_offsets[i]);
+        
+        Self { row_offsets, column_indices, values }
+    }
+    
+    pub fn zeroed() -> Self {
+        Self { row_offsets: vec![0; N + 1], column_indices: vec![], values: vec![] }
+    }
+    
+    pub fn identity() -> Self {
+        Self::from_triplets((0..N.min(M)).map(|i| (i, i, T::ONE)))
+    }
+    
+    /// number of stored (structurally nonzero) entries
+    pub fn nnz(&self) -> usize {
+        self.values.len()
+    }
+    
+    /// a_{ij}, or zero if not stored
+    pub fn get(&self, i: usize, j: usize) -> T {
+        self.position(i, j).map_or(T::ZERO, |k| self.values[k])
+    }
+    
+    /// index of the stored a_{ij} in `values`
+    pub(crate) fn position(&self, i: usize, j: usize) -> Option<usize> {
+        let range = self.row_positions(i);
+        self.column_indices[range.clone()].binary_search(&j).ok().map(|k| range.start + k)
+    }
+    /// indices of the stored entries of the i-th row in `values`
+    pub(crate) fn row_positions(&self, i: usize) -> std::ops::Range<usize> {
+        self.row_offsets[i]..self.row_offsets[i + 1]
+    }
+    pub(crate) fn column_index(&self, k: usize) -> usize {
+        self.column_indices[k]
+    }
+    pub(crate) fn values(&self) -> &[T] {
+        &self.values
+    }
+    pub(crate) fn values_mut(&mut self) -> &mut [T] {
+        &mut self.values
+    }
+    
+    /// iterate over the stored `(j, a_{ij})` of the i-th row
+    pub fn row(&self, i: usize) -> impl Iterator<Item = (usize, T)> + '_ {
+        let range = self.row_positions(i);
+        std::iter::zip(&self.column_indices[range.clone()], &self.values[range]).map(|(j, x)| (*j, *x))
+    }
+    
+    /// iterate over all stored `(i, j, a_{ij})` in row-major order
+    pub fn triplets(&self) -> impl Iterator<Item = (usize, usize, T)> + '_ {
+        (0..N).flat_map(move |i| self.row(i).map(move |(j, x)| (i, j, x)))
+    }
+    
+    pub fn transpose(&self) -> SparseMatrix<M, N, T> {
+        SparseMatrix::<M, N, T>::from_triplets(self.triplets().map(|(i, j, x)| (j, i, x)))
+    }
+    
+    pub fn to_dense(&self) -> Matrix<N, M, T> {
+        let mut dense = Matrix::<N, M, T>::zeroed();
+        self.triplets().for_each(|(i, j, x)| dense[(i, j)] = x);
+        dense
+    }
+    
+    /// in-place sparse matrix-vector product (SpMV) without allocation:
+    /// 
+    /// ```text
+    /// y <- alpha A x + beta y
+    /// ```
+    /// 
+    /// (`y` is not read when `beta` is 0)
+    pub fn spmv(&self, alpha: T, x: &Vector<M, T>, beta: T, y: &mut Vector<N, T>) {
+        for i in 0..N {
+            let ax_i = self.row(i).map(|(j, a_ij)| a_ij * x[j]).sum::<T>();
+            y[i] = if beta == T::ZERO { alpha * ax_i } else { alpha * ax_i + beta * y[i] };
+        }
+    }
+}
+
+/// COO (coordinate) builder accumulating `(i, j, a_{ij})` entries
+/// one by one, e.g. stencil by stencil for finite-difference operators,
+/// to be converted into a `SparseMatrix` or a dense `Matrix` at once.
+/// Duplicated positions are summed up.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TripletBuilder<const N: usize, const M: usize, T = f64> {
+    triplets: Vec<(usize, usize, T)>,
+}
+
+impl<const N: usize, const M: usize, T: Scalar> TripletBuilder<N, M, T> {
+    pub fn new() -> Self {
+        Self { triplets: vec![] }
+    }
+    
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self { triplets: Vec::with_capacity(capacity) }
+    }
+    
+    /// a_{ij} += value
+    pub fn add(&mut self, i: usize, j: usize, value: T) -> &mut Self {
+        assert!(i < N && j < M, "triplet index out of bounds");
+        self.triplets.push((i, j, value));
+        self
+    }
+    
+    /// number of accumulated entries, counting duplicates
+    pub fn len(&self) -> usize {
+        self.triplets.len()
+    }
+    
+    pub fn is_empty(&self) -> bool {
+        self.triplets.is_empty()
+    }
+    
+    pub fn to_sparse(&self) -> SparseMatrix<N, M, T> {
+        SparseMatrix::<N, M, T>::from_triplets(self.triplets.iter().copied())
+    }
+    
+    pub fn to_dense(&self) -> Matrix<N, M, T> {
+        let mut dense = Matrix::<N, M, T>::zeroed();
+        self.triplets.iter().for_each(|&(i, j, x)| dense[(i, j)] += x);
+        dense
+    }
+}
+
+impl<const N: usize, const M: usize, T: Scalar> Default for TripletBuilder<N, M, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize, const M: usize, T: Scalar> Extend<(usize, usize, T)> for TripletBuilder<N, M, T> {
+    fn extend<I: IntoIterator<Item = (usize, usize, T)>>(&mut self, triplets: I) {
+        triplets.into_iter().for_each(|(i, j, x)| { self.add(i, j, x); });
+    }
+}
+
+impl<const N: usize, const M: usize, T: Scalar> From<TripletBuilder<N, M, T>> for SparseMatrix<N, M, T> {
+    fn from(builder: TripletBuilder<N, M, T>) -> Self {
+        Self::from_triplets(builder.triplets)
+    }
+}
+
+/// Keeps only the nonzero entries
+impl<const N: usize, const M: usize, T: Scalar> From<&Matrix<N, M, T>> for SparseMatrix<N, M, T> {
+    fn from(dense: &Matrix<N, M, T>) -> Self {
+        Self::from_triplets(dense.entries().filter(|&(_, _, x)| x != T::ZERO))
+    }
+}
+
+/// Keeps all the entries inside the band, including zeros
+impl<const N: usize, T: Scalar> From<&BandedMatrix<N, T>> for SparseMatrix<N, N, T> {
+    fn from(banded: &BandedMatrix<N, T>) -> Self {
+        Self::from_triplets((0..N).flat_map(|i| banded.band_columns(i).map(move |j| (i, j, banded[(i, j)]))))
+    }
+}
+
+impl<const N: usize, const M: usize, T: Scalar> std::ops::Mul<&Vector<M, T>> for &SparseMatrix<N, M, T> {
+    type Output = Vector<N, T>;
+    fn mul(self, rhs: &Vector<M, T>) -> Self::Output {
+        let mut y = Vector::<N, T>::zeroed();
+        self.spmv(T::ONE, rhs, T::ZERO, &mut y);
+        y
+    }
+}
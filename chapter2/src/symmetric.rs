@@ -0,0 +1,115 @@
+use crate::{Float, Scalar, Matrix, Vector};
+
+/// N x N symmetric matrix of `T` (`f64` by default) storing only
+/// the lower triangle, row by row in N (N + 1) / 2 entries:
+/// 
+/// ```text
+/// a_{ij} = a_{ji} at i (i + 1) / 2 + j  for j <= i
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct SymmetricMatrix<const N: usize, T = f64> {
+    lower: Vec<T>,
+}
+
+fn packed_index(i: usize, j: usize) -> usize {
+    let (i, j) = if i >= j { (i, j) } else { (j, i) };
+    i * (i + 1) / 2 + j
+}
+
+impl<const N: usize, T: Scalar> SymmetricMatrix<N, T> {
+    pub fn zeroed() -> Self {
+        Self { lower: vec![T::ZERO; N * (N + 1) / 2] }
+    }
+    
+    pub fn identity() -> Self {
+        let mut identity = Self::zeroed();
+        (0..N).for_each(|i| identity[(i, i)] = T::ONE);
+        identity
+    }
+    
+    /// `f(i, j)` is called only for j <= i
+    pub fn from_fn(mut f: impl FnMut(usize, usize) -> T) -> Self {
+        Self { lower: (0..N).flat_map(|i| (0..=i).map(move |j| (i, j))).map(|(i, j)| f(i, j)).collect() }
+    }
+    
+    /// Take the lower triangle of `dense`, ignoring the upper one
+    pub fn from_lower_part(dense: &Matrix<N, N, T>) -> Self {
+        Self::from_fn(|i, j| dense[(i, j)])
+    }
+    
+    /// (A + A^T) / 2 of a possibly non-symmetric `dense`
+    pub fn symmetrize(dense: &Matrix<N, N, T>) -> Self {
+        let two = T::ONE + T::ONE;
+        Self::from_fn(|i, j| (dense[(i, j)] + dense[(j, i)]) / two)
+    }
+    
+    pub fn to_dense(&self) -> Matrix<N, N, T> {
+        Matrix::<N, N, T>::from_fn(|i, j| self[(i, j)])
+    }
+    
+    /// symmetric rank-1 update:
+    /// 
+    /// ```text
+    /// A <- A + alpha x x^T
+    /// ```
+    pub fn rank_1_update(&mut self, alpha: T, x: &Vector<N, T>) {
+        for i in 0..N {
+            let c = alpha * x[i];
+            (0..=i).for_each(|j| self.lower[packed_index(i, j)] += c * x[j]);
+        }
+    }
+    
+    /// symmetric rank-2 update:
+    /// 
+    /// ```text
+    /// A <- A + alpha (x y^T + y x^T)
+    /// ```
+    pub fn rank_2_update(&mut self, alpha: T, x: &Vector<N, T>, y: &Vector<N, T>) {
+        for i in 0..N {
+            let (c, d) = (alpha * x[i], alpha * y[i]);
+            (0..=i).for_each(|j| self.lower[packed_index(i, j)] += c * y[j] + d * x[j]);
+        }
+    }
+}
+
+/// Fails if `dense` is not symmetric up to `T::Real::TOLERANCE`
+impl<const N: usize, T: Scalar> TryFrom<&Matrix<N, N, T>> for SymmetricMatrix<N, T> {
+    type Error = &'static str;
+    fn try_from(dense: &Matrix<N, N, T>) -> Result<Self, Self::Error> {
+        dense.is_symmetric(T::Real::TOLERANCE)
+            .then(|| Self::from_lower_part(dense))
+            .ok_or("Matrix is not symmetric")
+    }
+}
+
+impl<const N: usize, T: Scalar> std::ops::Index<(usize, usize)> for SymmetricMatrix<N, T> {
+    type Output = T;
+    fn index(&self, (i, j): (usize, usize)) -> &Self::Output {
+        assert!(i < N && j < N, "index out of bounds");
+        &self.lower[packed_index(i, j)]
+    }
+}
+/// Writing a_{ij} also writes a_{ji}
+impl<const N: usize, T: Scalar> std::ops::IndexMut<(usize, usize)> for SymmetricMatrix<N, T> {
+    fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut Self::Output {
+        assert!(i < N && j < N, "index out of bounds");
+        &mut self.lower[packed_index(i, j)]
+    }
+}
+
+/// matrix-vector product reading each stored entry once
+impl<const N: usize, T: Scalar> std::ops::Mul<&Vector<N, T>> for &SymmetricMatrix<N, T> {
+    type Output = Vector<N, T>;
+    fn mul(self, rhs: &Vector<N, T>) -> Self::Output {
+        let mut y = Vector::<N, T>::zeroed();
+        for i in 0..N {
+            for j in 0..i {
+                let a_ij = self.lower[packed_index(i, j)];
+                y[i] += a_ij * rhs[j];
+                y[j] += a_ij * rhs[i];
+            }
+            y[i] += self.lower[packed_index(i, i)] * rhs[i];
+        }
+        y
+    }
+}
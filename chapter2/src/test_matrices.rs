@@ -0,0 +1,109 @@
+//! Classic structured test matrices to contrast with random instances
+
+use crate::{Matrix, Vector, SparseMatrix, TripletBuilder};
+
+/// Hilbert matrix, notoriously ill-conditioned:
+/// 
+/// ```text
+/// h_{ij} = 1 / (i + j + 1)
+/// ```
+pub fn hilbert<const N: usize>() -> Matrix<N, N> {
+    Matrix::<N, N>::from_fn(|i, j| 1.0 / ((i + j + 1) as f64))
+}
+
+/// Vandermonde matrix of the nodes `xs`:
+/// 
+/// ```text
+/// v_{ij} = x_i^j
+/// ```
+/// 
+/// M < N gives the coefficient matrix of polynomial fitting of degree M - 1.
+pub fn vandermonde<const N: usize, const M: usize>(xs: &Vector<N>) -> Matrix<N, M> {
+    Matrix::<N, M>::from_fn(|i, j| xs[i].powi(j as i32))
+}
+
+/// Tridiagonal Toeplitz matrix with `a` on the subdiagonal,
+/// `b` on the diagonal, and `c` on the superdiagonal
+pub fn tridiagonal<const N: usize>(a: f64, b: f64, c: f64) -> Matrix<N, N> {
+    Matrix::<N, N>::from_fn(|i, j| {
+        if i == j {
+            b
+        } else if i == j + 1 {
+            a
+        } else if i + 1 == j {
+            c
+        } else {
+            0.0
+        }
+    })
+}
+
+/// model Poisson problem -Δu = f on the unit square with the zero Dirichlet condition,
+/// discretized by the 5-point stencil on the K x K interior grid (N = K^2, row by row):
+/// 
+/// ```text
+/// 4 u_{p,q} - u_{p-1,q} - u_{p+1,q} - u_{p,q-1} - u_{p,q+1}  (scaled by h^2, h = 1 / (K + 1))
+/// ```
+/// 
+/// whose Jacobi iteration matrix has ρ_J = cos(π h).
+pub fn poisson_2d<const N: usize>() -> Matrix<N, N> {
+    let k = (N as f64).sqrt().round() as usize;
+    assert_eq!(k * k, N, "N must be a square number");
+    Matrix::<N, N>::from_fn(|i, j| {
+        let ((p, q), (r, s)) = ((i / k, i % k), (j / k, j % k));
+        if i == j {
+            4.0
+        } else if p.abs_diff(r) + q.abs_diff(s) == 1 {
+            -1.0
+        } else {
+            0.0
+        }
+    })
+}
+
+/// `poisson_2d` assembled as a `SparseMatrix` of the 5 nonzeros per row,
+/// for N too large for the dense one, with the eigenvalues
+/// 
+/// ```text
+/// λ_{pq} = 4 - 2 cos(p π h) - 2 cos(q π h)  (p, q = 1, ..., K)
+/// ```
+/// 
+/// in [4 - 4 cos(π h), 4 + 4 cos(π h)]
+pub fn poisson_2d_sparse<const N: usize>() -> SparseMatrix<N, N> {
+    let k = (N as f64).sqrt().round() as usize;
+    assert_eq!(k * k, N, "N must be a square number");
+    let mut builder = TripletBuilder::<N, N>::with_capacity(5 * N);
+    for i in 0..N {
+        let (p, q) = (i / k, i % k);
+        builder.add(i, i, 4.0);
+        if p > 0 { builder.add(i, i - k, -1.0); }
+        if p + 1 < k { builder.add(i, i + k, -1.0); }
+        if q > 0 { builder.add(i, i - 1, -1.0); }
+        if q + 1 < k { builder.add(i, i + 1, -1.0); }
+    }
+    builder.to_sparse()
+}
+
+/// Wilkinson's eigenvalue test matrix W_N^+: symmetric tridiagonal with
+/// 
+/// ```text
+/// w_{ii} = |i - (N - 1) / 2|,  w_{i,i+1} = w_{i+1,i} = 1
+/// ```
+/// 
+/// whose largest eigenvalues come in nearly equal pairs.
+pub fn wilkinson<const N: usize>() -> Matrix<N, N> {
+    let mut w = tridiagonal::<N>(1.0, 0.0, 1.0);
+    (0..N).for_each(|i| w[(i, i)] = (i as f64 - (N as f64 - 1.0) / 2.0).abs());
+    w
+}
+
+/// Wilkinson's matrix for which Gaussian elimination with partial pivoting
+/// attains the largest possible growth factor 2^{N-1} in the last column,
+/// while complete pivoting keeps it small:
+/// 
+/// ```text
+/// a_{ii} = a_{i,N-1} = 1,  a_{ij} = -1 (i > j),  0 otherwise
+/// ```
+pub fn wilkinson_growth<const N: usize>() -> Matrix<N, N> {
+    Matrix::<N, N>::from_fn(|i, j| if i == j || j == N - 1 { 1.0 } else if i > j { -1.0 } else { 0.0 })
+}
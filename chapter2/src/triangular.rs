@@ -0,0 +1,90 @@
+use crate::{Float, Scalar, Matrix};
+
+/// N x N lower triangular matrix, triangular by construction so that
+/// `forward_substitution` doesn't have to scan for it on every call
+#[derive(Clone)]
+pub struct LowerTriangular<const N: usize, T = f64>(Matrix<N, N, T>);
+
+/// N x N upper triangular matrix, triangular by construction so that
+/// `back_substitution` doesn't have to scan for it on every call
+#[derive(Clone)]
+pub struct UpperTriangular<const N: usize, T = f64>(Matrix<N, N, T>);
+
+impl<const N: usize, T: Scalar> LowerTriangular<N, T> {
+    /// Take the lower triangular part of `matrix`,
+    /// setting the entries above the diagonal to zero
+    pub fn from_lower_part(mut matrix: Matrix<N, N, T>) -> Self {
+        (1..N).for_each(|j| matrix.column_mut(j).take(j).for_each(|it| *it = T::ZERO));
+        Self(matrix)
+    }
+    
+    pub fn into_matrix(self) -> Matrix<N, N, T> {
+        self.0
+    }
+    
+    pub fn transpose(&self) -> UpperTriangular<N, T> {
+        UpperTriangular(self.0.transpose())
+    }
+}
+
+impl<const N: usize, T: Scalar> UpperTriangular<N, T> {
+    /// Take the upper triangular part of `matrix`,
+    /// setting the entries below the diagonal to zero
+    pub fn from_upper_part(mut matrix: Matrix<N, N, T>) -> Self {
+        (0..N).for_each(|j| matrix.column_mut(j).skip(j + 1).for_each(|it| *it = T::ZERO));
+        Self(matrix)
+    }
+    
+    pub fn into_matrix(self) -> Matrix<N, N, T> {
+        self.0
+    }
+    
+    pub fn transpose(&self) -> LowerTriangular<N, T> {
+        LowerTriangular(self.0.transpose())
+    }
+}
+
+/// Fails if some entry above the diagonal exceeds `T::Real::TOLERANCE` in absolute value
+impl<const N: usize, T: Scalar> TryFrom<Matrix<N, N, T>> for LowerTriangular<N, T> {
+    type Error = &'static str;
+    fn try_from(matrix: Matrix<N, N, T>) -> Result<Self, Self::Error> {
+        (0..N).all(|j| matrix.column(j).take(j).all(|x| x.modulus() < T::Real::TOLERANCE))
+            .then(|| Self::from_lower_part(matrix))
+            .ok_or("Matrix is not lower triangular")
+    }
+}
+/// Fails if some entry below the diagonal exceeds `T::Real::TOLERANCE` in absolute value
+impl<const N: usize, T: Scalar> TryFrom<Matrix<N, N, T>> for UpperTriangular<N, T> {
+    type Error = &'static str;
+    fn try_from(matrix: Matrix<N, N, T>) -> Result<Self, Self::Error> {
+        (0..N).all(|j| matrix.column(j).skip(j + 1).all(|x| x.modulus() < T::Real::TOLERANCE))
+            .then(|| Self::from_upper_part(matrix))
+            .ok_or("Matrix is not upper triangular")
+    }
+}
+
+impl<const N: usize, T: Scalar> std::fmt::Debug for LowerTriangular<N, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+impl<const N: usize, T: Scalar> std::fmt::Debug for UpperTriangular<N, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// read-only access to the underlying matrix, which cannot break the triangularity
+impl<const N: usize, T> std::ops::Deref for LowerTriangular<N, T> {
+    type Target = Matrix<N, N, T>;
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+/// read-only like `LowerTriangular`
+impl<const N: usize, T> std::ops::Deref for UpperTriangular<N, T> {
+    type Target = Matrix<N, N, T>;
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
@@ -0,0 +1,54 @@
+use crate::{Float, Matrix, BandedMatrix};
+use crate::qr::householder_reflector;
+
+/// Reduce a symmetric A to a symmetric tridiagonal T by Householder reflections
+/// H_k zeroing the k-th column below the subdiagonal, returning `(T, Q)` with
+/// 
+/// ```text
+/// A = Q T Q^T,  Q = H_0 H_1 ... H_{N-3}
+/// ```
+/// 
+/// as the preprocessing of the QR and Sturm-sequence eigenvalue methods,
+/// which then work on T in O(N) per step. Only the lower triangular part of A is read.
+pub fn tridiagonalize<const N: usize, T: Float>(a: &Matrix<N, N, T>) -> (BandedMatrix<N, T>, Matrix<N, N, T>) {
+    let mut a = Matrix::<N, N, T>::from_fn(|i, j| if i >= j { a[(i, j)] } else { a[(j, i)] });
+    let mut q = Matrix::<N, N, T>::identity();
+    let two = T::ONE + T::ONE;
+    
+    for k in 0..N.saturating_sub(2) {
+        let (v, tau, beta) = householder_reflector(&a.column(k).skip(k + 1).collect::<Vec<_>>());
+        if tau == T::ZERO {
+            continue;
+        }
+        let s = k + 1;
+        
+        // H A H on the trailing part as a symmetric rank-2 update:
+        // p = tau A v,  w = p - (tau / 2) (p^T v) v,  A <- A - v w^T - w v^T
+        let p = (s..N).map(|i| tau * (s..N).map(|j| a[(i, j)] * v[j - s]).sum::<T>()).collect::<Vec<_>>();
+        let pv = (0..(N - s)).map(|i| p[i] * v[i]).sum::<T>();
+        let w = (0..(N - s)).map(|i| p[i] - tau / two * pv * v[i]).collect::<Vec<_>>();
+        for j in s..N {
+            for i in s..N {
+                let update = v[i - s] * w[j - s] + w[i - s] * v[j - s];
+                a[(i, j)] -= update;
+            }
+        }
+        // the k-th row and column become beta e_{k+1} (and its transpose)
+        (s..N).for_each(|i| {
+            let value = if i == s { beta } else { T::ZERO };
+            a[(i, k)] = value;
+            a[(k, i)] = value;
+        });
+        
+        // Q <- Q H
+        for i in 0..N {
+            let qv = (s..N).map(|j| q[(i, j)] * v[j - s]).sum::<T>();
+            for j in s..N {
+                let update = tau * qv * v[j - s];
+                q[(i, j)] -= update;
+            }
+        }
+    }
+    
+    (BandedMatrix::<N, T>::from_dense(&a, 1, 1), q)
+}